@@ -36,6 +36,8 @@ fn opencv_link() {
     println!("cargo:rustc-link-lib=opencv_videoio");
     println!("cargo:rustc-link-lib=opencv_objdetect");
     println!("cargo:rustc-link-lib=opencv_video");
+    println!("cargo:rustc-link-lib=opencv_dnn");
+    println!("cargo:rustc-link-lib=opencv_ml");
 
     if cfg!(feature = "gpu") {
         println!("cargo:rustc-link-lib=opencv_cudaobjdetect");
@@ -47,6 +49,8 @@ fn main() {
     opencv_config
         .cpp(true)
         .file("native/opencv-wrapper.cc")
+        .file("native/opencv-ml.cc")
+        .file("native/opencv-persistence.cc")
         .file("native/utils.cc")
         .include("native")
         .include(opencv_include())
@@ -73,7 +73,10 @@ fn main() {
 
         let ch = [0, 0];
         let hue = hsv.mix_channels(1, 1, &ch[0] as *const i32, 1);
-        let mask = hsv.in_range(Scalar::new(0, 30, 10, 0), Scalar::new(180, 256, 256, 0));
+        let mask = hsv.in_range(
+            Scalar::new(0.0, 30.0, 10.0, 0.0),
+            Scalar::new(180.0, 256.0, 256.0, 0.0),
+        );
 
         {
             let mut current = selection_status.lock().unwrap();
@@ -32,7 +32,7 @@ fn main() {
         .map(|&r| {
             mat.rectangle_custom(
                 r.scale(1.2),
-                Scalar::new(255, 255, 0, 255),
+                Scalar::new(255.0, 255.0, 0.0, 255.0),
                 10,
                 LineTypes::Line8,
             )
@@ -58,7 +58,7 @@ fn run() -> Result<()> {
     param.group_threshold = 0;
     let mut hog = Hog::with_params(param);
     let detector = SvmDetector::default_people_detector();
-    hog.set_svm_detector(detector);
+    hog.set_svm_detector(detector).unwrap();
 
     for entry in try!(fs::read_dir(Path::new(&dir))) {
         let dir = try!(entry);
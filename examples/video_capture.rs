@@ -33,13 +33,8 @@ fn main() {
 
     create_named_window("Window", WindowFlags::WINDOW_AUTOSIZE);
 
-    loop {
-        let image = if let Some(mat) = cap.read() {
-            mat
-        } else {
-            break;
-        };
-
+    for image in cap.frames() {
+        let image = image.unwrap();
         show_mat("Window", &image);
         wait_key(Delay::Msec(30));
     }
@@ -62,11 +62,16 @@ void cv_gpu_hog_drop(GpuHog* hog) {
     hog = nullptr;
 }
 
-void cv_gpu_hog_set_detector(GpuHog* hog, SvmDetector* detector) {
+bool cv_gpu_hog_set_detector(GpuHog* hog, SvmDetector* detector) {
     CV_GPU_HOG* cv_hog = reinterpret_cast<CV_GPU_HOG*>(hog);
     std::vector<float>* cv_detector =
         reinterpret_cast<std::vector<float>*>(detector);
-    (*cv_hog)->setSVMDetector(*cv_detector);
+    try {
+        (*cv_hog)->setSVMDetector(*cv_detector);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
 }
 
 void cv_gpu_hog_detect(GpuHog* hog, GpuMat* image, VecRect* found) {
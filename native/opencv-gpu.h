@@ -34,7 +34,8 @@ GpuHog* cv_gpu_hog_default();
 GpuHog* cv_gpu_hog_new(Size2i win_size, Size2i block_size,
                        Size2i block_stride, Size2i cell_size, int32_t nbins);
 void cv_gpu_hog_drop(GpuHog*);
-void cv_gpu_hog_set_detector(GpuHog*, SvmDetector*);
+// Returns false if the detector doesn't match the descriptor size.
+bool cv_gpu_hog_set_detector(GpuHog*, SvmDetector*);
 void cv_gpu_hog_detect(GpuHog*, GpuMat*, VecRect*);
 void cv_gpu_hog_detect_with_conf(GpuHog*, GpuMat*, VecRect*, VecDouble*);
 
@@ -0,0 +1,506 @@
+#include "opencv-ml.h"
+#include "opencv-wrapper.h"
+#include "utils.h"
+#include <opencv2/ml.hpp>
+
+typedef cv::Ptr<cv::ml::StatModel> StatModelPtr;
+
+static CStatModel* wrap_model(StatModelPtr model) {
+    return reinterpret_cast<CStatModel*>(new StatModelPtr(model));
+}
+
+template <typename T>
+static T* model_as(CStatModel* model) {
+    return static_cast<T*>(reinterpret_cast<StatModelPtr*>(model)->get());
+}
+
+template <typename T>
+static const T* model_as(const CStatModel* const model) {
+    return static_cast<const T*>(
+        reinterpret_cast<const StatModelPtr*>(model)->get());
+}
+
+template <typename T>
+static CStatModel* load_model(const char* const path) {
+    try {
+        cv::Ptr<T> model = cv::Algorithm::load<T>(path);
+        if (model.empty()) {
+            return nullptr;
+        }
+        return wrap_model(model);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+EXTERN_C_BEGIN
+
+// =============================================================================
+//   TrainData
+// =============================================================================
+CTrainData* cv_ml_train_data_new(const CvMatrix* const samples, int layout,
+                                 const CvMatrix* const responses,
+                                 const CvMatrix* const var_idx,
+                                 const CvMatrix* const sample_idx,
+                                 const CvMatrix* const sample_weights,
+                                 int response_type) {
+    const cv::Mat* cv_samples = reinterpret_cast<const cv::Mat*>(samples);
+    const cv::Mat* cv_responses = reinterpret_cast<const cv::Mat*>(responses);
+    const cv::Mat* cv_var_idx = reinterpret_cast<const cv::Mat*>(var_idx);
+    const cv::Mat* cv_sample_idx = reinterpret_cast<const cv::Mat*>(sample_idx);
+    const cv::Mat* cv_weights =
+        reinterpret_cast<const cv::Mat*>(sample_weights);
+
+    cv::Mat var_type;
+    if (response_type >= 0) {
+        int n_vars =
+            layout == cv::ml::ROW_SAMPLE ? cv_samples->cols : cv_samples->rows;
+        var_type =
+            cv::Mat(n_vars + 1, 1, CV_8U, cv::Scalar(cv::ml::VAR_ORDERED));
+        var_type.at<uchar>(n_vars) = static_cast<uchar>(response_type);
+    }
+
+    try {
+        cv::Ptr<cv::ml::TrainData> data = cv::ml::TrainData::create(
+            *cv_samples, layout, *cv_responses,
+            cv_var_idx ? cv::_InputArray(*cv_var_idx) : cv::noArray(),
+            cv_sample_idx ? cv::_InputArray(*cv_sample_idx) : cv::noArray(),
+            cv_weights ? cv::_InputArray(*cv_weights) : cv::noArray(),
+            var_type.empty() ? cv::noArray() : cv::_InputArray(var_type));
+        if (data.empty()) {
+            return nullptr;
+        }
+        return reinterpret_cast<CTrainData*>(
+            new cv::Ptr<cv::ml::TrainData>(data));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+void cv_ml_train_data_drop(CTrainData* data) {
+    cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<cv::Ptr<cv::ml::TrainData>*>(data);
+    delete cv_data;
+    cv_data = nullptr;
+}
+
+bool cv_ml_train_data_set_train_test_split_ratio(CTrainData* data, double ratio,
+                                                 bool shuffle) {
+    cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<cv::Ptr<cv::ml::TrainData>*>(data);
+    try {
+        (*cv_data)->setTrainTestSplitRatio(ratio, shuffle);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+int cv_ml_train_data_get_n_samples(const CTrainData* const data) {
+    const cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<const cv::Ptr<cv::ml::TrainData>*>(data);
+    return (*cv_data)->getNSamples();
+}
+
+int cv_ml_train_data_get_n_vars(const CTrainData* const data) {
+    const cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<const cv::Ptr<cv::ml::TrainData>*>(data);
+    return (*cv_data)->getNVars();
+}
+
+// =============================================================================
+//   StatModel
+// =============================================================================
+void cv_ml_stat_model_drop(CStatModel* model) {
+    StatModelPtr* cv_model = reinterpret_cast<StatModelPtr*>(model);
+    delete cv_model;
+    cv_model = nullptr;
+}
+
+bool cv_ml_stat_model_train(CStatModel* model, CTrainData* data, int flags) {
+    cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<cv::Ptr<cv::ml::TrainData>*>(data);
+    try {
+        return model_as<cv::ml::StatModel>(model)->train(*cv_data, flags);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_ml_stat_model_predict(CStatModel* model, const CvMatrix* const samples,
+                              CvMatrix* results, int flags) {
+    const cv::Mat* cv_samples = reinterpret_cast<const cv::Mat*>(samples);
+    cv::Mat* cv_results = reinterpret_cast<cv::Mat*>(results);
+    try {
+        model_as<cv::ml::StatModel>(model)->predict(
+            *cv_samples, *cv_results, flags);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_ml_stat_model_calc_error(CStatModel* model, CTrainData* data, bool test,
+                                 float* error) {
+    cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<cv::Ptr<cv::ml::TrainData>*>(data);
+    try {
+        cv::Mat responses;
+        *error = model_as<cv::ml::StatModel>(model)->calcError(
+            *cv_data, test, responses);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_ml_stat_model_save(const CStatModel* const model,
+                           const char* const path) {
+    try {
+        model_as<cv::ml::StatModel>(model)->save(path);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_ml_stat_model_is_trained(const CStatModel* const model) {
+    return model_as<cv::ml::StatModel>(model)->isTrained();
+}
+
+bool cv_ml_stat_model_is_classifier(const CStatModel* const model) {
+    return model_as<cv::ml::StatModel>(model)->isClassifier();
+}
+
+int cv_ml_stat_model_get_var_count(const CStatModel* const model) {
+    return model_as<cv::ml::StatModel>(model)->getVarCount();
+}
+
+// =============================================================================
+//   SVM
+// =============================================================================
+CStatModel* cv_ml_svm_new() {
+    return wrap_model(cv::ml::SVM::create());
+}
+
+CStatModel* cv_ml_svm_load(const char* const path) {
+    return load_model<cv::ml::SVM>(path);
+}
+
+void cv_ml_svm_set_type(CStatModel* model, int svm_type) {
+    model_as<cv::ml::SVM>(model)->setType(svm_type);
+}
+
+void cv_ml_svm_set_kernel(CStatModel* model, int kernel_type) {
+    model_as<cv::ml::SVM>(model)->setKernel(kernel_type);
+}
+
+void cv_ml_svm_set_c(CStatModel* model, double c) {
+    model_as<cv::ml::SVM>(model)->setC(c);
+}
+
+void cv_ml_svm_set_gamma(CStatModel* model, double gamma) {
+    model_as<cv::ml::SVM>(model)->setGamma(gamma);
+}
+
+void cv_ml_svm_set_nu(CStatModel* model, double nu) {
+    model_as<cv::ml::SVM>(model)->setNu(nu);
+}
+
+void cv_ml_svm_set_p(CStatModel* model, double p) {
+    model_as<cv::ml::SVM>(model)->setP(p);
+}
+
+void cv_ml_svm_set_degree(CStatModel* model, double degree) {
+    model_as<cv::ml::SVM>(model)->setDegree(degree);
+}
+
+void cv_ml_svm_set_coef0(CStatModel* model, double coef0) {
+    model_as<cv::ml::SVM>(model)->setCoef0(coef0);
+}
+
+void cv_ml_svm_set_term_criteria(CStatModel* model, CTermCriteria* criteria) {
+    cv::TermCriteria* cv_criteria =
+        reinterpret_cast<cv::TermCriteria*>(criteria);
+    model_as<cv::ml::SVM>(model)->setTermCriteria(*cv_criteria);
+}
+
+int cv_ml_svm_get_type(const CStatModel* const model) {
+    return model_as<cv::ml::SVM>(model)->getType();
+}
+
+int cv_ml_svm_get_kernel_type(const CStatModel* const model) {
+    return model_as<cv::ml::SVM>(model)->getKernelType();
+}
+
+double cv_ml_svm_get_c(const CStatModel* const model) {
+    return model_as<cv::ml::SVM>(model)->getC();
+}
+
+double cv_ml_svm_get_gamma(const CStatModel* const model) {
+    return model_as<cv::ml::SVM>(model)->getGamma();
+}
+
+bool cv_ml_svm_train_auto(CStatModel* model, CTrainData* data, int k_fold,
+                          bool balanced) {
+    cv::Ptr<cv::ml::TrainData>* cv_data =
+        reinterpret_cast<cv::Ptr<cv::ml::TrainData>*>(data);
+    cv::ml::SVM* svm = model_as<cv::ml::SVM>(model);
+    try {
+        return svm->trainAuto(*cv_data, k_fold,
+                              cv::ml::SVM::getDefaultGrid(cv::ml::SVM::C),
+                              cv::ml::SVM::getDefaultGrid(cv::ml::SVM::GAMMA),
+                              cv::ml::SVM::getDefaultGrid(cv::ml::SVM::P),
+                              cv::ml::SVM::getDefaultGrid(cv::ml::SVM::NU),
+                              cv::ml::SVM::getDefaultGrid(cv::ml::SVM::COEF),
+                              cv::ml::SVM::getDefaultGrid(cv::ml::SVM::DEGREE),
+                              balanced);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+CvMatrix* cv_ml_svm_get_support_vectors(const CStatModel* const model) {
+    cv::Mat sv = model_as<cv::ml::SVM>(model)->getSupportVectors();
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(sv));
+}
+
+double cv_ml_svm_get_decision_function(const CStatModel* const model, int i,
+                                       CvMatrix* alpha, CvMatrix* svidx) {
+    cv::Mat* cv_alpha = reinterpret_cast<cv::Mat*>(alpha);
+    cv::Mat* cv_svidx = reinterpret_cast<cv::Mat*>(svidx);
+    return model_as<cv::ml::SVM>(model)->getDecisionFunction(
+        i, *cv_alpha, *cv_svidx);
+}
+
+SvmDetector* cv_ml_svm_to_hog_detector(const CStatModel* const model) {
+    const cv::ml::SVM* svm = model_as<cv::ml::SVM>(model);
+    if (!svm->isTrained() || svm->getKernelType() != cv::ml::SVM::LINEAR) {
+        return nullptr;
+    }
+
+    // A trained linear SVM keeps a single compressed support vector
+    cv::Mat sv = svm->getSupportVectors();
+    if (sv.rows != 1 || sv.type() != CV_32F) {
+        return nullptr;
+    }
+    cv::Mat alpha, svidx;
+    double rho = svm->getDecisionFunction(0, alpha, svidx);
+
+    std::vector<float>* detector = new std::vector<float>(sv.cols + 1);
+    std::copy(sv.ptr<float>(0), sv.ptr<float>(0) + sv.cols, detector->begin());
+    (*detector)[sv.cols] = static_cast<float>(-rho);
+    return reinterpret_cast<SvmDetector*>(detector);
+}
+
+// =============================================================================
+//   KNearest
+// =============================================================================
+CStatModel* cv_ml_knearest_new() {
+    return wrap_model(cv::ml::KNearest::create());
+}
+
+CStatModel* cv_ml_knearest_load(const char* const path) {
+    return load_model<cv::ml::KNearest>(path);
+}
+
+void cv_ml_knearest_set_default_k(CStatModel* model, int k) {
+    model_as<cv::ml::KNearest>(model)->setDefaultK(k);
+}
+
+void cv_ml_knearest_set_is_classifier(CStatModel* model, bool is_classifier) {
+    model_as<cv::ml::KNearest>(model)->setIsClassifier(is_classifier);
+}
+
+void cv_ml_knearest_set_algorithm_type(CStatModel* model, int algorithm) {
+    model_as<cv::ml::KNearest>(model)->setAlgorithmType(algorithm);
+}
+
+bool cv_ml_knearest_find_nearest(CStatModel* model,
+                                 const CvMatrix* const samples, int k,
+                                 CvMatrix* results,
+                                 CvMatrix* neighbor_responses, CvMatrix* dist) {
+    const cv::Mat* cv_samples = reinterpret_cast<const cv::Mat*>(samples);
+    cv::Mat* cv_results = reinterpret_cast<cv::Mat*>(results);
+    cv::Mat* cv_neighbors = reinterpret_cast<cv::Mat*>(neighbor_responses);
+    cv::Mat* cv_dist = reinterpret_cast<cv::Mat*>(dist);
+    try {
+        model_as<cv::ml::KNearest>(model)->findNearest(
+            *cv_samples, k, *cv_results, *cv_neighbors, *cv_dist);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// =============================================================================
+//   DTrees (RTrees, Boost)
+// =============================================================================
+void cv_ml_dtrees_set_max_depth(CStatModel* model, int max_depth) {
+    model_as<cv::ml::DTrees>(model)->setMaxDepth(max_depth);
+}
+
+void cv_ml_dtrees_set_min_sample_count(CStatModel* model, int count) {
+    model_as<cv::ml::DTrees>(model)->setMinSampleCount(count);
+}
+
+CStatModel* cv_ml_rtrees_new() {
+    return wrap_model(cv::ml::RTrees::create());
+}
+
+CStatModel* cv_ml_rtrees_load(const char* const path) {
+    return load_model<cv::ml::RTrees>(path);
+}
+
+void cv_ml_rtrees_set_active_var_count(CStatModel* model, int count) {
+    model_as<cv::ml::RTrees>(model)->setActiveVarCount(count);
+}
+
+void cv_ml_rtrees_set_calculate_var_importance(CStatModel* model, bool value) {
+    model_as<cv::ml::RTrees>(model)->setCalculateVarImportance(value);
+}
+
+void cv_ml_rtrees_set_term_criteria(CStatModel* model,
+                                    CTermCriteria* criteria) {
+    cv::TermCriteria* cv_criteria =
+        reinterpret_cast<cv::TermCriteria*>(criteria);
+    model_as<cv::ml::RTrees>(model)->setTermCriteria(*cv_criteria);
+}
+
+CvMatrix* cv_ml_rtrees_get_var_importance(const CStatModel* const model) {
+    cv::Mat importance = model_as<cv::ml::RTrees>(model)->getVarImportance();
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(importance));
+}
+
+CStatModel* cv_ml_boost_new() {
+    return wrap_model(cv::ml::Boost::create());
+}
+
+CStatModel* cv_ml_boost_load(const char* const path) {
+    return load_model<cv::ml::Boost>(path);
+}
+
+void cv_ml_boost_set_boost_type(CStatModel* model, int boost_type) {
+    model_as<cv::ml::Boost>(model)->setBoostType(boost_type);
+}
+
+void cv_ml_boost_set_weak_count(CStatModel* model, int count) {
+    model_as<cv::ml::Boost>(model)->setWeakCount(count);
+}
+
+void cv_ml_boost_set_weight_trim_rate(CStatModel* model, double rate) {
+    model_as<cv::ml::Boost>(model)->setWeightTrimRate(rate);
+}
+
+// =============================================================================
+//   LogisticRegression
+// =============================================================================
+CStatModel* cv_ml_logistic_regression_new() {
+    return wrap_model(cv::ml::LogisticRegression::create());
+}
+
+CStatModel* cv_ml_logistic_regression_load(const char* const path) {
+    return load_model<cv::ml::LogisticRegression>(path);
+}
+
+void cv_ml_logistic_regression_set_learning_rate(CStatModel* model,
+                                                 double rate) {
+    model_as<cv::ml::LogisticRegression>(model)->setLearningRate(rate);
+}
+
+void cv_ml_logistic_regression_set_iterations(CStatModel* model,
+                                              int iterations) {
+    model_as<cv::ml::LogisticRegression>(model)->setIterations(iterations);
+}
+
+void cv_ml_logistic_regression_set_regularization(CStatModel* model,
+                                                  int regularization) {
+    model_as<cv::ml::LogisticRegression>(model)->setRegularization(
+        regularization);
+}
+
+void cv_ml_logistic_regression_set_train_method(CStatModel* model, int method) {
+    model_as<cv::ml::LogisticRegression>(model)->setTrainMethod(method);
+}
+
+void cv_ml_logistic_regression_set_mini_batch_size(CStatModel* model,
+                                                   int size) {
+    model_as<cv::ml::LogisticRegression>(model)->setMiniBatchSize(size);
+}
+
+void cv_ml_logistic_regression_set_term_criteria(CStatModel* model,
+                                                 CTermCriteria* criteria) {
+    cv::TermCriteria* cv_criteria =
+        reinterpret_cast<cv::TermCriteria*>(criteria);
+    model_as<cv::ml::LogisticRegression>(model)->setTermCriteria(*cv_criteria);
+}
+
+// =============================================================================
+//   NormalBayesClassifier
+// =============================================================================
+CStatModel* cv_ml_normal_bayes_new() {
+    return wrap_model(cv::ml::NormalBayesClassifier::create());
+}
+
+CStatModel* cv_ml_normal_bayes_load(const char* const path) {
+    return load_model<cv::ml::NormalBayesClassifier>(path);
+}
+
+bool cv_ml_normal_bayes_predict_prob(CStatModel* model,
+                                     const CvMatrix* const samples,
+                                     CvMatrix* outputs,
+                                     CvMatrix* output_probs) {
+    const cv::Mat* cv_samples = reinterpret_cast<const cv::Mat*>(samples);
+    cv::Mat* cv_outputs = reinterpret_cast<cv::Mat*>(outputs);
+    cv::Mat* cv_probs = reinterpret_cast<cv::Mat*>(output_probs);
+    try {
+        model_as<cv::ml::NormalBayesClassifier>(model)->predictProb(
+            *cv_samples, *cv_outputs, *cv_probs);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// =============================================================================
+//   ANN_MLP
+// =============================================================================
+CStatModel* cv_ml_ann_mlp_new() {
+    return wrap_model(cv::ml::ANN_MLP::create());
+}
+
+CStatModel* cv_ml_ann_mlp_load(const char* const path) {
+    return load_model<cv::ml::ANN_MLP>(path);
+}
+
+bool cv_ml_ann_mlp_set_layer_sizes(CStatModel* model, const int* const sizes,
+                                   size_t len) {
+    cv::Mat layers(1, static_cast<int>(len), CV_32S);
+    std::copy(sizes, sizes + len, layers.ptr<int>(0));
+    try {
+        model_as<cv::ml::ANN_MLP>(model)->setLayerSizes(layers);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+void cv_ml_ann_mlp_set_activation_function(CStatModel* model, int function,
+                                           double param1, double param2) {
+    model_as<cv::ml::ANN_MLP>(model)->setActivationFunction(
+        function, param1, param2);
+}
+
+void cv_ml_ann_mlp_set_train_method(CStatModel* model, int method,
+                                    double param1, double param2) {
+    model_as<cv::ml::ANN_MLP>(model)->setTrainMethod(method, param1, param2);
+}
+
+void cv_ml_ann_mlp_set_term_criteria(CStatModel* model,
+                                     CTermCriteria* criteria) {
+    cv::TermCriteria* cv_criteria =
+        reinterpret_cast<cv::TermCriteria*>(criteria);
+    model_as<cv::ml::ANN_MLP>(model)->setTermCriteria(*cv_criteria);
+}
+
+EXTERN_C_END
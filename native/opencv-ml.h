@@ -0,0 +1,151 @@
+#ifndef OPENCV_ML_H_
+#define OPENCV_ML_H_
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+#define EXTERN_C_BEGIN extern "C" {
+#define EXTERN_C_END }
+#else
+#define EXTERN_C_BEGIN
+#define EXTERN_C_END
+#endif
+
+#include "opencv-wrapper.h"
+
+EXTERN_C_BEGIN
+
+// =============================================================================
+//   TrainData
+// =============================================================================
+typedef struct _CTrainData CTrainData;
+CTrainData* cv_ml_train_data_new(const CvMatrix* const samples, int layout,
+                                 const CvMatrix* const responses,
+                                 const CvMatrix* const var_idx,
+                                 const CvMatrix* const sample_idx,
+                                 const CvMatrix* const sample_weights,
+                                 int response_type);
+void cv_ml_train_data_drop(CTrainData* data);
+// Returns false if `ratio` isn't within [0, 1].
+bool cv_ml_train_data_set_train_test_split_ratio(CTrainData* data, double ratio,
+                                                 bool shuffle);
+int cv_ml_train_data_get_n_samples(const CTrainData* const data);
+int cv_ml_train_data_get_n_vars(const CTrainData* const data);
+
+// =============================================================================
+//   StatModel
+// =============================================================================
+typedef struct _CStatModel CStatModel;
+void cv_ml_stat_model_drop(CStatModel* model);
+bool cv_ml_stat_model_train(CStatModel* model, CTrainData* data, int flags);
+bool cv_ml_stat_model_predict(CStatModel* model, const CvMatrix* const samples,
+                              CvMatrix* results, int flags);
+bool cv_ml_stat_model_calc_error(CStatModel* model, CTrainData* data, bool test,
+                                 float* error);
+bool cv_ml_stat_model_save(const CStatModel* const model,
+                           const char* const path);
+bool cv_ml_stat_model_is_trained(const CStatModel* const model);
+bool cv_ml_stat_model_is_classifier(const CStatModel* const model);
+int cv_ml_stat_model_get_var_count(const CStatModel* const model);
+
+// =============================================================================
+//   SVM
+// =============================================================================
+CStatModel* cv_ml_svm_new();
+CStatModel* cv_ml_svm_load(const char* const path);
+void cv_ml_svm_set_type(CStatModel* model, int svm_type);
+void cv_ml_svm_set_kernel(CStatModel* model, int kernel_type);
+void cv_ml_svm_set_c(CStatModel* model, double c);
+void cv_ml_svm_set_gamma(CStatModel* model, double gamma);
+void cv_ml_svm_set_nu(CStatModel* model, double nu);
+void cv_ml_svm_set_p(CStatModel* model, double p);
+void cv_ml_svm_set_degree(CStatModel* model, double degree);
+void cv_ml_svm_set_coef0(CStatModel* model, double coef0);
+void cv_ml_svm_set_term_criteria(CStatModel* model, CTermCriteria* criteria);
+int cv_ml_svm_get_type(const CStatModel* const model);
+int cv_ml_svm_get_kernel_type(const CStatModel* const model);
+double cv_ml_svm_get_c(const CStatModel* const model);
+double cv_ml_svm_get_gamma(const CStatModel* const model);
+bool cv_ml_svm_train_auto(CStatModel* model, CTrainData* data, int k_fold,
+                          bool balanced);
+CvMatrix* cv_ml_svm_get_support_vectors(const CStatModel* const model);
+double cv_ml_svm_get_decision_function(const CStatModel* const model, int i,
+                                       CvMatrix* alpha, CvMatrix* svidx);
+SvmDetector* cv_ml_svm_to_hog_detector(const CStatModel* const model);
+
+// =============================================================================
+//   KNearest
+// =============================================================================
+CStatModel* cv_ml_knearest_new();
+CStatModel* cv_ml_knearest_load(const char* const path);
+void cv_ml_knearest_set_default_k(CStatModel* model, int k);
+void cv_ml_knearest_set_is_classifier(CStatModel* model, bool is_classifier);
+void cv_ml_knearest_set_algorithm_type(CStatModel* model, int algorithm);
+bool cv_ml_knearest_find_nearest(CStatModel* model,
+                                 const CvMatrix* const samples, int k,
+                                 CvMatrix* results,
+                                 CvMatrix* neighbor_responses, CvMatrix* dist);
+
+// =============================================================================
+//   DTrees (RTrees, Boost)
+// =============================================================================
+void cv_ml_dtrees_set_max_depth(CStatModel* model, int max_depth);
+void cv_ml_dtrees_set_min_sample_count(CStatModel* model, int count);
+
+CStatModel* cv_ml_rtrees_new();
+CStatModel* cv_ml_rtrees_load(const char* const path);
+void cv_ml_rtrees_set_active_var_count(CStatModel* model, int count);
+void cv_ml_rtrees_set_calculate_var_importance(CStatModel* model, bool value);
+void cv_ml_rtrees_set_term_criteria(CStatModel* model, CTermCriteria* criteria);
+CvMatrix* cv_ml_rtrees_get_var_importance(const CStatModel* const model);
+
+CStatModel* cv_ml_boost_new();
+CStatModel* cv_ml_boost_load(const char* const path);
+void cv_ml_boost_set_boost_type(CStatModel* model, int boost_type);
+void cv_ml_boost_set_weak_count(CStatModel* model, int count);
+void cv_ml_boost_set_weight_trim_rate(CStatModel* model, double rate);
+
+// =============================================================================
+//   LogisticRegression
+// =============================================================================
+CStatModel* cv_ml_logistic_regression_new();
+CStatModel* cv_ml_logistic_regression_load(const char* const path);
+void cv_ml_logistic_regression_set_learning_rate(CStatModel* model,
+                                                 double rate);
+void cv_ml_logistic_regression_set_iterations(CStatModel* model,
+                                              int iterations);
+void cv_ml_logistic_regression_set_regularization(CStatModel* model,
+                                                  int regularization);
+void cv_ml_logistic_regression_set_train_method(CStatModel* model, int method);
+void cv_ml_logistic_regression_set_mini_batch_size(CStatModel* model, int size);
+void cv_ml_logistic_regression_set_term_criteria(CStatModel* model,
+                                                 CTermCriteria* criteria);
+
+// =============================================================================
+//   NormalBayesClassifier
+// =============================================================================
+CStatModel* cv_ml_normal_bayes_new();
+CStatModel* cv_ml_normal_bayes_load(const char* const path);
+bool cv_ml_normal_bayes_predict_prob(CStatModel* model,
+                                     const CvMatrix* const samples,
+                                     CvMatrix* outputs, CvMatrix* output_probs);
+
+// =============================================================================
+//   ANN_MLP
+// =============================================================================
+CStatModel* cv_ml_ann_mlp_new();
+CStatModel* cv_ml_ann_mlp_load(const char* const path);
+// Returns false if OpenCV rejects the layer sizes.
+bool cv_ml_ann_mlp_set_layer_sizes(CStatModel* model, const int* const sizes,
+                                   size_t len);
+void cv_ml_ann_mlp_set_activation_function(CStatModel* model, int function,
+                                           double param1, double param2);
+void cv_ml_ann_mlp_set_train_method(CStatModel* model, int method,
+                                    double param1, double param2);
+void cv_ml_ann_mlp_set_term_criteria(CStatModel* model,
+                                     CTermCriteria* criteria);
+
+EXTERN_C_END
+
+#endif  // OPENCV_ML_H_
@@ -0,0 +1,267 @@
+#include "opencv-persistence.h"
+#include "opencv-wrapper.h"
+#include "utils.h"
+#include <cstdlib>
+#include <cstring>
+#include <opencv2/core.hpp>
+
+static char* string_cxx_to_c(const std::string& s) {
+    char* result = static_cast<char*>(std::malloc(s.size() + 1));
+    std::memcpy(result, s.c_str(), s.size() + 1);
+    return result;
+}
+
+static std::string name_c_to_cxx(const char* const name) {
+    return name ? std::string(name) : std::string();
+}
+
+static CFileNode* wrap_node(const cv::FileNode& node) {
+    if (node.empty()) {
+        return nullptr;
+    }
+    return reinterpret_cast<CFileNode*>(new cv::FileNode(node));
+}
+
+static const cv::FileNode* node_cxx(const CFileNode* const node) {
+    return reinterpret_cast<const cv::FileNode*>(node);
+}
+
+// Keeps the end of the elements next to the position, since the iterators
+// of OpenCV 3 and 4 don't agree on how to query the remaining elements.
+struct FileNodeRange {
+    cv::FileNodeIterator it;
+    cv::FileNodeIterator end;
+};
+
+EXTERN_C_BEGIN
+
+// =============================================================================
+//   FileStorage
+// =============================================================================
+CFileStorage* cv_file_storage_new(const char* const source, int flags) {
+    try {
+        cv::FileStorage* fs = new cv::FileStorage(source, flags);
+        if (!fs->isOpened()) {
+            delete fs;
+            return nullptr;
+        }
+        return reinterpret_cast<CFileStorage*>(fs);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+void cv_file_storage_drop(CFileStorage* fs) {
+    delete reinterpret_cast<cv::FileStorage*>(fs);
+}
+
+bool cv_file_storage_release(CFileStorage* fs) {
+    try {
+        reinterpret_cast<cv::FileStorage*>(fs)->release();
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+char* cv_file_storage_release_and_get_string(CFileStorage* fs) {
+    try {
+        cv::FileStorage* storage = reinterpret_cast<cv::FileStorage*>(fs);
+        return string_cxx_to_c(storage->releaseAndGetString());
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+void cv_string_drop(char* s) {
+    std::free(s);
+}
+
+bool cv_file_storage_write_int(CFileStorage* fs, const char* const name,
+                               int value) {
+    try {
+        cv::write(*reinterpret_cast<cv::FileStorage*>(fs),
+                  name_c_to_cxx(name), value);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_file_storage_write_double(CFileStorage* fs, const char* const name,
+                                  double value) {
+    try {
+        cv::write(*reinterpret_cast<cv::FileStorage*>(fs),
+                  name_c_to_cxx(name), value);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_file_storage_write_string(CFileStorage* fs, const char* const name,
+                                  const char* const value) {
+    try {
+        cv::write(*reinterpret_cast<cv::FileStorage*>(fs),
+                  name_c_to_cxx(name), std::string(value));
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_file_storage_write_mat(CFileStorage* fs, const char* const name,
+                               const CvMatrix* const mat) {
+    try {
+        cv::write(*reinterpret_cast<cv::FileStorage*>(fs), name_c_to_cxx(name),
+                  *reinterpret_cast<const cv::Mat*>(mat));
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_file_storage_start_struct(CFileStorage* fs, const char* const name,
+                                  int flags) {
+    try {
+        reinterpret_cast<cv::FileStorage*>(fs)->startWriteStruct(
+            name_c_to_cxx(name), flags);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_file_storage_end_struct(CFileStorage* fs) {
+    try {
+        reinterpret_cast<cv::FileStorage*>(fs)->endWriteStruct();
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// =============================================================================
+//   FileNode
+// =============================================================================
+CFileNode* cv_file_storage_root(CFileStorage* fs) {
+    return wrap_node(reinterpret_cast<cv::FileStorage*>(fs)->root());
+}
+
+CFileNode* cv_file_node_get(const CFileNode* const node,
+                            const char* const name) {
+    const cv::FileNode* parent = node_cxx(node);
+    if (!parent->isMap()) {
+        return nullptr;
+    }
+    return wrap_node((*parent)[name]);
+}
+
+CFileNode* cv_file_node_at(const CFileNode* const node, size_t index) {
+    const cv::FileNode* parent = node_cxx(node);
+    if ((!parent->isSeq() && !parent->isMap()) || index >= parent->size()) {
+        return nullptr;
+    }
+    // Iterate instead of indexing so that map entries are reachable too.
+    cv::FileNodeIterator it = parent->begin();
+    it += static_cast<int>(index);
+    return wrap_node(*it);
+}
+
+void cv_file_node_drop(CFileNode* node) {
+    delete reinterpret_cast<cv::FileNode*>(node);
+}
+
+CFileNodeIterator* cv_file_node_iterator_new(const CFileNode* const node) {
+    const cv::FileNode* parent = node_cxx(node);
+    if (!parent->isSeq() && !parent->isMap()) {
+        return nullptr;
+    }
+    try {
+        FileNodeRange* range = new FileNodeRange{parent->begin(),
+                                                 parent->end()};
+        return reinterpret_cast<CFileNodeIterator*>(range);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+CFileNode* cv_file_node_iterator_next(CFileNodeIterator* it) {
+    FileNodeRange* range = reinterpret_cast<FileNodeRange*>(it);
+    if (range->it == range->end) {
+        return nullptr;
+    }
+    // Elements holding no value are still returned, unlike with wrap_node.
+    cv::FileNode* node = new cv::FileNode(*range->it);
+    ++range->it;
+    return reinterpret_cast<CFileNode*>(node);
+}
+
+void cv_file_node_iterator_drop(CFileNodeIterator* it) {
+    delete reinterpret_cast<FileNodeRange*>(it);
+}
+
+int cv_file_node_type(const CFileNode* const node) {
+    const cv::FileNode* n = node_cxx(node);
+    if (n->isInt()) {
+        return 1;
+    } else if (n->isReal()) {
+        return 2;
+    } else if (n->isString()) {
+        return 3;
+    } else if (n->isSeq()) {
+        return 4;
+    } else if (n->isMap()) {
+        return 5;
+    }
+    return 0;
+}
+
+size_t cv_file_node_size(const CFileNode* const node) {
+    return node_cxx(node)->size();
+}
+
+char* cv_file_node_name(const CFileNode* const node) {
+    return string_cxx_to_c(node_cxx(node)->name());
+}
+
+bool cv_file_node_read_int(const CFileNode* const node, int* value) {
+    const cv::FileNode* n = node_cxx(node);
+    if (!n->isInt() && !n->isReal()) {
+        return false;
+    }
+    *value = static_cast<int>(*n);
+    return true;
+}
+
+bool cv_file_node_read_double(const CFileNode* const node, double* value) {
+    const cv::FileNode* n = node_cxx(node);
+    if (!n->isInt() && !n->isReal()) {
+        return false;
+    }
+    *value = static_cast<double>(*n);
+    return true;
+}
+
+char* cv_file_node_read_string(const CFileNode* const node) {
+    const cv::FileNode* n = node_cxx(node);
+    if (!n->isString()) {
+        return nullptr;
+    }
+    return string_cxx_to_c(static_cast<std::string>(*n));
+}
+
+bool cv_file_node_read_mat(const CFileNode* const node, CvMatrix* mat) {
+    const cv::FileNode* n = node_cxx(node);
+    if (!n->isMap()) {
+        return false;
+    }
+    try {
+        cv::read(*n, *reinterpret_cast<cv::Mat*>(mat));
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+EXTERN_C_END
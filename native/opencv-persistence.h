@@ -0,0 +1,73 @@
+#ifndef OPENCV_PERSISTENCE_H_
+#define OPENCV_PERSISTENCE_H_
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+#define EXTERN_C_BEGIN extern "C" {
+#define EXTERN_C_END }
+#else
+#define EXTERN_C_BEGIN
+#define EXTERN_C_END
+#endif
+
+#include "opencv-wrapper.h"
+
+EXTERN_C_BEGIN
+
+// =============================================================================
+//   FileStorage
+// =============================================================================
+typedef struct _CFileStorage CFileStorage;
+typedef struct _CFileNode CFileNode;
+typedef struct _CFileNodeIterator CFileNodeIterator;
+
+// Returns nullptr if the storage can't be opened.
+CFileStorage* cv_file_storage_new(const char* const source, int flags);
+void cv_file_storage_drop(CFileStorage* fs);
+bool cv_file_storage_release(CFileStorage* fs);
+// The returned string is allocated with malloc and must be freed with
+// cv_string_drop.
+char* cv_file_storage_release_and_get_string(CFileStorage* fs);
+void cv_string_drop(char* s);
+
+// `name` may be NULL when writing elements of a sequence.
+bool cv_file_storage_write_int(CFileStorage* fs, const char* const name,
+                               int value);
+bool cv_file_storage_write_double(CFileStorage* fs, const char* const name,
+                                  double value);
+bool cv_file_storage_write_string(CFileStorage* fs, const char* const name,
+                                  const char* const value);
+bool cv_file_storage_write_mat(CFileStorage* fs, const char* const name,
+                               const CvMatrix* const mat);
+bool cv_file_storage_start_struct(CFileStorage* fs, const char* const name,
+                                  int flags);
+bool cv_file_storage_end_struct(CFileStorage* fs);
+
+// Node accessors return nullptr if the node doesn't exist.
+CFileNode* cv_file_storage_root(CFileStorage* fs);
+CFileNode* cv_file_node_get(const CFileNode* const node,
+                            const char* const name);
+CFileNode* cv_file_node_at(const CFileNode* const node, size_t index);
+void cv_file_node_drop(CFileNode* node);
+
+// Returns nullptr unless the node is a sequence or a mapping. The iterator
+// must not outlive the storage.
+CFileNodeIterator* cv_file_node_iterator_new(const CFileNode* const node);
+// Returns the current element and advances, or nullptr past the last one.
+CFileNode* cv_file_node_iterator_next(CFileNodeIterator* it);
+void cv_file_node_iterator_drop(CFileNodeIterator* it);
+
+// 0: none, 1: int, 2: real, 3: string, 4: seq, 5: map
+int cv_file_node_type(const CFileNode* const node);
+size_t cv_file_node_size(const CFileNode* const node);
+char* cv_file_node_name(const CFileNode* const node);
+bool cv_file_node_read_int(const CFileNode* const node, int* value);
+bool cv_file_node_read_double(const CFileNode* const node, double* value);
+char* cv_file_node_read_string(const CFileNode* const node);
+bool cv_file_node_read_mat(const CFileNode* const node, CvMatrix* mat);
+
+EXTERN_C_END
+
+#endif  // OPENCV_PERSISTENCE_H_
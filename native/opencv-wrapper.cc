@@ -2,11 +2,17 @@
 #include "utils.h"
 
 #include <opencv2/core.hpp>
+#include <opencv2/dnn.hpp>
 #include <opencv2/highgui.hpp>
+#include <opencv2/imgcodecs.hpp>
 #include <opencv2/imgproc.hpp>
 #include <opencv2/objdetect.hpp>
+#include <opencv2/videoio.hpp>
 #include <opencv2/video/tracking.hpp>
 
+#include <algorithm>
+#include <fstream>
+
 EXTERN_C_BEGIN
 
 // =============================================================================
@@ -27,6 +33,14 @@ CvMatrix* cv_mat_zeros(int rows, int cols, int type) {
     return reinterpret_cast<CvMatrix*>(mat);
 }
 
+CvMatrix* cv_mat_new_nd(int ndims, const int* const sizes, int type) {
+    try {
+        return reinterpret_cast<CvMatrix*>(new cv::Mat(ndims, sizes, type));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
 bool cv_mat_is_valid(CvMatrix* cmat) {
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
     return mat->data != nullptr;
@@ -39,6 +53,130 @@ CvMatrix* cv_mat_roi(CvMatrix* cmat, Rect crect) {
     return reinterpret_cast<CvMatrix*>(dst);
 }
 
+CvMatrix* cv_mat_clone(const CvMatrix* const cmat) {
+    const cv::Mat* mat = reinterpret_cast<const cv::Mat*>(cmat);
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(mat->clone()));
+}
+
+bool cv_mat_copy_to(const CvMatrix* const csrc, CvMatrix* cdst,
+                    const CvMatrix* const cmask) {
+    const cv::Mat* src = reinterpret_cast<const cv::Mat*>(csrc);
+    cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
+    const cv::Mat* mask = reinterpret_cast<const cv::Mat*>(cmask);
+    try {
+        src->copyTo(*dst, mask ? cv::_InputArray(*mask) : cv::noArray());
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
+bool cv_mat_convert_to(const CvMatrix* const csrc, CvMatrix* cdst, int rtype,
+                       double alpha, double beta) {
+    const cv::Mat* src = reinterpret_cast<const cv::Mat*>(csrc);
+    cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
+    try {
+        src->convertTo(*dst, rtype, alpha, beta);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
+bool cv_mat_set_to(CvMatrix* cmat, Scalar value, const CvMatrix* const cmask) {
+    cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
+    const cv::Mat* mask = reinterpret_cast<const cv::Mat*>(cmask);
+    try {
+        mat->setTo(scalar_c_to_cxx(value),
+                   mask ? cv::_InputArray(*mask) : cv::noArray());
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
+CvMatrix* cv_mat_reshape(const CvMatrix* const cmat, int cn, int rows) {
+    const cv::Mat* mat = reinterpret_cast<const cv::Mat*>(cmat);
+    try {
+        return reinterpret_cast<CvMatrix*>(new cv::Mat(mat->reshape(cn, rows)));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+CvMatrix* cv_mat_row_range(const CvMatrix* const cmat, int start, int end) {
+    const cv::Mat* mat = reinterpret_cast<const cv::Mat*>(cmat);
+    if (start < 0 || end > mat->rows || start >= end) {
+        return nullptr;
+    }
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(mat->rowRange(start, end)));
+}
+
+CvMatrix* cv_mat_col_range(const CvMatrix* const cmat, int start, int end) {
+    const cv::Mat* mat = reinterpret_cast<const cv::Mat*>(cmat);
+    if (start < 0 || end > mat->cols || start >= end) {
+        return nullptr;
+    }
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(mat->colRange(start, end)));
+}
+
+static void mats_from_array(const CvMatrix* const* srcs, size_t len,
+                            std::vector<cv::Mat>& mats) {
+    mats.reserve(len);
+    for (size_t i = 0; i < len; i++) {
+        mats.push_back(*reinterpret_cast<const cv::Mat*>(srcs[i]));
+    }
+}
+
+bool cv_hconcat(const CvMatrix* const* srcs, size_t len, CvMatrix* cdst) {
+    cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
+    std::vector<cv::Mat> mats;
+    mats_from_array(srcs, len, mats);
+    try {
+        cv::hconcat(mats, *dst);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
+bool cv_vconcat(const CvMatrix* const* srcs, size_t len, CvMatrix* cdst) {
+    cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
+    std::vector<cv::Mat> mats;
+    mats_from_array(srcs, len, mats);
+    try {
+        cv::vconcat(mats, *dst);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
+bool cv_repeat(const CvMatrix* const csrc, int ny, int nx, CvMatrix* cdst) {
+    const cv::Mat* src = reinterpret_cast<const cv::Mat*>(csrc);
+    cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
+    try {
+        cv::repeat(*src, ny, nx, *dst);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
+bool cv_copy_make_border(const CvMatrix* const csrc, CvMatrix* cdst, int top,
+                         int bottom, int left, int right, int border_type,
+                         Scalar value) {
+    const cv::Mat* src = reinterpret_cast<const cv::Mat*>(csrc);
+    cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
+    try {
+        cv::copyMakeBorder(*src, *dst, top, bottom, left, right, border_type,
+                           scalar_c_to_cxx(value));
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    return true;
+}
+
 void cv_mat_logic_and(CvMatrix* cimage, const CvMatrix* const cmask) {
     cv::Mat* image = reinterpret_cast<cv::Mat*>(cimage);
     const cv::Mat* mask = reinterpret_cast<const cv::Mat*>(cmask);
@@ -51,8 +189,13 @@ void cv_mat_flip(CvMatrix* cimage, int code) {
 }
 
 CvMatrix* cv_imread(const char* const filename, int flags) {
-    cv::Mat* image;
-    *image = cv::imread(filename, flags);
+    cv::Mat* image = new cv::Mat();
+    try {
+        *image = cv::imread(filename, flags);
+    } catch (cv::Exception& e) {
+        delete image;
+        return nullptr;
+    }
     if (image->data == nullptr) {
         // image read failed for whatever reason
         delete image;
@@ -85,6 +228,28 @@ size_t cv_mat_elem_size(const CvMatrix* const cmat) {
     return (reinterpret_cast<const cv::Mat* const>(cmat))->elemSize();
 }
 
+int cv_mat_dims(const CvMatrix* const cmat) {
+    return (reinterpret_cast<const cv::Mat* const>(cmat))->dims;
+}
+
+void cv_mat_sizes(const CvMatrix* const cmat, int* sizes) {
+    const cv::Mat* mat = reinterpret_cast<const cv::Mat*>(cmat);
+    for (int i = 0; i < mat->dims; i++) {
+        sizes[i] = mat->size[i];
+    }
+}
+
+void cv_mat_steps(const CvMatrix* const cmat, size_t* steps) {
+    const cv::Mat* mat = reinterpret_cast<const cv::Mat*>(cmat);
+    for (int i = 0; i < mat->dims; i++) {
+        steps[i] = mat->step[i];
+    }
+}
+
+bool cv_mat_is_continuous(const CvMatrix* const cmat) {
+    return (reinterpret_cast<const cv::Mat* const>(cmat))->isContinuous();
+}
+
 void cv_mat_drop(CvMatrix* cmat) {
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
     delete mat;
@@ -99,15 +264,38 @@ void cv_vec_of_rect_drop(VecRect* v) {
     }
 }
 
+void cv_vec_of_mat_drop(VecMat* v) {
+    if (v->array != nullptr) {
+        free(v->array);
+        v->array = nullptr;
+        v->size = 0;
+    }
+}
+
+void cv_vec_double_drop(VecDouble* v) {
+    if (v->array != nullptr) {
+        free(v->array);
+        v->array = nullptr;
+        v->size = 0;
+    }
+}
+
+void cv_vec_float_drop(VecFloat* v) {
+    if (v->array != nullptr) {
+        free(v->array);
+        v->array = nullptr;
+        v->size = 0;
+    }
+}
+
 // =============================================================================
 //  core array
 // =============================================================================
 void cv_in_range(CvMatrix* cmat, Scalar lowerb, Scalar upperb, CvMatrix* cdst) {
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
-    cv::Scalar lb(lowerb.v0, lowerb.v1, lowerb.v2);
-    cv::Scalar ub(upperb.v0, upperb.v1, upperb.v2);
     cv::Mat* dst = reinterpret_cast<cv::Mat*>(cdst);
-    cv::inRange(*mat, lb, ub, *dst);
+    cv::inRange(
+        *mat, scalar_c_to_cxx(lowerb), scalar_c_to_cxx(upperb), *dst);
 }
 
 void cv_mix_channels(CvMatrix* cmat, size_t nsrcs, CvMatrix* dst, size_t ndsts,
@@ -163,6 +351,417 @@ int cv_count_non_zero(const CvMatrix* const csrc) {
     return cv::countNonZero(*src);
 }
 
+// =============================================================================
+//  Core/Clustering
+// =============================================================================
+bool cv_kmeans(const CvMatrix* const data, int k, CvMatrix* labels,
+               const CTermCriteria* const criteria, int attempts, int flags,
+               CvMatrix* centers, double* compactness) {
+    const cv::Mat* cv_data = reinterpret_cast<const cv::Mat*>(data);
+    cv::Mat* cv_labels = reinterpret_cast<cv::Mat*>(labels);
+    const cv::TermCriteria* cv_criteria =
+        reinterpret_cast<const cv::TermCriteria*>(criteria);
+    cv::Mat* cv_centers = reinterpret_cast<cv::Mat*>(centers);
+    try {
+        *compactness = cv::kmeans(*cv_data, k, *cv_labels, *cv_criteria,
+                                  attempts, flags, *cv_centers);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+CPca* cv_pca_new(const CvMatrix* const data, int flags, int max_components) {
+    const cv::Mat* cv_data = reinterpret_cast<const cv::Mat*>(data);
+    try {
+        cv::PCA* pca =
+            new cv::PCA(*cv_data, cv::noArray(), flags, max_components);
+        return reinterpret_cast<CPca*>(pca);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+CPca* cv_pca_new_with_retained_variance(const CvMatrix* const data, int flags,
+                                        double retained_variance) {
+    const cv::Mat* cv_data = reinterpret_cast<const cv::Mat*>(data);
+    try {
+        cv::PCA* pca =
+            new cv::PCA(*cv_data, cv::noArray(), flags, retained_variance);
+        return reinterpret_cast<CPca*>(pca);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+void cv_pca_drop(CPca* pca) {
+    cv::PCA* cv_pca = reinterpret_cast<cv::PCA*>(pca);
+    delete cv_pca;
+    cv_pca = nullptr;
+}
+
+bool cv_pca_project(const CPca* const pca, const CvMatrix* const vec,
+                    CvMatrix* result) {
+    const cv::PCA* cv_pca = reinterpret_cast<const cv::PCA*>(pca);
+    const cv::Mat* cv_vec = reinterpret_cast<const cv::Mat*>(vec);
+    cv::Mat* cv_result = reinterpret_cast<cv::Mat*>(result);
+    try {
+        cv_pca->project(*cv_vec, *cv_result);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_pca_back_project(const CPca* const pca, const CvMatrix* const vec,
+                         CvMatrix* result) {
+    const cv::PCA* cv_pca = reinterpret_cast<const cv::PCA*>(pca);
+    const cv::Mat* cv_vec = reinterpret_cast<const cv::Mat*>(vec);
+    cv::Mat* cv_result = reinterpret_cast<cv::Mat*>(result);
+    try {
+        cv_pca->backProject(*cv_vec, *cv_result);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+CvMatrix* cv_pca_eigenvectors(const CPca* const pca) {
+    const cv::PCA* cv_pca = reinterpret_cast<const cv::PCA*>(pca);
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(cv_pca->eigenvectors));
+}
+
+CvMatrix* cv_pca_eigenvalues(const CPca* const pca) {
+    const cv::PCA* cv_pca = reinterpret_cast<const cv::PCA*>(pca);
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(cv_pca->eigenvalues));
+}
+
+CvMatrix* cv_pca_mean(const CPca* const pca) {
+    const cv::PCA* cv_pca = reinterpret_cast<const cv::PCA*>(pca);
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(cv_pca->mean));
+}
+
+// =============================================================================
+//  Core/Linear algebra
+// =============================================================================
+bool cv_gemm(const CvMatrix* const src1, const CvMatrix* const src2,
+             double alpha, const CvMatrix* const src3, double beta,
+             CvMatrix* dst, int flags) {
+    const cv::Mat* cv_src1 = reinterpret_cast<const cv::Mat*>(src1);
+    const cv::Mat* cv_src2 = reinterpret_cast<const cv::Mat*>(src2);
+    const cv::Mat* cv_src3 = reinterpret_cast<const cv::Mat*>(src3);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::gemm(*cv_src1, *cv_src2, alpha,
+                 cv_src3 ? cv::_InputArray(*cv_src3) : cv::noArray(), beta,
+                 *cv_dst, flags);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_transpose(const CvMatrix* const src, CvMatrix* dst) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::transpose(*cv_src, *cv_dst);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_invert(const CvMatrix* const src, CvMatrix* dst, int method,
+               double* result) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        *result = cv::invert(*cv_src, *cv_dst, method);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_solve(const CvMatrix* const src1, const CvMatrix* const src2,
+              CvMatrix* dst, int method, bool* solved) {
+    const cv::Mat* cv_src1 = reinterpret_cast<const cv::Mat*>(src1);
+    const cv::Mat* cv_src2 = reinterpret_cast<const cv::Mat*>(src2);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        *solved = cv::solve(*cv_src1, *cv_src2, *cv_dst, method);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_determinant(const CvMatrix* const src, double* determinant) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    try {
+        *determinant = cv::determinant(*cv_src);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_trace(const CvMatrix* const src, double* trace) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    try {
+        *trace = cv::trace(*cv_src)[0];
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_svd_compute(const CvMatrix* const src, CvMatrix* w, CvMatrix* u,
+                    CvMatrix* vt, int flags) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_w = reinterpret_cast<cv::Mat*>(w);
+    cv::Mat* cv_u = reinterpret_cast<cv::Mat*>(u);
+    cv::Mat* cv_vt = reinterpret_cast<cv::Mat*>(vt);
+    try {
+        cv::SVD::compute(*cv_src, *cv_w, *cv_u, *cv_vt, flags);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_eigen(const CvMatrix* const src, CvMatrix* eigenvalues,
+              CvMatrix* eigenvectors) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_values = reinterpret_cast<cv::Mat*>(eigenvalues);
+    cv::Mat* cv_vectors = reinterpret_cast<cv::Mat*>(eigenvectors);
+    try {
+        return cv::eigen(*cv_src, *cv_values, *cv_vectors);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_mul_transposed(const CvMatrix* const src, CvMatrix* dst, bool a_top,
+                       const CvMatrix* const delta, double scale) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    const cv::Mat* cv_delta = reinterpret_cast<const cv::Mat*>(delta);
+    try {
+        cv::mulTransposed(*cv_src, *cv_dst, a_top,
+                          cv_delta ? cv::_InputArray(*cv_delta) : cv::noArray(),
+                          scale);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// =============================================================================
+//  Core/Discrete transforms
+// =============================================================================
+bool cv_dft(const CvMatrix* const src, CvMatrix* dst, int flags,
+            int nonzero_rows) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::dft(*cv_src, *cv_dst, flags, nonzero_rows);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_idft(const CvMatrix* const src, CvMatrix* dst, int flags,
+             int nonzero_rows) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::idft(*cv_src, *cv_dst, flags, nonzero_rows);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_dct(const CvMatrix* const src, CvMatrix* dst, int flags) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::dct(*cv_src, *cv_dst, flags);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_idct(const CvMatrix* const src, CvMatrix* dst, int flags) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::idct(*cv_src, *cv_dst, flags);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+int cv_get_optimal_dft_size(int vecsize) {
+    return cv::getOptimalDFTSize(vecsize);
+}
+
+bool cv_mul_spectrums(const CvMatrix* const a, const CvMatrix* const b,
+                      CvMatrix* dst, int flags, bool conj_b) {
+    const cv::Mat* cv_a = reinterpret_cast<const cv::Mat*>(a);
+    const cv::Mat* cv_b = reinterpret_cast<const cv::Mat*>(b);
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::mulSpectrums(*cv_a, *cv_b, *cv_dst, flags, conj_b);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// =============================================================================
+//  Core/Statistics
+// =============================================================================
+bool cv_mean(const CvMatrix* const src, const CvMatrix* const mask,
+             Scalar* mean) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    const cv::Mat* cv_mask = reinterpret_cast<const cv::Mat*>(mask);
+    try {
+        *mean = scalar_cxx_to_c(cv::mean(
+            *cv_src, cv_mask ? cv::_InputArray(*cv_mask) : cv::noArray()));
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_mean_std_dev(const CvMatrix* const src, const CvMatrix* const mask,
+                     Scalar* mean, Scalar* stddev) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    const cv::Mat* cv_mask = reinterpret_cast<const cv::Mat*>(mask);
+    try {
+        cv::Scalar cv_mean, cv_stddev;
+        cv::meanStdDev(*cv_src, cv_mean, cv_stddev,
+                       cv_mask ? cv::_InputArray(*cv_mask) : cv::noArray());
+        *mean = scalar_cxx_to_c(cv_mean);
+        *stddev = scalar_cxx_to_c(cv_stddev);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_sum(const CvMatrix* const src, Scalar* sum) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    try {
+        *sum = scalar_cxx_to_c(cv::sum(*cv_src));
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_min_max_loc(const CvMatrix* const src, const CvMatrix* const mask,
+                    double* min_val, double* max_val, Point2i* min_loc,
+                    Point2i* max_loc) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    const cv::Mat* cv_mask = reinterpret_cast<const cv::Mat*>(mask);
+    try {
+        cv::Point cv_min_loc, cv_max_loc;
+        cv::minMaxLoc(*cv_src, min_val, max_val, &cv_min_loc, &cv_max_loc,
+                      cv_mask ? cv::_InputArray(*cv_mask) : cv::noArray());
+        min_loc->x = cv_min_loc.x;
+        min_loc->y = cv_min_loc.y;
+        max_loc->x = cv_max_loc.x;
+        max_loc->y = cv_max_loc.y;
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// `min_idx` and `max_idx` must hold CV_MAX_DIM elements, returns the number
+// of dimensions or -1 on failure.
+int cv_min_max_idx(const CvMatrix* const src, const CvMatrix* const mask,
+                   double* min_val, double* max_val, int* min_idx,
+                   int* max_idx) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    const cv::Mat* cv_mask = reinterpret_cast<const cv::Mat*>(mask);
+    try {
+        cv::minMaxIdx(*cv_src, min_val, max_val, min_idx, max_idx,
+                      cv_mask ? cv::_InputArray(*cv_mask) : cv::noArray());
+        return cv_src->dims;
+    } catch (cv::Exception& e) {
+        return -1;
+    }
+}
+
+bool cv_norm(const CvMatrix* const src, int norm_type,
+             const CvMatrix* const mask, double* norm) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    const cv::Mat* cv_mask = reinterpret_cast<const cv::Mat*>(mask);
+    try {
+        *norm = cv::norm(*cv_src, norm_type,
+                         cv_mask ? cv::_InputArray(*cv_mask) : cv::noArray());
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_norm_diff(const CvMatrix* const src1, const CvMatrix* const src2,
+                  int norm_type, const CvMatrix* const mask, double* norm) {
+    const cv::Mat* cv_src1 = reinterpret_cast<const cv::Mat*>(src1);
+    const cv::Mat* cv_src2 = reinterpret_cast<const cv::Mat*>(src2);
+    const cv::Mat* cv_mask = reinterpret_cast<const cv::Mat*>(mask);
+    try {
+        *norm = cv::norm(*cv_src1, *cv_src2, norm_type,
+                         cv_mask ? cv::_InputArray(*cv_mask) : cv::noArray());
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_reduce(const CvMatrix* const src, int dim, int rtype,
+               VecDouble* result) {
+    const cv::Mat* cv_src = reinterpret_cast<const cv::Mat*>(src);
+    try {
+        // Max and min keep the source depth, sums and averages are computed
+        // in double precision to avoid overflows
+        int dtype = (rtype == cv::REDUCE_MAX || rtype == cv::REDUCE_MIN)
+                        ? -1
+                        : CV_MAKETYPE(CV_64F, cv_src->channels());
+        cv::Mat reduced;
+        cv::reduce(*cv_src, reduced, dim, rtype, dtype);
+        reduced.convertTo(reduced, CV_64F);
+        const double* data = reduced.ptr<double>();
+        std::vector<double> values(data,
+                                   data + reduced.total() * reduced.channels());
+        vec_double_cxx_to_c(values, result);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_psnr(const CvMatrix* const src1, const CvMatrix* const src2,
+             double* psnr) {
+    const cv::Mat* cv_src1 = reinterpret_cast<const cv::Mat*>(src1);
+    const cv::Mat* cv_src2 = reinterpret_cast<const cv::Mat*>(src2);
+    try {
+        *psnr = cv::PSNR(*cv_src1, *cv_src2);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
 // =============================================================================
 //  Imgproc
 // =============================================================================
@@ -170,9 +769,7 @@ void cv_rectangle(CvMatrix* cmat, Rect crect, Scalar color, int thickness,
                   int linetype) {
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
     cv::Rect rect(crect.x, crect.y, crect.width, crect.height);
-    cv::rectangle(*mat, rect,
-                  cv::Scalar(color.v0, color.v1, color.v2, color.v3), thickness,
-                  linetype);
+    cv::rectangle(*mat, rect, scalar_c_to_cxx(color), thickness, linetype);
 }
 
 void cv_ellipse(CvMatrix* cmat, Point2i center, Size2i axes, double angle,
@@ -181,12 +778,29 @@ void cv_ellipse(CvMatrix* cmat, Point2i center, Size2i axes, double angle,
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
     cv::Point cv_center(center.x, center.y);
     cv::Size cv_axes(axes.width, axes.height);
-    cv::Scalar cv_color(color.v0, color.v1, color.v2, color.v3);
+    cv::Scalar cv_color = scalar_c_to_cxx(color);
 
     cv::ellipse(*mat, cv_center, cv_axes, angle, start_angle, end_angle,
                 cv_color, thickness, linetype, shift);
 }
 
+bool cv_draw_rotated_rect(CvMatrix* cmat, RotatedRect rect, Scalar color,
+                          int thickness, int linetype) {
+    cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
+    cv::Point2f vertices[4];
+    rotated_rect_c_to_cxx(rect).points(vertices);
+    cv::Scalar cv_color = scalar_c_to_cxx(color);
+    try {
+        for (int i = 0; i < 4; i++) {
+            cv::line(*mat, vertices[i], vertices[(i + 1) % 4], cv_color,
+                     thickness, linetype);
+        }
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
 void cv_cvt_color(CvMatrix* cmat, CvMatrix* output, int code) {
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
     cv::Mat* out = reinterpret_cast<cv::Mat*>(output);
@@ -227,35 +841,281 @@ void cv_calc_back_project(const CvMatrix* cimages, int nimages,
                         ranges);
 }
 
+bool cv_phase_correlate(const CvMatrix* const src1, const CvMatrix* const src2,
+                        const CvMatrix* const window, Point2f* shift,
+                        double* response) {
+    const cv::Mat* cv_src1 = reinterpret_cast<const cv::Mat*>(src1);
+    const cv::Mat* cv_src2 = reinterpret_cast<const cv::Mat*>(src2);
+    const cv::Mat* cv_window = reinterpret_cast<const cv::Mat*>(window);
+    try {
+        cv::Point2d p = cv::phaseCorrelate(
+            *cv_src1, *cv_src2,
+            cv_window ? cv::_InputArray(*cv_window) : cv::noArray(), response);
+        shift->x = static_cast<float>(p.x);
+        shift->y = static_cast<float>(p.y);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_create_hanning_window(CvMatrix* dst, Size2i size, int type) {
+    cv::Mat* cv_dst = reinterpret_cast<cv::Mat*>(dst);
+    try {
+        cv::createHanningWindow(*cv_dst, cv::Size(size.width, size.height),
+                                type);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+int cv_rotated_rectangle_intersection(RotatedRect rect1, RotatedRect rect2,
+                                      Point2f* vertices, size_t* len) {
+    std::vector<cv::Point2f> cv_vertices;
+    int type;
+    try {
+        type = cv::rotatedRectangleIntersection(rotated_rect_c_to_cxx(rect1),
+                                                rotated_rect_c_to_cxx(rect2),
+                                                cv_vertices);
+    } catch (cv::Exception& e) {
+        return -1;
+    }
+    if (cv_vertices.size() > 8) {
+        return -1;
+    }
+    *len = cv_vertices.size();
+    for (size_t i = 0; i < *len; i++) {
+        vertices[i].x = cv_vertices[i].x;
+        vertices[i].y = cv_vertices[i].y;
+    }
+    return type;
+}
+
 // =============================================================================
 //  Imgcodecs
 // =============================================================================
 CvMatrix* cv_imdecode(const uint8_t* const buffer, size_t len, int flag) {
     cv::Mat* dst = new cv::Mat();
     std::vector<uchar> input(buffer, buffer + len);
-    cv::imdecode(cv::Mat(input), flag, dst);
+    try {
+        cv::imdecode(cv::Mat(input), flag, dst);
+    } catch (cv::Exception& e) {
+        // Undecodable input leaves `dst` empty, like unknown formats do.
+        dst->release();
+    }
     return reinterpret_cast<CvMatrix*>(dst);
 }
 
-// The caller is responsible for the allocated buffer
+bool cv_imwrite(const char* const filename, const CvMatrix* const cmat,
+                const int* const params, size_t params_len) {
+    const cv::Mat* image = reinterpret_cast<const cv::Mat*>(cmat);
+    std::vector<int> param_vec(params, params + params_len);
+    try {
+        return cv::imwrite(filename, *image, param_vec);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+// haveImageReader and haveImageWriter were added in OpenCV 3.4.10 and 4.3.0.
+#if CV_VERSION_MAJOR > 4 || (CV_VERSION_MAJOR == 4 && CV_VERSION_MINOR >= 3) \
+    || (CV_VERSION_MAJOR == 3 && CV_VERSION_MINOR == 4                      \
+        && CV_VERSION_REVISION >= 10)
+#define CV_HAVE_IMAGE_QUERIES 1
+#endif
+
+bool cv_have_image_writer(const char* const filename) {
+#ifdef CV_HAVE_IMAGE_QUERIES
+    try {
+        return cv::haveImageWriter(filename);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+#else
+    // Older versions only expose the encoder lookup through imencode, which
+    // fails when no encoder matches the extension.
+    std::string name(filename);
+    size_t dot = name.rfind('.');
+    if (dot == std::string::npos) {
+        return false;
+    }
+    std::vector<uchar> buf;
+    try {
+        return cv::imencode(name.substr(dot), cv::Mat(1, 1, CV_8UC1), buf);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+#endif
+}
+
+bool cv_have_image_reader(const char* const filename) {
+#ifdef CV_HAVE_IMAGE_QUERIES
+    try {
+        return cv::haveImageReader(filename);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+#else
+    // Decoders are picked by signature, which older versions don't let us
+    // query. Every built-in decoder has a matching encoder, so fall back to
+    // the extension of an existing file.
+    std::ifstream file(filename);
+    return file.good() && cv_have_image_writer(filename);
+#endif
+}
+
+static void vec_mat_cxx_to_c(std::vector<cv::Mat>& mats, VecMat* v) {
+    v->size = mats.size();
+    v->array = (CvMatrix**) malloc(mats.size() * sizeof(CvMatrix*));
+    for (size_t i = 0; i < mats.size(); i++) {
+        v->array[i] = reinterpret_cast<CvMatrix*>(new cv::Mat(mats[i]));
+    }
+}
+
+bool cv_imreadmulti(const char* const filename, int flags, VecMat* pages) {
+    std::vector<cv::Mat> mats;
+    try {
+        if (!cv::imreadmulti(filename, mats, flags)) {
+            return false;
+        }
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    vec_mat_cxx_to_c(mats, pages);
+    return true;
+}
+
+// imcount and ranged imreadmulti were added in OpenCV 4.5.4.
+#if CV_VERSION_MAJOR > 4 || (CV_VERSION_MAJOR == 4 && CV_VERSION_MINOR > 5) \
+    || (CV_VERSION_MAJOR == 4 && CV_VERSION_MINOR == 5                     \
+        && CV_VERSION_REVISION >= 4)
+#define CV_HAVE_IMCOUNT 1
+#endif
+
+size_t cv_imcount(const char* const filename, int flags) {
+    try {
+#ifdef CV_HAVE_IMCOUNT
+        return cv::imcount(filename, flags);
+#else
+        std::vector<cv::Mat> mats;
+        cv::imreadmulti(filename, mats, flags);
+        return mats.size();
+#endif
+    } catch (cv::Exception& e) {
+        return 0;
+    }
+}
+
+bool cv_imwritemulti(const char* const filename,
+                     const CvMatrix* const* cmats, size_t len,
+                     const int* const params, size_t params_len) {
+    std::vector<cv::Mat> mats;
+    mats_from_array(cmats, len, mats);
+    std::vector<int> param_vec(params, params + params_len);
+    try {
+        return cv::imwrite(filename, mats, param_vec);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+struct ImagePages {
+    std::string filename;
+    int flags;
+    size_t len;
+    // Without ranged imreadmulti, every page is decoded up front.
+    std::vector<cv::Mat> decoded;
+};
+
+CImagePages* cv_image_pages_new(const char* const filename, int flags) {
+    ImagePages* pages = new ImagePages();
+    pages->filename = filename;
+    pages->flags = flags;
+    try {
+#ifdef CV_HAVE_IMCOUNT
+        pages->len = cv::imcount(filename, flags);
+#else
+        cv::imreadmulti(filename, pages->decoded, flags);
+        pages->len = pages->decoded.size();
+#endif
+    } catch (cv::Exception& e) {
+        delete pages;
+        return nullptr;
+    }
+    if (pages->len == 0) {
+        delete pages;
+        return nullptr;
+    }
+    return reinterpret_cast<CImagePages*>(pages);
+}
+
+void cv_image_pages_drop(CImagePages* cpages) {
+    delete reinterpret_cast<ImagePages*>(cpages);
+}
+
+size_t cv_image_pages_len(const CImagePages* const cpages) {
+    return reinterpret_cast<const ImagePages*>(cpages)->len;
+}
+
+CvMatrix* cv_image_pages_read(CImagePages* cpages, size_t index) {
+    ImagePages* pages = reinterpret_cast<ImagePages*>(cpages);
+    if (index >= pages->len) {
+        return nullptr;
+    }
+#ifdef CV_HAVE_IMCOUNT
+    std::vector<cv::Mat> mats;
+    try {
+        cv::imreadmulti(pages->filename, mats, static_cast<int>(index), 1,
+                        pages->flags);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+    if (mats.empty()) {
+        return nullptr;
+    }
+    return reinterpret_cast<CvMatrix*>(new cv::Mat(mats[0]));
+#else
+    // Pages are handed out once, so release our reference to free memory
+    // while iterating.
+    if (pages->decoded[index].empty()) {
+        return nullptr;
+    }
+    cv::Mat* page = new cv::Mat(pages->decoded[index]);
+    pages->decoded[index].release();
+    return reinterpret_cast<CvMatrix*>(page);
+#endif
+}
+
+// The caller is responsible for freeing the buffer with
+// cv_imencode_result_drop.
 ImencodeResult cv_imencode(const char* const ext, const CvMatrix* const cmat,
                            const int* const flag_ptr, size_t flag_size) {
     const cv::Mat* image = reinterpret_cast<const cv::Mat*>(cmat);
     std::vector<uchar> buf;
     std::vector<int> params(flag_ptr, flag_ptr + flag_size);
-    bool r = cv::imencode(ext, *image, buf, params);
+    ImencodeResult result;
+    result.status = false;
+    result.size = 0;
+    result.buf = nullptr;
+    try {
+        result.status = cv::imencode(ext, *image, buf, params);
+    } catch (cv::Exception& e) {
+        return result;
+    }
 
-    int size = buf.size();
+    size_t size = buf.size();
     uint8_t* buffer = new uint8_t[size];
-    std::copy(buf.begin(), buf.begin() + size, buffer);
-
-    ImencodeResult result;
-    result.status = r;
+    std::copy(buf.begin(), buf.end(), buffer);
     result.size = size;
     result.buf = buffer;
     return result;
 }
 
+void cv_imencode_result_drop(ImencodeResult result) {
+    delete[] result.buf;
+}
+
 // =============================================================================
 //   Highgui: high-level GUI
 // =============================================================================
@@ -292,7 +1152,12 @@ CVideoCapture* cv_videocapture_new(int index) {
 }
 
 CVideoCapture* cv_videocapture_from_file(const char* const filename) {
-    cv::VideoCapture* cap = new cv::VideoCapture(filename);
+    cv::VideoCapture* cap = new cv::VideoCapture();
+    try {
+        cap->open(filename);
+    } catch (cv::Exception& e) {
+        cap->release();
+    }
     return reinterpret_cast<CVideoCapture*>(cap);
 }
 
@@ -302,10 +1167,77 @@ bool cv_videocapture_is_opened(const CVideoCapture* const ccap) {
     return cap->isOpened();
 }
 
-bool cv_videocapture_read(CVideoCapture* ccap, CvMatrix* cmat) {
+bool cv_videocapture_read(CVideoCapture* ccap, CvMatrix* cmat, bool* grabbed) {
     cv::VideoCapture* cap = reinterpret_cast<cv::VideoCapture*>(ccap);
     cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
-    return cap->read(*mat);
+    try {
+        *grabbed = cap->read(*mat);
+        return true;
+    } catch (cv::Exception& e) {
+        *grabbed = false;
+        return false;
+    }
+}
+
+bool cv_videocapture_grab(CVideoCapture* ccap, bool* grabbed) {
+    cv::VideoCapture* cap = reinterpret_cast<cv::VideoCapture*>(ccap);
+    try {
+        *grabbed = cap->grab();
+        return true;
+    } catch (cv::Exception& e) {
+        *grabbed = false;
+        return false;
+    }
+}
+
+bool cv_videocapture_retrieve(CVideoCapture* ccap, CvMatrix* cmat, int flag,
+                              bool* retrieved) {
+    cv::VideoCapture* cap = reinterpret_cast<cv::VideoCapture*>(ccap);
+    cv::Mat* mat = reinterpret_cast<cv::Mat*>(cmat);
+    try {
+        *retrieved = cap->retrieve(*mat, flag);
+        return true;
+    } catch (cv::Exception& e) {
+        *retrieved = false;
+        return false;
+    }
+}
+
+// VideoCapture::waitAny was added in OpenCV 4.1.0.
+#if CV_VERSION_MAJOR > 4 || (CV_VERSION_MAJOR == 4 && CV_VERSION_MINOR >= 1)
+#define CV_HAVE_WAIT_ANY 1
+#endif
+
+bool cv_videocapture_have_wait_any() {
+#ifdef CV_HAVE_WAIT_ANY
+    return true;
+#else
+    return false;
+#endif
+}
+
+bool cv_videocapture_wait_any(CVideoCapture* const* ccaps, size_t len,
+                              int64_t timeout_ns, bool* ready) {
+    std::fill(ready, ready + len, false);
+#ifdef CV_HAVE_WAIT_ANY
+    // Copies share the underlying capture.
+    std::vector<cv::VideoCapture> streams;
+    for (size_t i = 0; i < len; i++) {
+        streams.push_back(*reinterpret_cast<cv::VideoCapture*>(ccaps[i]));
+    }
+    std::vector<int> ready_index;
+    try {
+        cv::VideoCapture::waitAny(streams, ready_index, timeout_ns);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    for (size_t i = 0; i < ready_index.size(); i++) {
+        ready[ready_index[i]] = true;
+    }
+    return true;
+#else
+    return false;
+#endif
 }
 
 void cv_videocapture_drop(CVideoCapture* ccap) {
@@ -432,6 +1364,56 @@ void cv_cascade_classifier_detect(CCascadeClassifier* cc, CvMatrix* cmat,
     }
 }
 
+void cv_cascade_classifier_detect_with_weights(
+    CCascadeClassifier* cc, CvMatrix* cmat, VecRect* vec_of_rect,
+    VecDouble* level_weights, double scale_factor, int min_neighbors, int flags,
+    Size2i min_size, Size2i max_size) {
+    cv::CascadeClassifier* cascade =
+        reinterpret_cast<cv::CascadeClassifier*>(cc);
+    cv::Mat* image = reinterpret_cast<cv::Mat*>(cmat);
+    std::vector<cv::Rect> objects;
+    std::vector<int> reject_levels;
+    std::vector<double> weights;
+
+    cv::Size cv_min_size(min_size.width, min_size.height);
+    cv::Size cv_max_size(max_size.width, max_size.height);
+    cascade->detectMultiScale(*image, objects, reject_levels, weights,
+                              scale_factor, min_neighbors, flags, cv_min_size,
+                              cv_max_size, true);
+
+    vec_rect_cxx_to_c(objects, vec_of_rect);
+    vec_double_cxx_to_c(weights, level_weights);
+}
+
+void cv_group_rectangles(const Rect* const rects, size_t len,
+                         int group_threshold, double eps, VecRect* grouped,
+                         VecDouble* weights) {
+    std::vector<cv::Rect> rect_list = vec_rect_c_to_cxx(rects, len);
+    std::vector<int> group_sizes;
+    cv::groupRectangles(rect_list, group_sizes, group_threshold, eps);
+
+    vec_rect_cxx_to_c(rect_list, grouped);
+    std::vector<double> cxx_weights(group_sizes.begin(), group_sizes.end());
+    vec_double_cxx_to_c(cxx_weights, weights);
+}
+
+void cv_group_rectangles_meanshift(const Rect* const rects,
+                                   const double* const weights,
+                                   const double* const scales, size_t len,
+                                   double detect_threshold,
+                                   Size2i win_det_size, VecRect* grouped,
+                                   VecDouble* grouped_weights) {
+    std::vector<cv::Rect> rect_list = vec_rect_c_to_cxx(rects, len);
+    std::vector<double> found_weights(weights, weights + len);
+    std::vector<double> found_scales(scales, scales + len);
+    cv::Size cv_win_det_size(win_det_size.width, win_det_size.height);
+    cv::groupRectangles_meanshift(rect_list, found_weights, found_scales,
+                                  detect_threshold, cv_win_det_size);
+
+    vec_rect_cxx_to_c(rect_list, grouped);
+    vec_double_cxx_to_c(found_weights, grouped_weights);
+}
+
 SvmDetector* cv_hog_default_people_detector() {
     std::vector<float>* detector =
         new std::vector<float>(cv::HOGDescriptor::getDefaultPeopleDetector());
@@ -444,6 +1426,17 @@ SvmDetector* cv_hog_daimler_people_detector() {
     return reinterpret_cast<SvmDetector*>(detector);
 }
 
+SvmDetector* cv_hog_detector_from_coefficients(const float* const coefficients,
+                                               size_t len) {
+    std::vector<float>* detector =
+        new std::vector<float>(coefficients, coefficients + len);
+    return reinterpret_cast<SvmDetector*>(detector);
+}
+
+size_t cv_hog_detector_len(const SvmDetector* const detector) {
+    return reinterpret_cast<const std::vector<float>*>(detector)->size();
+}
+
 void cv_hog_detector_drop(SvmDetector* detector) {
     std::vector<float>* cv_detector =
         reinterpret_cast<std::vector<float>*>(detector);
@@ -455,17 +1448,128 @@ HogDescriptor* cv_hog_new() {
     return reinterpret_cast<HogDescriptor*>(new cv::HOGDescriptor());
 }
 
+HogDescriptor* cv_hog_new_with_params(Size2i win_size, Size2i block_size,
+                                      Size2i block_stride, Size2i cell_size,
+                                      int nbins, double win_sigma,
+                                      double l2hys_threshold,
+                                      bool gamma_correction, int nlevels) {
+    cv::HOGDescriptor* hog = new cv::HOGDescriptor(
+        cv::Size(win_size.width, win_size.height),
+        cv::Size(block_size.width, block_size.height),
+        cv::Size(block_stride.width, block_stride.height),
+        cv::Size(cell_size.width, cell_size.height), nbins, 1, win_sigma,
+        cv::HOGDescriptor::L2Hys, l2hys_threshold, gamma_correction, nlevels);
+    return reinterpret_cast<HogDescriptor*>(hog);
+}
+
+void cv_hog_get_params(const HogDescriptor* const hog, Size2i* win_size,
+                       Size2i* block_size, Size2i* block_stride,
+                       Size2i* cell_size, int* nbins, double* win_sigma,
+                       double* l2hys_threshold, bool* gamma_correction,
+                       int* nlevels) {
+    const cv::HOGDescriptor* cv_hog =
+        reinterpret_cast<const cv::HOGDescriptor*>(hog);
+    win_size->width = cv_hog->winSize.width;
+    win_size->height = cv_hog->winSize.height;
+    block_size->width = cv_hog->blockSize.width;
+    block_size->height = cv_hog->blockSize.height;
+    block_stride->width = cv_hog->blockStride.width;
+    block_stride->height = cv_hog->blockStride.height;
+    cell_size->width = cv_hog->cellSize.width;
+    cell_size->height = cv_hog->cellSize.height;
+    *nbins = cv_hog->nbins;
+    *win_sigma = cv_hog->winSigma;
+    *l2hys_threshold = cv_hog->L2HysThreshold;
+    *gamma_correction = cv_hog->gammaCorrection;
+    *nlevels = cv_hog->nlevels;
+}
+
 void cv_hog_drop(HogDescriptor* hog) {
     cv::HOGDescriptor* cv_hog = reinterpret_cast<cv::HOGDescriptor*>(hog);
     delete cv_hog;
     cv_hog = nullptr;
 }
 
-void cv_hog_set_svm_detector(HogDescriptor* hog, SvmDetector* detector) {
+bool cv_hog_set_svm_detector(HogDescriptor* hog, SvmDetector* detector) {
     cv::HOGDescriptor* cv_hog = reinterpret_cast<cv::HOGDescriptor*>(hog);
     std::vector<float>* cv_detector =
         reinterpret_cast<std::vector<float>*>(detector);
-    cv_hog->setSVMDetector(*cv_detector);
+    try {
+        cv_hog->setSVMDetector(*cv_detector);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+size_t cv_hog_get_descriptor_size(const HogDescriptor* const hog) {
+    return reinterpret_cast<const cv::HOGDescriptor*>(hog)
+        ->getDescriptorSize();
+}
+
+bool cv_hog_check_detector_size(const HogDescriptor* const hog) {
+    return reinterpret_cast<const cv::HOGDescriptor*>(hog)
+        ->checkDetectorSize();
+}
+
+bool cv_hog_compute(HogDescriptor* hog, CvMatrix* cmat, VecFloat* descriptors,
+                    Size2i win_stride, Size2i padding,
+                    const Point2i* const locations, size_t num_locations) {
+    cv::HOGDescriptor* cv_hog = reinterpret_cast<cv::HOGDescriptor*>(hog);
+    cv::Mat* image = reinterpret_cast<cv::Mat*>(cmat);
+    cv::Size cv_win_stride(win_stride.width, win_stride.height);
+    cv::Size cv_padding(padding.width, padding.height);
+    std::vector<cv::Point> cv_locations;
+    for (size_t i = 0; i < num_locations; i++) {
+        cv_locations.push_back(cv::Point(locations[i].x, locations[i].y));
+    }
+
+    std::vector<float> cv_descriptors;
+    try {
+        cv_hog->compute(*image, cv_descriptors, cv_win_stride, cv_padding,
+                        cv_locations);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    vec_float_cxx_to_c(cv_descriptors, descriptors);
+    return true;
+}
+
+bool cv_hog_compute_gradient(HogDescriptor* hog, CvMatrix* cmat,
+                             CvMatrix* cgrad, CvMatrix* cangle_ofs,
+                             Size2i padding_tl, Size2i padding_br) {
+    cv::HOGDescriptor* cv_hog = reinterpret_cast<cv::HOGDescriptor*>(hog);
+    cv::Mat* image = reinterpret_cast<cv::Mat*>(cmat);
+    cv::Mat* grad = reinterpret_cast<cv::Mat*>(cgrad);
+    cv::Mat* angle_ofs = reinterpret_cast<cv::Mat*>(cangle_ofs);
+    try {
+        cv_hog->computeGradient(*image, *grad, *angle_ofs,
+                                cv::Size(padding_tl.width, padding_tl.height),
+                                cv::Size(padding_br.width, padding_br.height));
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_hog_save(const HogDescriptor* const hog, const char* const path) {
+    const cv::HOGDescriptor* cv_hog =
+        reinterpret_cast<const cv::HOGDescriptor*>(hog);
+    try {
+        cv_hog->save(path);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+bool cv_hog_load(HogDescriptor* hog, const char* const path) {
+    cv::HOGDescriptor* cv_hog = reinterpret_cast<cv::HOGDescriptor*>(hog);
+    try {
+        return cv_hog->load(path);
+    } catch (cv::Exception& e) {
+        return false;
+    }
 }
 
 void cv_hog_detect(HogDescriptor* hog, CvMatrix* cmat, VecRect* vec_rect,
@@ -504,6 +1608,18 @@ void cv_term_criteria_drop(CTermCriteria* c_criteria) {
     c_criteria = nullptr;
 }
 
+int cv_term_criteria_type(const CTermCriteria* const c_criteria) {
+    return reinterpret_cast<const cv::TermCriteria*>(c_criteria)->type;
+}
+
+int cv_term_criteria_max_count(const CTermCriteria* const c_criteria) {
+    return reinterpret_cast<const cv::TermCriteria*>(c_criteria)->maxCount;
+}
+
+double cv_term_criteria_epsilon(const CTermCriteria* const c_criteria) {
+    return reinterpret_cast<const cv::TermCriteria*>(c_criteria)->epsilon;
+}
+
 RotatedRect cv_camshift(CvMatrix* c_bp_image, Rect crect,
                         CTermCriteria* c_criteria) {
     cv::Mat* bp_image = reinterpret_cast<cv::Mat*>(c_bp_image);
@@ -511,13 +1627,144 @@ RotatedRect cv_camshift(CvMatrix* c_bp_image, Rect crect,
     cv::TermCriteria* criteria =
         reinterpret_cast<cv::TermCriteria*>(c_criteria);
     cv::RotatedRect rr = cv::CamShift(*bp_image, rect, *criteria);
-    RotatedRect c_rr;
-    c_rr.center.x = rr.center.x;
-    c_rr.center.y = rr.center.y;
-    c_rr.size.width = rr.size.width;
-    c_rr.size.height = rr.size.height;
-    c_rr.angle = rr.angle;
-    return c_rr;
+    return rotated_rect_cxx_to_c(rr);
+}
+
+// =============================================================================
+//   DNN
+// =============================================================================
+static CDnnNet* wrap_net(const cv::dnn::Net& net) {
+    if (net.empty()) {
+        return nullptr;
+    }
+    return reinterpret_cast<CDnnNet*>(new cv::dnn::Net(net));
+}
+
+CDnnNet* cv_dnn_net_from_caffe(const char* const prototxt,
+                               const char* const model) {
+    try {
+        return wrap_net(cv::dnn::readNetFromCaffe(prototxt, model));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+CDnnNet* cv_dnn_net_from_tensorflow(const char* const model,
+                                    const char* const config) {
+    try {
+        return wrap_net(cv::dnn::readNetFromTensorflow(model, config));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+CDnnNet* cv_dnn_net_from_darknet(const char* const cfg,
+                                 const char* const model) {
+    try {
+        return wrap_net(cv::dnn::readNetFromDarknet(cfg, model));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+CDnnNet* cv_dnn_net_from_onnx(const char* const model) {
+    try {
+        return wrap_net(cv::dnn::readNetFromONNX(model));
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+void cv_dnn_net_drop(CDnnNet* cnet) {
+    cv::dnn::Net* net = reinterpret_cast<cv::dnn::Net*>(cnet);
+    delete net;
+    cnet = nullptr;
+}
+
+bool cv_dnn_net_set_input(CDnnNet* cnet, const CvMatrix* const cblob,
+                          const char* const name) {
+    cv::dnn::Net* net = reinterpret_cast<cv::dnn::Net*>(cnet);
+    const cv::Mat* blob = reinterpret_cast<const cv::Mat*>(cblob);
+    try {
+        net->setInput(*blob, name);
+        return true;
+    } catch (cv::Exception& e) {
+        return false;
+    }
+}
+
+CvMatrix* cv_dnn_net_forward(CDnnNet* cnet, const char* const output_name) {
+    cv::dnn::Net* net = reinterpret_cast<cv::dnn::Net*>(cnet);
+    try {
+        // The output blob is owned by the network and reused by the next
+        // forward pass, so hand out a copy.
+        cv::Mat* out = new cv::Mat(net->forward(output_name).clone());
+        return reinterpret_cast<CvMatrix*>(out);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+bool cv_dnn_net_forward_unconnected(CDnnNet* cnet, VecMat* outputs) {
+    cv::dnn::Net* net = reinterpret_cast<cv::dnn::Net*>(cnet);
+    std::vector<cv::String> names;
+    std::vector<cv::Mat> blobs;
+    try {
+        std::vector<int> layers = net->getUnconnectedOutLayers();
+        std::vector<cv::String> layer_names = net->getLayerNames();
+        for (size_t i = 0; i < layers.size(); i++) {
+            names.push_back(layer_names[layers[i] - 1]);
+        }
+        net->forward(blobs, names);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+
+    size_t num = blobs.size();
+    outputs->size = num;
+    outputs->array = (CvMatrix**) malloc(num * sizeof(CvMatrix*));
+    for (size_t i = 0; i < num; i++) {
+        outputs->array[i] =
+            reinterpret_cast<CvMatrix*>(new cv::Mat(blobs[i].clone()));
+    }
+    return true;
+}
+
+CvMatrix* cv_dnn_blob_from_image(const CvMatrix* const cimage, double scale,
+                                 Size2i size, Scalar mean, bool swap_rb,
+                                 bool crop) {
+    const cv::Mat* image = reinterpret_cast<const cv::Mat*>(cimage);
+    cv::Size cv_size(size.width, size.height);
+    cv::Scalar cv_mean = scalar_c_to_cxx(mean);
+    try {
+        cv::Mat* blob = new cv::Mat(cv::dnn::blobFromImage(
+            *image, scale, cv_size, cv_mean, swap_rb, crop));
+        return reinterpret_cast<CvMatrix*>(blob);
+    } catch (cv::Exception& e) {
+        return nullptr;
+    }
+}
+
+bool cv_dnn_nms_boxes(const Rect* const bboxes, const float* const scores,
+                      size_t len, float score_threshold, float nms_threshold,
+                      int* indices, size_t* num_kept) {
+    std::vector<cv::Rect> cv_bboxes;
+    std::vector<float> cv_scores(scores, scores + len);
+    for (size_t i = 0; i < len; i++) {
+        cv_bboxes.push_back(cv::Rect(bboxes[i].x, bboxes[i].y, bboxes[i].width,
+                                     bboxes[i].height));
+    }
+
+    std::vector<int> kept;
+    try {
+        cv::dnn::NMSBoxes(cv_bboxes, cv_scores, score_threshold, nms_threshold,
+                          kept);
+    } catch (cv::Exception& e) {
+        return false;
+    }
+    std::copy(kept.begin(), kept.end(), indices);
+    *num_kept = kept.size();
+    return true;
 }
 
 EXTERN_C_END
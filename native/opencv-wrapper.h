@@ -18,6 +18,7 @@ EXTERN_C_BEGIN
 //   Core/Types
 // =============================================================================
 typedef struct _CvMatrixrix CvMatrix;
+typedef struct _CTermCriteria CTermCriteria;
 
 typedef struct {
     int32_t x;
@@ -63,10 +64,20 @@ typedef struct {
 } VecDouble;
 
 typedef struct {
-    int32_t v0;
-    int32_t v1;
-    int32_t v2;
-    int32_t v3;
+    float* array;
+    size_t size;
+} VecFloat;
+
+typedef struct {
+    CvMatrix** array;
+    size_t size;
+} VecMat;
+
+typedef struct {
+    double v0;
+    double v1;
+    double v2;
+    double v3;
 } Scalar;
 
 typedef struct {
@@ -80,12 +91,35 @@ CvMatrix* cv_mat_new();
 CvMatrix* cv_mat_new_with_size(int rows, int cols, int type);
 CvMatrix* cv_mat_zeros(int rows, int cols, int type);
 CvMatrix* cv_mat_from_buffer(int rows, int cols, int type, const uint8_t* buf);
+CvMatrix* cv_mat_new_nd(int ndims, const int* const sizes, int type);
 
 bool cv_mat_valid(CvMatrix* cmat);
 
 // The caller owns the returned CvMatrix
 CvMatrix* cv_mat_roi(CvMatrix* cmat, Rect crect);
 
+// The caller owns the returned deep copy
+CvMatrix* cv_mat_clone(const CvMatrix* const cmat);
+
+bool cv_mat_copy_to(const CvMatrix* const src, CvMatrix* dst,
+                    const CvMatrix* const mask);
+bool cv_mat_convert_to(const CvMatrix* const src, CvMatrix* dst, int rtype,
+                       double alpha, double beta);
+bool cv_mat_set_to(CvMatrix* cmat, Scalar value, const CvMatrix* const mask);
+
+// The caller owns the returned CvMatrix, which shares the data of `cmat`.
+// Returns nullptr if the operation fails.
+CvMatrix* cv_mat_reshape(const CvMatrix* const cmat, int cn, int rows);
+CvMatrix* cv_mat_row_range(const CvMatrix* const cmat, int start, int end);
+CvMatrix* cv_mat_col_range(const CvMatrix* const cmat, int start, int end);
+
+bool cv_hconcat(const CvMatrix* const* srcs, size_t len, CvMatrix* dst);
+bool cv_vconcat(const CvMatrix* const* srcs, size_t len, CvMatrix* dst);
+bool cv_repeat(const CvMatrix* const src, int ny, int nx, CvMatrix* dst);
+bool cv_copy_make_border(const CvMatrix* const src, CvMatrix* dst, int top,
+                         int bottom, int left, int right, int border_type,
+                         Scalar value);
+
 void cv_mat_logic_and(CvMatrix* image, const CvMatrix* const mask);
 void cv_mat_flip(CvMatrix* image, int code);
 
@@ -100,11 +134,19 @@ int cv_mat_type(const CvMatrix* const cmat);
 const uint8_t* cv_mat_data(const CvMatrix* const cmat);
 size_t cv_mat_total(const CvMatrix* const cmat);
 size_t cv_mat_elem_size(const CvMatrix* const cmat);
+int cv_mat_dims(const CvMatrix* const cmat);
+// `sizes` and `steps` must hold `cv_mat_dims` elements.
+void cv_mat_sizes(const CvMatrix* const cmat, int* sizes);
+void cv_mat_steps(const CvMatrix* const cmat, size_t* steps);
+bool cv_mat_is_continuous(const CvMatrix* const cmat);
 
 // Free a Mat object
 void cv_mat_drop(CvMatrix* cmat);
 
 void cv_vec_of_rect_drop(VecRect* v);
+void cv_vec_of_mat_drop(VecMat* v);
+void cv_vec_double_drop(VecDouble* v);
+void cv_vec_float_drop(VecFloat* v);
 
 // =============================================================================
 //  core array
@@ -123,6 +165,82 @@ void cv_bitwise_xor(const CvMatrix* const src1, const CvMatrix* const src2,
                     CvMatrix* dst);
 int cv_count_non_zero(const CvMatrix* const src);
 
+// =============================================================================
+//  Core/Clustering
+// =============================================================================
+bool cv_kmeans(const CvMatrix* const data, int k, CvMatrix* labels,
+               const CTermCriteria* const criteria, int attempts, int flags,
+               CvMatrix* centers, double* compactness);
+
+typedef struct _CPca CPca;
+CPca* cv_pca_new(const CvMatrix* const data, int flags, int max_components);
+CPca* cv_pca_new_with_retained_variance(const CvMatrix* const data, int flags,
+                                        double retained_variance);
+void cv_pca_drop(CPca* pca);
+bool cv_pca_project(const CPca* const pca, const CvMatrix* const vec,
+                    CvMatrix* result);
+bool cv_pca_back_project(const CPca* const pca, const CvMatrix* const vec,
+                         CvMatrix* result);
+CvMatrix* cv_pca_eigenvectors(const CPca* const pca);
+CvMatrix* cv_pca_eigenvalues(const CPca* const pca);
+CvMatrix* cv_pca_mean(const CPca* const pca);
+
+// =============================================================================
+//  Core/Linear algebra
+// =============================================================================
+bool cv_gemm(const CvMatrix* const src1, const CvMatrix* const src2,
+             double alpha, const CvMatrix* const src3, double beta,
+             CvMatrix* dst, int flags);
+bool cv_transpose(const CvMatrix* const src, CvMatrix* dst);
+bool cv_invert(const CvMatrix* const src, CvMatrix* dst, int method,
+               double* result);
+bool cv_solve(const CvMatrix* const src1, const CvMatrix* const src2,
+              CvMatrix* dst, int method, bool* solved);
+bool cv_determinant(const CvMatrix* const src, double* determinant);
+bool cv_trace(const CvMatrix* const src, double* trace);
+bool cv_svd_compute(const CvMatrix* const src, CvMatrix* w, CvMatrix* u,
+                    CvMatrix* vt, int flags);
+bool cv_eigen(const CvMatrix* const src, CvMatrix* eigenvalues,
+              CvMatrix* eigenvectors);
+bool cv_mul_transposed(const CvMatrix* const src, CvMatrix* dst, bool a_top,
+                       const CvMatrix* const delta, double scale);
+
+// =============================================================================
+//  Core/Discrete transforms
+// =============================================================================
+bool cv_dft(const CvMatrix* const src, CvMatrix* dst, int flags,
+            int nonzero_rows);
+bool cv_idft(const CvMatrix* const src, CvMatrix* dst, int flags,
+             int nonzero_rows);
+bool cv_dct(const CvMatrix* const src, CvMatrix* dst, int flags);
+bool cv_idct(const CvMatrix* const src, CvMatrix* dst, int flags);
+int cv_get_optimal_dft_size(int vecsize);
+bool cv_mul_spectrums(const CvMatrix* const a, const CvMatrix* const b,
+                      CvMatrix* dst, int flags, bool conj_b);
+
+// =============================================================================
+//  Core/Statistics
+// =============================================================================
+bool cv_mean(const CvMatrix* const src, const CvMatrix* const mask,
+             Scalar* mean);
+bool cv_mean_std_dev(const CvMatrix* const src, const CvMatrix* const mask,
+                     Scalar* mean, Scalar* stddev);
+bool cv_sum(const CvMatrix* const src, Scalar* sum);
+bool cv_min_max_loc(const CvMatrix* const src, const CvMatrix* const mask,
+                    double* min_val, double* max_val, Point2i* min_loc,
+                    Point2i* max_loc);
+int cv_min_max_idx(const CvMatrix* const src, const CvMatrix* const mask,
+                   double* min_val, double* max_val, int* min_idx,
+                   int* max_idx);
+bool cv_norm(const CvMatrix* const src, int norm_type,
+             const CvMatrix* const mask, double* norm);
+bool cv_norm_diff(const CvMatrix* const src1, const CvMatrix* const src2,
+                  int norm_type, const CvMatrix* const mask, double* norm);
+bool cv_reduce(const CvMatrix* const src, int dim, int rtype,
+               VecDouble* result);
+bool cv_psnr(const CvMatrix* const src1, const CvMatrix* const src2,
+             double* psnr);
+
 // =============================================================================
 //  Imgproc
 // =============================================================================
@@ -131,6 +249,9 @@ void cv_rectangle(CvMatrix* cmat, Rect crect, Scalar color, int thickness,
 void cv_ellipse(CvMatrix* cmat, Point2i center, Size2i axes, double angle,
                 double start_angle, double end_angle, Scalar color,
                 int thickness, int linetype, int shift);
+// Returns false if the thickness or the line type is invalid.
+bool cv_draw_rotated_rect(CvMatrix* cmat, RotatedRect rect, Scalar color,
+                          int thickness, int linetype);
 
 void cv_cvt_color(CvMatrix* cmat, CvMatrix* output, int code);
 void cv_pyr_down(CvMatrix* cmat, CvMatrix* output);
@@ -142,13 +263,46 @@ void cv_calc_hist(const CvMatrix* const cimages, int nimages,
 void cv_calc_back_project(const CvMatrix* images, int nimages,
                           const int* channels, CvMatrix* hist,
                           CvMatrix* back_project, const float** ranges);
+bool cv_phase_correlate(const CvMatrix* const src1, const CvMatrix* const src2,
+                        const CvMatrix* const window, Point2f* shift,
+                        double* response);
+bool cv_create_hanning_window(CvMatrix* dst, Size2i size, int type);
+
+// `vertices` must hold 8 elements, the number of vertices is written to `len`.
+// Returns the intersection type, or -1 on failure or if there are more than 8
+// vertices.
+int cv_rotated_rectangle_intersection(RotatedRect rect1, RotatedRect rect2,
+                                      Point2f* vertices, size_t* len);
 
 // =============================================================================
 //  Imgcodecs
 // =============================================================================
 CvMatrix* cv_imdecode(const uint8_t* const buffer, size_t len, int flag);
+// The returned buffer must be freed with cv_imencode_result_drop.
 ImencodeResult cv_imencode(const char* const ext, const CvMatrix* const cmat,
                            const int* const flag_ptr, size_t flag_size);
+void cv_imencode_result_drop(ImencodeResult result);
+bool cv_imwrite(const char* const filename, const CvMatrix* const cmat,
+                const int* const params, size_t params_len);
+bool cv_have_image_reader(const char* const filename);
+bool cv_have_image_writer(const char* const filename);
+
+// Multi-page images. `pages` is filled with newly allocated matrices owned by
+// the caller.
+bool cv_imreadmulti(const char* const filename, int flags, VecMat* pages);
+// Returns the number of pages, or 0 if the file can't be decoded.
+size_t cv_imcount(const char* const filename, int flags);
+bool cv_imwritemulti(const char* const filename,
+                     const CvMatrix* const* mats, size_t len,
+                     const int* const params, size_t params_len);
+
+// Decodes the pages of a multi-page image one at a time.
+typedef struct _CImagePages CImagePages;
+CImagePages* cv_image_pages_new(const char* const filename, int flags);
+void cv_image_pages_drop(CImagePages* pages);
+size_t cv_image_pages_len(const CImagePages* const pages);
+// The caller owns the returned matrix, or gets nullptr on failure.
+CvMatrix* cv_image_pages_read(CImagePages* pages, size_t index);
 
 // =============================================================================
 //   Highgui: high-level GUI
@@ -170,7 +324,19 @@ typedef struct _CVideoCapture CVideoCapture;
 CVideoCapture* cv_videocapture_new(int index);
 CVideoCapture* cv_videocapture_from_file(const char* const filename);
 bool cv_videocapture_is_opened(const CVideoCapture* const ccap);
-bool cv_videocapture_read(CVideoCapture* ccap, CvMatrix* cmat);
+// Returns false if OpenCV throws; `grabbed` tells whether a frame was read.
+bool cv_videocapture_read(CVideoCapture* ccap, CvMatrix* cmat, bool* grabbed);
+// Returns false if OpenCV throws; `grabbed` tells whether a frame was grabbed.
+bool cv_videocapture_grab(CVideoCapture* ccap, bool* grabbed);
+// Returns false if OpenCV throws; `retrieved` tells whether a frame was read.
+bool cv_videocapture_retrieve(CVideoCapture* ccap, CvMatrix* cmat, int flag,
+                              bool* retrieved);
+// Whether cv_videocapture_wait_any is available, which needs OpenCV 4.1.
+bool cv_videocapture_have_wait_any();
+// `ready` must hold `len` elements, set to whether each capture is ready.
+// Returns false if OpenCV throws or if waiting isn't available.
+bool cv_videocapture_wait_any(CVideoCapture* const* ccaps, size_t len,
+                              int64_t timeout_ns, bool* ready);
 void cv_videocapture_drop(CVideoCapture* ccap);
 bool cv_videocapture_set(CVideoCapture* ccap, int property, double value);
 double cv_videocapture_get(CVideoCapture* ccap, int property);
@@ -205,16 +371,60 @@ void cv_cascade_classifier_detect(CCascadeClassifier* cc, CvMatrix* cmat,
                                   VecRect* vec_of_rect, double scale_factor,
                                   int min_neighbors, int flags, Size2i min_size,
                                   Size2i max_size);
+// Like `cv_cascade_classifier_detect`, but also reports the weight of the last
+// stage each object passed (detectMultiScale3 with outputRejectLevels).
+void cv_cascade_classifier_detect_with_weights(
+    CCascadeClassifier* cc, CvMatrix* cmat, VecRect* vec_of_rect,
+    VecDouble* level_weights, double scale_factor, int min_neighbors, int flags,
+    Size2i min_size, Size2i max_size);
+
+// Groups the rectangles with cv::groupRectangles; `weights` receives the number
+// of rectangles merged into each group.
+void cv_group_rectangles(const Rect* const rects, size_t len,
+                         int group_threshold, double eps, VecRect* grouped,
+                         VecDouble* weights);
+void cv_group_rectangles_meanshift(const Rect* const rects,
+                                   const double* const weights,
+                                   const double* const scales, size_t len,
+                                   double detect_threshold,
+                                   Size2i win_det_size, VecRect* grouped,
+                                   VecDouble* grouped_weights);
 
 typedef struct _SvmDetector SvmDetector;
 SvmDetector* cv_hog_default_people_detector();
 SvmDetector* cv_hog_daimler_people_detector();
+SvmDetector* cv_hog_detector_from_coefficients(const float* const coefficients,
+                                               size_t len);
+size_t cv_hog_detector_len(const SvmDetector* const detector);
 void cv_hog_detector_drop(SvmDetector*);
 
 typedef struct _HogDescriptor HogDescriptor;
 HogDescriptor* cv_hog_new();
+HogDescriptor* cv_hog_new_with_params(Size2i win_size, Size2i block_size,
+                                      Size2i block_stride, Size2i cell_size,
+                                      int nbins, double win_sigma,
+                                      double l2hys_threshold,
+                                      bool gamma_correction, int nlevels);
+void cv_hog_get_params(const HogDescriptor* const hog, Size2i* win_size,
+                       Size2i* block_size, Size2i* block_stride,
+                       Size2i* cell_size, int* nbins, double* win_sigma,
+                       double* l2hys_threshold, bool* gamma_correction,
+                       int* nlevels);
 void cv_hog_drop(HogDescriptor*);
-void cv_hog_set_svm_detector(HogDescriptor*, SvmDetector*);
+// Returns false if the detector doesn't match the descriptor size.
+bool cv_hog_set_svm_detector(HogDescriptor*, SvmDetector*);
+size_t cv_hog_get_descriptor_size(const HogDescriptor* const hog);
+bool cv_hog_check_detector_size(const HogDescriptor* const hog);
+// Both return false if OpenCV rejects the image or the parameters.
+bool cv_hog_compute(HogDescriptor* hog, CvMatrix* image, VecFloat* descriptors,
+                    Size2i win_stride, Size2i padding,
+                    const Point2i* const locations, size_t num_locations);
+bool cv_hog_compute_gradient(HogDescriptor* hog, CvMatrix* image,
+                             CvMatrix* grad, CvMatrix* angle_ofs,
+                             Size2i padding_tl, Size2i padding_br);
+// Both return false if the file can't be written or parsed.
+bool cv_hog_save(const HogDescriptor* const hog, const char* const path);
+bool cv_hog_load(HogDescriptor* hog, const char* const path);
 void cv_hog_detect(HogDescriptor*, CvMatrix*, VecRect* vec_detected,
                    VecDouble* vec_weight, Size2i win_stride, Size2i padding,
                    double scale, double final_threshold, bool use_means_shift);
@@ -222,12 +432,49 @@ void cv_hog_detect(HogDescriptor*, CvMatrix*, VecRect* vec_detected,
 // =============================================================================
 //   VideoTrack
 // =============================================================================
-typedef struct _CTermCriteria CTermCriteria;
 CTermCriteria* cv_term_criteria_new(int type, int count, double epsilon);
 void cv_term_criteria_drop(CTermCriteria* c_criteria);
+int cv_term_criteria_type(const CTermCriteria* const c_criteria);
+int cv_term_criteria_max_count(const CTermCriteria* const c_criteria);
+double cv_term_criteria_epsilon(const CTermCriteria* const c_criteria);
 RotatedRect cv_camshift(CvMatrix* back_project_image, Rect window,
                         CTermCriteria* term_criteria);
 
+// =============================================================================
+//   DNN
+// =============================================================================
+typedef struct _CDnnNet CDnnNet;
+
+// All constructors return nullptr if the model can't be loaded.
+CDnnNet* cv_dnn_net_from_caffe(const char* const prototxt,
+                               const char* const model);
+CDnnNet* cv_dnn_net_from_tensorflow(const char* const model,
+                                    const char* const config);
+CDnnNet* cv_dnn_net_from_darknet(const char* const cfg,
+                                 const char* const model);
+CDnnNet* cv_dnn_net_from_onnx(const char* const model);
+void cv_dnn_net_drop(CDnnNet* net);
+
+// Returns false if the network has no input called `name`.
+bool cv_dnn_net_set_input(CDnnNet* net, const CvMatrix* const blob,
+                          const char* const name);
+// The caller owns the returned CvMatrix. Returns nullptr if the forward pass
+// fails.
+CvMatrix* cv_dnn_net_forward(CDnnNet* net, const char* const output_name);
+// Runs a forward pass and collects the outputs of all unconnected layers.
+// Returns false if the forward pass fails.
+bool cv_dnn_net_forward_unconnected(CDnnNet* net, VecMat* outputs);
+
+// Returns nullptr if the image can't be turned into a blob.
+CvMatrix* cv_dnn_blob_from_image(const CvMatrix* const image, double scale,
+                                 Size2i size, Scalar mean, bool swap_rb,
+                                 bool crop);
+// `indices` must have room for `len` elements; the number of kept boxes is
+// stored in `num_kept`. Returns false if OpenCV rejects the arguments.
+bool cv_dnn_nms_boxes(const Rect* const bboxes, const float* const scores,
+                      size_t len, float score_threshold, float nms_threshold,
+                      int* indices, size_t* num_kept);
+
 EXTERN_C_END
 
 #endif  // OPENCV_WRAPPER_H_
@@ -12,6 +12,16 @@ void vec_rect_cxx_to_c(const std::vector<cv::Rect>& cxx_vec_rect, VecRect* vr) {
     }
 }
 
+std::vector<cv::Rect> vec_rect_c_to_cxx(const Rect* const rects, size_t len) {
+    std::vector<cv::Rect> cxx_vec_rect;
+    cxx_vec_rect.reserve(len);
+    for (size_t i = 0; i < len; i++) {
+        cxx_vec_rect.push_back(
+            cv::Rect(rects[i].x, rects[i].y, rects[i].width, rects[i].height));
+    }
+    return cxx_vec_rect;
+}
+
 void vec_double_cxx_to_c(const std::vector<double>& cxx_vec_double,
                          VecDouble* vd) {
     size_t num = cxx_vec_double.size();
@@ -19,3 +29,34 @@ void vec_double_cxx_to_c(const std::vector<double>& cxx_vec_double,
     vd->array = (double*) malloc(num * sizeof(double));
     ::memcpy(vd->array, cxx_vec_double.data(), num * sizeof(double));
 }
+
+void vec_float_cxx_to_c(const std::vector<float>& cxx_vec_float, VecFloat* vf) {
+    size_t num = cxx_vec_float.size();
+    vf->size = num;
+    vf->array = (float*) malloc(num * sizeof(float));
+    ::memcpy(vf->array, cxx_vec_float.data(), num * sizeof(float));
+}
+
+cv::Scalar scalar_c_to_cxx(const Scalar& s) {
+    return cv::Scalar(s.v0, s.v1, s.v2, s.v3);
+}
+
+Scalar scalar_cxx_to_c(const cv::Scalar& s) {
+    Scalar c_scalar = {s[0], s[1], s[2], s[3]};
+    return c_scalar;
+}
+
+cv::RotatedRect rotated_rect_c_to_cxx(const RotatedRect& r) {
+    return cv::RotatedRect(cv::Point2f(r.center.x, r.center.y),
+                           cv::Size2f(r.size.width, r.size.height), r.angle);
+}
+
+RotatedRect rotated_rect_cxx_to_c(const cv::RotatedRect& r) {
+    RotatedRect c_rect;
+    c_rect.center.x = r.center.x;
+    c_rect.center.y = r.center.y;
+    c_rect.size.width = r.size.width;
+    c_rect.size.height = r.size.height;
+    c_rect.angle = r.angle;
+    return c_rect;
+}
@@ -10,6 +10,12 @@
 // =============================================================================
 
 void vec_rect_cxx_to_c(const std::vector<cv::Rect>& cxx_vec_rect, VecRect* vr);
+std::vector<cv::Rect> vec_rect_c_to_cxx(const Rect* const rects, size_t len);
 void vec_double_cxx_to_c(const std::vector<double>& cxx_vec, VecDouble* v);
+void vec_float_cxx_to_c(const std::vector<float>& cxx_vec, VecFloat* v);
+cv::Scalar scalar_c_to_cxx(const Scalar& s);
+Scalar scalar_cxx_to_c(const cv::Scalar& s);
+cv::RotatedRect rotated_rect_c_to_cxx(const RotatedRect& r);
+RotatedRect rotated_rect_cxx_to_c(const cv::RotatedRect& r);
 
 #endif  // UTILS_H_
@@ -2,6 +2,7 @@
 
 use libc::c_int;
 use errors::*;
+use video::tracking::TermCriteria;
 
 use super::wrapper::*;
 
@@ -39,6 +40,35 @@ impl Mat {
         Mat::from_raw(m)
     }
 
+    /// Creates an uninitialized N-dimensional `Mat` with the size of each
+    /// dimension given by `sizes`, slowest-changing dimension first.
+    pub fn new_nd(sizes: &[i32], mat_type: MatType) -> Result<Self> {
+        let m = unsafe {
+            cv_mat_new_nd(
+                sizes.len() as c_int,
+                sizes.as_ptr(),
+                mat_type.as_opencv_value(),
+            )
+        };
+        if m.is_null() {
+            Err(ErrorKind::Operation("new_nd").into())
+        } else {
+            Ok(Mat::from_raw(m))
+        }
+    }
+
+    /// Creates a `Mat` with specific size and type by copying the pixel data
+    /// from `buf`, which must hold exactly `rows * cols` continuous elements.
+    pub fn from_buffer(rows: c_int, cols: c_int, mat_type: MatType, buf: &[u8]) -> Result<Self> {
+        let m = Mat::with_size(rows, cols, mat_type);
+        let len = m.total() * m.elem_size();
+        if buf.len() != len {
+            return Err(ErrorKind::BufferSize(len, buf.len()).into());
+        }
+        unsafe { ::std::ptr::copy_nonoverlapping(buf.as_ptr(), m.data() as *mut u8, len) };
+        Ok(m)
+    }
+
     /// Returns the raw data (as a uchar pointer)
     pub fn data(&self) -> *const u8 {
         unsafe { cv_mat_data(self.inner) }
@@ -59,17 +89,95 @@ impl Mat {
         unsafe { cv_mat_elem_size(self.inner) }
     }
 
-    /// Returns the height of this matrix.
+    /// Returns the height of this matrix, or -1 if it has more than two
+    /// dimensions (see [sizes](#method.sizes)).
     pub fn rows(&self) -> c_int {
         unsafe { cv_mat_rows(self.inner) }
     }
 
-    /// Returns the width of this matrix.
+    /// Returns the width of this matrix, or -1 if it has more than two
+    /// dimensions (see [sizes](#method.sizes)).
     pub fn cols(&self) -> c_int {
         unsafe { cv_mat_cols(self.inner) }
     }
 
-    /// Returns the size of this matrix.
+    /// Returns the number of dimensions, which is at least 2 for non-empty
+    /// matrices.
+    pub fn dims(&self) -> c_int {
+        unsafe { cv_mat_dims(self.inner) }
+    }
+
+    /// Returns the size of each dimension, slowest-changing dimension first.
+    /// For 2-dimensional matrices this is `[rows, cols]`.
+    pub fn sizes(&self) -> Vec<i32> {
+        let mut sizes = vec![0; self.dims() as usize];
+        unsafe { cv_mat_sizes(self.inner, sizes.as_mut_ptr()) };
+        sizes
+    }
+
+    /// Returns the number of bytes between two consecutive elements of each
+    /// dimension. The last step is the element size.
+    pub fn steps(&self) -> Vec<usize> {
+        let mut steps = vec![0; self.dims() as usize];
+        unsafe { cv_mat_steps(self.inner, steps.as_mut_ptr()) };
+        steps
+    }
+
+    /// Returns true if the elements are stored without gaps between rows,
+    /// which isn't the case for most regions of interest.
+    pub fn is_continuous(&self) -> bool {
+        unsafe { cv_mat_is_continuous(self.inner) }
+    }
+
+    /// Returns a copy of the element at `idx`, which holds one index per
+    /// dimension (`[row, col]` for 2-dimensional matrices). `T` must match
+    /// the whole element, e.g. `[u8; 3]` or `Vec3b` for `CV_8UC3`.
+    pub fn at<T: MatValue>(&self, idx: &[i32]) -> Result<T> {
+        let offset = self.offset_of::<T>(idx)?;
+        Ok(unsafe { ::std::ptr::read_unaligned(self.data().offset(offset) as *const T) })
+    }
+
+    /// Overwrites the element at `idx`, see [at](#method.at).
+    pub fn set_at<T: MatValue>(&mut self, idx: &[i32], value: T) -> Result<()> {
+        let offset = self.offset_of::<T>(idx)?;
+        unsafe { ::std::ptr::write_unaligned(self.data().offset(offset) as *mut T, value) };
+        Ok(())
+    }
+
+    fn offset_of<T: MatValue>(&self, idx: &[i32]) -> Result<isize> {
+        let elem_size = self.elem_size();
+        if ::std::mem::size_of::<T>() != elem_size {
+            return Err(
+                ErrorKind::ElementSizeMismatch(elem_size, ::std::mem::size_of::<T>()).into(),
+            );
+        }
+        let mat_type = self.mat_type();
+        if mat_type.depth() != T::DEPTH || mat_type.channels() != T::CHANNELS {
+            return Err(
+                ErrorKind::UnsupportedMatType(mat_type.depth(), mat_type.channels()).into(),
+            );
+        }
+        // Empty matrices have no data to point into.
+        let sizes = self.sizes();
+        if self.dims() == 0
+            || self.total() == 0
+            || idx.len() != sizes.len()
+            || idx
+                .iter()
+                .zip(sizes.iter())
+                .any(|(&i, &size)| i < 0 || i >= size)
+        {
+            return Err(ErrorKind::IndexOutOfRange(idx.to_vec()).into());
+        }
+        Ok(idx
+            .iter()
+            .zip(self.steps())
+            .map(|(&i, step)| i as isize * step as isize)
+            .sum())
+    }
+
+    /// Returns the size of this matrix. Only meaningful for 2-dimensional
+    /// matrices.
     pub fn size(&self) -> Size2i {
         Size2i {
             width: self.cols(),
@@ -126,17 +234,30 @@ impl Drop for Mat {
     }
 }
 
+impl Clone for Mat {
+    /// Creates a deep copy of the matrix, including its data.
+    fn clone(&self) -> Self {
+        Mat::from_raw(unsafe { cv_mat_clone(self.inner) })
+    }
+}
+
 // TODO(benzh): Should consider Unique<T>,
 // https://github.com/rust-lang/rust/issues/27730
 unsafe impl Send for Mat {}
 
-/// The `Rect2f` are rectangles in float.
-#[derive(Default, Debug, Clone, Copy)]
-pub struct Rect2f {
-    pub x: f32,
-    pub y: f32,
-    pub width: f32,
-    pub height: f32,
+impl Rect {
+    /// Normalize the rectangle according to the image (if the rectangle is
+    /// inside the image, then the result should be all within (0, 1).
+    pub fn normalize_to_mat(&self, mat: &Mat) -> Rect2f {
+        let cols = mat.cols();
+        let rows = mat.rows();
+        Rect2f {
+            x: (self.x as f32) / (cols as f32),
+            y: (self.y as f32) / (rows as f32),
+            width: (self.width as f32) / (cols as f32),
+            height: (self.height as f32) / (rows as f32),
+        }
+    }
 }
 
 impl Rect2f {
@@ -207,6 +328,7 @@ pub enum CvType {
 
 /// Channel depths of mats.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MatDepth {
     /// `u8`
     Unsigned8 = 0,
@@ -261,6 +383,230 @@ impl MatType {
     }
 }
 
+pub(crate) mod sealed {
+    /// Keeps traits from being implemented outside of this crate, e.g. the
+    /// ones describing `Mat` elements, since the matrix data is reinterpreted
+    /// as them.
+    pub trait Sealed {}
+}
+
+/// Rust types that can be stored in a `Mat`, one value per channel. This
+/// trait is sealed: it is only implemented for the primitive types matching
+/// a `MatDepth`.
+pub trait MatElement: Copy + sealed::Sealed {
+    /// The channel depth matching the type.
+    const DEPTH: MatDepth;
+}
+
+macro_rules! mat_element {
+    ($t:ty, $depth:ident) => {
+        impl sealed::Sealed for $t {}
+
+        impl MatElement for $t {
+            const DEPTH: MatDepth = MatDepth::$depth;
+        }
+    };
+}
+
+mat_element!(u8, Unsigned8);
+mat_element!(i8, Signed8);
+mat_element!(u16, Unsigned16);
+mat_element!(i16, Signed16);
+mat_element!(i32, Signed32);
+mat_element!(f32, Float32);
+mat_element!(f64, Float64);
+
+/// Rust types holding a whole element of a `Mat`, as read and written by
+/// [`Mat::at`](struct.Mat.html#method.at): a `MatElement` for single-channel
+/// matrices, or an array or `Vec*` with one `MatElement` per channel. This
+/// trait is sealed.
+pub trait MatValue: Copy + sealed::Sealed {
+    /// The channel depth matching the type.
+    const DEPTH: MatDepth;
+    /// The number of channels matching the type.
+    const CHANNELS: u16;
+}
+
+impl<T: MatElement> MatValue for T {
+    const DEPTH: MatDepth = T::DEPTH;
+    const CHANNELS: u16 = 1;
+}
+
+macro_rules! mat_value {
+    ($t:ty, $n:expr) => {
+        impl<T: MatElement> sealed::Sealed for $t {}
+
+        impl<T: MatElement> MatValue for $t {
+            const DEPTH: MatDepth = T::DEPTH;
+            const CHANNELS: u16 = $n;
+        }
+    };
+}
+
+mat_value!([T; 1], 1);
+mat_value!([T; 2], 2);
+mat_value!([T; 3], 3);
+mat_value!([T; 4], 4);
+mat_value!(Vec2<T>, 2);
+mat_value!(Vec3<T>, 3);
+mat_value!(Vec4<T>, 4);
+
+/// Pixel extrapolation method used when an operation needs pixels outside
+/// of the image.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BorderTypes {
+    /// `iiiiii|abcdefgh|iiiiiii` with some specified `i`
+    Constant = 0,
+    /// `aaaaaa|abcdefgh|hhhhhhh`
+    Replicate = 1,
+    /// `fedcba|abcdefgh|hgfedcb`
+    Reflect = 2,
+    /// `cdefgh|abcdefgh|abcdefg`
+    Wrap = 3,
+    /// `gfedcb|abcdefgh|gfedcba`, the default in OpenCV
+    Reflect101 = 4,
+}
+
+impl Mat {
+    /// Copies the matrix into `dst`, reallocating it if needed. If `mask` is
+    /// given, only the elements where it is non-zero are copied and `dst`
+    /// keeps its other values.
+    pub fn copy_to(&self, dst: &mut Mat, mask: Option<&Mat>) -> Result<()> {
+        if unsafe { cv_mat_copy_to(self.inner, dst.inner, optional_mask(mask)) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("copy_to").into())
+        }
+    }
+
+    /// Converts the matrix to another depth, keeping the channel count. Each
+    /// element is computed as `saturate(alpha * x + beta)`.
+    pub fn convert_to(&self, depth: MatDepth, alpha: f64, beta: f64) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_mat_convert_to(self.inner, dst.inner, depth as c_int, alpha, beta) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("convert_to").into())
+        }
+    }
+
+    /// Sets all (or only the masked) elements to `value`.
+    pub fn set_to(&mut self, value: Scalar, mask: Option<&Mat>) -> Result<()> {
+        if unsafe { cv_mat_set_to(self.inner, value, optional_mask(mask)) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("set_to").into())
+        }
+    }
+
+    /// Changes the channel count and/or number of rows without copying the
+    /// data; 0 keeps the current value. The returned `Mat` shares the data
+    /// of this one, which must be continuous.
+    pub fn reshape(&self, channels: i32, rows: i32) -> Result<Mat> {
+        let m = unsafe { cv_mat_reshape(self.inner, channels, rows) };
+        if m.is_null() {
+            Err(ErrorKind::Operation("reshape").into())
+        } else {
+            Ok(Mat::from_raw(m))
+        }
+    }
+
+    /// Returns the rows `start..end` as a `Mat` sharing the data of this one.
+    pub fn row_range(&self, start: i32, end: i32) -> Result<Mat> {
+        let m = unsafe { cv_mat_row_range(self.inner, start, end) };
+        if m.is_null() {
+            Err(ErrorKind::Operation("row_range").into())
+        } else {
+            Ok(Mat::from_raw(m))
+        }
+    }
+
+    /// Returns the columns `start..end` as a `Mat` sharing the data of this
+    /// one.
+    pub fn col_range(&self, start: i32, end: i32) -> Result<Mat> {
+        let m = unsafe { cv_mat_col_range(self.inner, start, end) };
+        if m.is_null() {
+            Err(ErrorKind::Operation("col_range").into())
+        } else {
+            Ok(Mat::from_raw(m))
+        }
+    }
+
+    /// Returns row `y` as a `Mat` sharing the data of this one.
+    pub fn row(&self, y: i32) -> Result<Mat> {
+        self.row_range(y, y + 1)
+    }
+
+    /// Returns column `x` as a `Mat` sharing the data of this one.
+    pub fn col(&self, x: i32) -> Result<Mat> {
+        self.col_range(x, x + 1)
+    }
+
+    /// Concatenates matrices with the same number of rows and the same type
+    /// horizontally.
+    pub fn hconcat(mats: &[&Mat]) -> Result<Mat> {
+        let srcs: Vec<*const CMat> = mats.iter().map(|m| m.inner as *const CMat).collect();
+        let dst = Mat::new();
+        if unsafe { cv_hconcat(srcs.as_ptr(), srcs.len(), dst.inner) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("hconcat").into())
+        }
+    }
+
+    /// Concatenates matrices with the same number of columns and the same
+    /// type vertically.
+    pub fn vconcat(mats: &[&Mat]) -> Result<Mat> {
+        let srcs: Vec<*const CMat> = mats.iter().map(|m| m.inner as *const CMat).collect();
+        let dst = Mat::new();
+        if unsafe { cv_vconcat(srcs.as_ptr(), srcs.len(), dst.inner) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("vconcat").into())
+        }
+    }
+
+    /// Tiles the matrix `ny` times vertically and `nx` times horizontally.
+    pub fn repeat(&self, ny: i32, nx: i32) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_repeat(self.inner, ny, nx, dst.inner) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("repeat").into())
+        }
+    }
+
+    /// Returns a copy of the matrix surrounded by a border of the given
+    /// widths. `value` is only used with `BorderTypes::Constant`.
+    pub fn copy_make_border(
+        &self,
+        top: i32,
+        bottom: i32,
+        left: i32,
+        right: i32,
+        border_type: BorderTypes,
+        value: Scalar,
+    ) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe {
+            cv_copy_make_border(
+                self.inner,
+                dst.inner,
+                top,
+                bottom,
+                left,
+                right,
+                border_type as c_int,
+                value,
+            )
+        } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("copy_make_border").into())
+        }
+    }
+}
+
 // =============================================================================
 // core array
 // =============================================================================
@@ -355,3 +701,665 @@ impl Mat {
         unsafe { cv_count_non_zero(self.inner) }
     }
 }
+
+/// Initialization of the centers in [Mat::kmeans](struct.Mat.html#method.kmeans).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum KmeansFlags {
+    /// Random initial centers in each attempt.
+    RandomCenters = 0,
+    /// kmeans++ center initialization by Arthur and Vassilvitskii.
+    PpCenters = 2,
+}
+
+impl Mat {
+    /// Finds the centers of `k` clusters among the samples of `self` (one
+    /// `CV_32F` sample per row) and returns `(labels, centers,
+    /// compactness)`: the cluster index of every sample (`CV_32S`, one row
+    /// per sample), the cluster centers (one row per cluster) and the sum of
+    /// squared distances from each sample to its center.
+    ///
+    /// The algorithm runs `attempts` times and keeps the most compact
+    /// result; each run stops according to `criteria`.
+    pub fn kmeans(
+        &self,
+        k: i32,
+        criteria: &TermCriteria,
+        attempts: i32,
+        flags: KmeansFlags,
+    ) -> Result<(Mat, Mat, f64)> {
+        let labels = Mat::new();
+        self.kmeans_impl(k, labels, criteria, attempts, flags as c_int)
+    }
+
+    /// Same as [kmeans](#method.kmeans), but the first attempt starts from the
+    /// user supplied `labels` (`CV_32S`, one row per sample) instead of
+    /// initial centers. `flags` controls the initialization of the remaining
+    /// attempts.
+    pub fn kmeans_with_labels(
+        &self,
+        k: i32,
+        labels: &Mat,
+        criteria: &TermCriteria,
+        attempts: i32,
+        flags: KmeansFlags,
+    ) -> Result<(Mat, Mat, f64)> {
+        const KMEANS_USE_INITIAL_LABELS: c_int = 1;
+        let labels = labels.clone();
+        self.kmeans_impl(
+            k,
+            labels,
+            criteria,
+            attempts,
+            flags as c_int | KMEANS_USE_INITIAL_LABELS,
+        )
+    }
+
+    fn kmeans_impl(
+        &self,
+        k: i32,
+        labels: Mat,
+        criteria: &TermCriteria,
+        attempts: i32,
+        flags: c_int,
+    ) -> Result<(Mat, Mat, f64)> {
+        let centers = Mat::new();
+        let mut compactness = 0.0;
+        let ok = unsafe {
+            cv_kmeans(
+                self.inner,
+                k,
+                labels.inner,
+                criteria.c_criteria,
+                attempts,
+                flags,
+                centers.inner,
+                &mut compactness,
+            )
+        };
+        if ok {
+            Ok((labels, centers, compactness))
+        } else {
+            Err(ErrorKind::Operation("kmeans").into())
+        }
+    }
+}
+
+/// How the samples are stored in the data given to [Pca](struct.Pca.html).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PcaLayout {
+    /// Each sample is a row.
+    DataAsRow = 0,
+    /// Each sample is a column.
+    DataAsCol = 1,
+}
+
+/// Principal Component Analysis: finds the basis of the subspace that best
+/// explains a set of vectors, and projects vectors to and from that
+/// subspace.
+#[derive(Debug)]
+pub struct Pca {
+    inner: *mut CPca,
+}
+
+unsafe impl Send for Pca {}
+
+impl Pca {
+    /// Performs the analysis of `data` and keeps at most `max_components`
+    /// components (0 keeps all of them).
+    pub fn new(data: &Mat, layout: PcaLayout, max_components: i32) -> Result<Pca> {
+        Pca::from_raw(unsafe { cv_pca_new(data.inner, layout as c_int, max_components) })
+    }
+
+    /// Performs the analysis of `data` and keeps as few components as
+    /// needed to retain at least `retained_variance` (between 0 and 1) of
+    /// the total variance.
+    pub fn with_retained_variance(
+        data: &Mat,
+        layout: PcaLayout,
+        retained_variance: f64,
+    ) -> Result<Pca> {
+        Pca::from_raw(unsafe {
+            cv_pca_new_with_retained_variance(data.inner, layout as c_int, retained_variance)
+        })
+    }
+
+    fn from_raw(inner: *mut CPca) -> Result<Pca> {
+        if inner.is_null() {
+            Err(ErrorKind::Operation("PCA").into())
+        } else {
+            Ok(Pca { inner: inner })
+        }
+    }
+
+    /// Projects vectors (laid out as in the analyzed data) onto the
+    /// principal components subspace.
+    pub fn project(&self, vec: &Mat) -> Result<Mat> {
+        let result = Mat::new();
+        if unsafe { cv_pca_project(self.inner, vec.inner, result.inner) } {
+            Ok(result)
+        } else {
+            Err(ErrorKind::Operation("PCA project").into())
+        }
+    }
+
+    /// Reconstructs the original vectors from their projections.
+    pub fn back_project(&self, vec: &Mat) -> Result<Mat> {
+        let result = Mat::new();
+        if unsafe { cv_pca_back_project(self.inner, vec.inner, result.inner) } {
+            Ok(result)
+        } else {
+            Err(ErrorKind::Operation("PCA back project").into())
+        }
+    }
+
+    /// Returns the eigenvectors of the covariance matrix, one per row,
+    /// sorted by decreasing eigenvalue.
+    pub fn eigenvectors(&self) -> Mat {
+        Mat::from_raw(unsafe { cv_pca_eigenvectors(self.inner) })
+    }
+
+    /// Returns the eigenvalues of the covariance matrix in decreasing order.
+    pub fn eigenvalues(&self) -> Mat {
+        Mat::from_raw(unsafe { cv_pca_eigenvalues(self.inner) })
+    }
+
+    /// Returns the mean vector of the analyzed data.
+    pub fn mean(&self) -> Mat {
+        Mat::from_raw(unsafe { cv_pca_mean(self.inner) })
+    }
+}
+
+impl Drop for Pca {
+    fn drop(&mut self) {
+        unsafe {
+            cv_pca_drop(self.inner);
+        }
+    }
+}
+
+/// Matrix decomposition used to invert matrices or solve linear systems.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DecompTypes {
+    /// Gaussian elimination with the optimal pivot element chosen.
+    Lu = 0,
+    /// Singular value decomposition; the system can be over-defined and/or
+    /// the matrix singular.
+    Svd = 1,
+    /// Eigenvalue decomposition; the matrix must be symmetrical.
+    Eig = 2,
+    /// Cholesky factorization; the matrix must be symmetrical and positively
+    /// defined.
+    Cholesky = 3,
+    /// QR factorization; the system can be over-defined and/or the matrix
+    /// singular.
+    Qr = 4,
+}
+
+bitflags! {
+    /// Transposition flags for [Mat::gemm](struct.Mat.html#method.gemm).
+    pub struct GemmFlags: c_int {
+        /// Transposes the first matrix.
+        const GEMM_1_T = 1;
+        /// Transposes the second matrix.
+        const GEMM_2_T = 2;
+        /// Transposes the third matrix.
+        const GEMM_3_T = 4;
+    }
+}
+
+bitflags! {
+    /// Flags for [Svd::compute](struct.Svd.html#method.compute).
+    pub struct SvdFlags: c_int {
+        /// Only computes the singular values; `u` and `vt` are empty.
+        const NO_UV = 2;
+        /// Computes full-size `u` and `vt` for non-square matrices.
+        const FULL_UV = 4;
+    }
+}
+
+impl Mat {
+    /// Performs the generalized matrix multiplication `alpha * self * other +
+    /// beta * src3`, where each operand may be transposed according to
+    /// `flags`. Fails if the sizes or types don't match.
+    pub fn gemm(
+        &self,
+        other: &Mat,
+        alpha: f64,
+        src3: Option<&Mat>,
+        beta: f64,
+        flags: GemmFlags,
+    ) -> Result<Mat> {
+        let dst = Mat::new();
+        let src3 = src3.map_or(::std::ptr::null(), |m| m.inner);
+        if unsafe {
+            cv_gemm(
+                self.inner,
+                other.inner,
+                alpha,
+                src3,
+                beta,
+                dst.inner,
+                flags.bits(),
+            )
+        } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("gemm").into())
+        }
+    }
+
+    /// Returns the transposed matrix. Fails for matrices with more than 2
+    /// dimensions.
+    pub fn transpose(&self) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_transpose(self.inner, dst.inner) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("transpose").into())
+        }
+    }
+
+    /// Returns the transposed matrix, same as [transpose](#method.transpose).
+    pub fn t(&self) -> Result<Mat> {
+        self.transpose()
+    }
+
+    /// Returns the inverse of the matrix. `DecompTypes::Svd` also accepts
+    /// non-square matrices and computes the pseudo-inverse, `DecompTypes::Eig`
+    /// and `DecompTypes::Cholesky` require a symmetrical matrix.
+    ///
+    /// Fails with `ErrorKind::SingularMatrix` if the matrix is singular and
+    /// `method` is `DecompTypes::Lu` or `DecompTypes::Cholesky`; the other
+    /// methods return the pseudo-inverse of singular matrices.
+    pub fn invert(&self, method: DecompTypes) -> Result<Mat> {
+        let dst = Mat::new();
+        let mut result = 0.0;
+        if !unsafe { cv_invert(self.inner, dst.inner, method as c_int, &mut result) } {
+            return Err(ErrorKind::Operation("invert").into());
+        }
+        let exact = method == DecompTypes::Lu || method == DecompTypes::Cholesky;
+        if exact && result == 0.0 {
+            Err(ErrorKind::SingularMatrix.into())
+        } else {
+            Ok(dst)
+        }
+    }
+
+    /// Solves the linear system `self * x = rhs` and returns `x`. With
+    /// `DecompTypes::Svd` or `DecompTypes::Qr`, over-defined systems are
+    /// solved in the least-squares sense.
+    ///
+    /// Fails with `ErrorKind::SingularMatrix` if the matrix is singular.
+    pub fn solve(&self, rhs: &Mat, method: DecompTypes) -> Result<Mat> {
+        let dst = Mat::new();
+        let mut solved = false;
+        if !unsafe {
+            cv_solve(
+                self.inner,
+                rhs.inner,
+                dst.inner,
+                method as c_int,
+                &mut solved,
+            )
+        } {
+            return Err(ErrorKind::Operation("solve").into());
+        }
+        if solved {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::SingularMatrix.into())
+        }
+    }
+
+    /// Returns the determinant of a square floating-point matrix.
+    pub fn determinant(&self) -> Result<f64> {
+        let mut determinant = 0.0;
+        if unsafe { cv_determinant(self.inner, &mut determinant) } {
+            Ok(determinant)
+        } else {
+            Err(ErrorKind::Operation("determinant").into())
+        }
+    }
+
+    /// Returns the sum of the diagonal elements (of the first channel).
+    pub fn trace(&self) -> Result<f64> {
+        let mut trace = 0.0;
+        if unsafe { cv_trace(self.inner, &mut trace) } {
+            Ok(trace)
+        } else {
+            Err(ErrorKind::Operation("trace").into())
+        }
+    }
+
+    /// Computes the eigenvalues and eigenvectors of a symmetric matrix and
+    /// returns `(eigenvalues, eigenvectors)`: the eigenvalues in descending
+    /// order as a column, and the eigenvectors as rows in the same order.
+    pub fn eigen(&self) -> Result<(Mat, Mat)> {
+        let eigenvalues = Mat::new();
+        let eigenvectors = Mat::new();
+        if unsafe { cv_eigen(self.inner, eigenvalues.inner, eigenvectors.inner) } {
+            Ok((eigenvalues, eigenvectors))
+        } else {
+            Err(ErrorKind::Operation("eigen").into())
+        }
+    }
+
+    /// Computes `scale * (self - delta)^T * (self - delta)` if `a_top` is
+    /// true, `scale * (self - delta) * (self - delta)^T` otherwise.
+    pub fn mul_transposed(&self, a_top: bool, delta: Option<&Mat>, scale: f64) -> Result<Mat> {
+        let dst = Mat::new();
+        let delta = delta.map_or(::std::ptr::null(), |m| m.inner);
+        if unsafe { cv_mul_transposed(self.inner, dst.inner, a_top, delta, scale) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("mul_transposed").into())
+        }
+    }
+}
+
+/// Singular value decomposition of a floating-point matrix: `src = u *
+/// diag(w) * vt`.
+#[derive(Debug)]
+pub struct Svd {
+    /// The singular values, as a column in descending order.
+    pub w: Mat,
+    /// The left singular vectors, as columns.
+    pub u: Mat,
+    /// The transposed right singular vectors, as rows.
+    pub vt: Mat,
+}
+
+impl Svd {
+    /// Decomposes `src`.
+    pub fn compute(src: &Mat, flags: SvdFlags) -> Result<Svd> {
+        let svd = Svd {
+            w: Mat::new(),
+            u: Mat::new(),
+            vt: Mat::new(),
+        };
+        if unsafe {
+            cv_svd_compute(
+                src.inner,
+                svd.w.inner,
+                svd.u.inner,
+                svd.vt.inner,
+                flags.bits(),
+            )
+        } {
+            Ok(svd)
+        } else {
+            Err(ErrorKind::Operation("SVD").into())
+        }
+    }
+}
+
+bitflags! {
+    /// Flags for the discrete Fourier and cosine transforms, see
+    /// [Mat::dft](struct.Mat.html#method.dft).
+    pub struct DftFlags: c_int {
+        /// Performs an inverse transform instead of the forward one.
+        const DFT_INVERSE = 1;
+        /// Scales the result by dividing it by the number of array elements.
+        const DFT_SCALE = 2;
+        /// Transforms each row independently.
+        const DFT_ROWS = 4;
+        /// Produces a full complex output (2 channels) from real input,
+        /// instead of the packed CCS format.
+        const DFT_COMPLEX_OUTPUT = 16;
+        /// Produces a real output from complex conjugate-symmetrical input,
+        /// typically for inverse transforms.
+        const DFT_REAL_OUTPUT = 32;
+    }
+}
+
+/// Returns the optimal DFT size for a vector of size `vecsize`: the smallest
+/// number greater than or equal to `vecsize` that is a product of 2, 3 and
+/// 5. Padding arrays to this size speeds up the transform.
+pub fn get_optimal_dft_size(vecsize: i32) -> i32 {
+    unsafe { cv_get_optimal_dft_size(vecsize) }
+}
+
+impl Mat {
+    /// Performs a forward (or, with `DftFlags::DFT_INVERSE`, inverse)
+    /// discrete Fourier transform of a 1D or 2D floating-point array. Real
+    /// input produces the packed CCS format unless
+    /// `DftFlags::DFT_COMPLEX_OUTPUT` is set; complex input has 2 channels.
+    ///
+    /// Only the first `nonzero_rows` rows of the input (or of the output
+    /// for inverse transforms) are assumed to be non-zero; 0 processes all
+    /// rows.
+    pub fn dft(&self, flags: DftFlags, nonzero_rows: i32) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_dft(self.inner, dst.inner, flags.bits(), nonzero_rows) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("dft").into())
+        }
+    }
+
+    /// Performs an inverse discrete Fourier transform. Note that the result
+    /// is not scaled unless `DftFlags::DFT_SCALE` is set, and that
+    /// `DftFlags::DFT_REAL_OUTPUT` returns a real array.
+    pub fn idft(&self, flags: DftFlags, nonzero_rows: i32) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_idft(self.inner, dst.inner, flags.bits(), nonzero_rows) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("idft").into())
+        }
+    }
+
+    /// Performs a forward (or, with `DftFlags::DFT_INVERSE`, inverse)
+    /// discrete cosine transform of a 1D or 2D floating-point array of even
+    /// size. Only `DftFlags::DFT_INVERSE` and `DftFlags::DFT_ROWS` apply.
+    pub fn dct(&self, flags: DftFlags) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_dct(self.inner, dst.inner, flags.bits()) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("dct").into())
+        }
+    }
+
+    /// Performs an inverse discrete cosine transform.
+    pub fn idct(&self, flags: DftFlags) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_idct(self.inner, dst.inner, flags.bits()) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("idct").into())
+        }
+    }
+
+    /// Performs the per-element multiplication of two Fourier spectrums
+    /// (either packed CCS or complex), conjugating `other` first if
+    /// `conj_b` is set. Only `DftFlags::DFT_ROWS` applies.
+    pub fn mul_spectrums(&self, other: &Mat, flags: DftFlags, conj_b: bool) -> Result<Mat> {
+        let dst = Mat::new();
+        if unsafe { cv_mul_spectrums(self.inner, other.inner, dst.inner, flags.bits(), conj_b) } {
+            Ok(dst)
+        } else {
+            Err(ErrorKind::Operation("mul_spectrums").into())
+        }
+    }
+}
+
+/// The dimension along which [Mat::reduce](struct.Mat.html#method.reduce)
+/// reduces a matrix.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReduceDimension {
+    /// Reduces the matrix to a single row (one value per column).
+    ToRow = 0,
+    /// Reduces the matrix to a single column (one value per row).
+    ToCol = 1,
+}
+
+/// The reduction operation of [Mat::reduce](struct.Mat.html#method.reduce).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReduceTypes {
+    /// The sum over all rows/columns.
+    Sum = 0,
+    /// The mean vector of all rows/columns.
+    Avg = 1,
+    /// The maximum over all rows/columns.
+    Max = 2,
+    /// The minimum over all rows/columns.
+    Min = 3,
+}
+
+/// Maximum number of dimensions of a `Mat`.
+const CV_MAX_DIM: usize = 32;
+
+fn optional_mask(mask: Option<&Mat>) -> *const CMat {
+    mask.map_or(::std::ptr::null(), |m| m.inner)
+}
+
+impl Mat {
+    /// Returns the mean of each channel, optionally only over the elements
+    /// where `mask` (`CV_8UC1`, same size) is non-zero. Unused channels are
+    /// 0.
+    pub fn mean(&self, mask: Option<&Mat>) -> Result<Scalar> {
+        let mut mean = Scalar::default();
+        if unsafe { cv_mean(self.inner, optional_mask(mask), &mut mean) } {
+            Ok(mean)
+        } else {
+            Err(ErrorKind::Operation("mean").into())
+        }
+    }
+
+    /// Returns the mean and standard deviation of each channel as `(mean,
+    /// stddev)`, optionally only over the elements where `mask` is non-zero.
+    pub fn mean_std_dev(&self, mask: Option<&Mat>) -> Result<(Scalar, Scalar)> {
+        let mut mean = Scalar::default();
+        let mut stddev = Scalar::default();
+        if unsafe { cv_mean_std_dev(self.inner, optional_mask(mask), &mut mean, &mut stddev) } {
+            Ok((mean, stddev))
+        } else {
+            Err(ErrorKind::Operation("mean_std_dev").into())
+        }
+    }
+
+    /// Returns the sum of the elements of each channel. Fails for matrices
+    /// with more than 4 channels.
+    pub fn sum(&self) -> Result<Scalar> {
+        let mut sum = Scalar::default();
+        if unsafe { cv_sum(self.inner, &mut sum) } {
+            Ok(sum)
+        } else {
+            Err(ErrorKind::Operation("sum").into())
+        }
+    }
+
+    /// Finds the global minimum and maximum of a single-channel matrix and
+    /// returns `(min, max, min_loc, max_loc)`, optionally only over the
+    /// elements where `mask` is non-zero.
+    pub fn min_max_loc(&self, mask: Option<&Mat>) -> Result<(f64, f64, Point2i, Point2i)> {
+        let mut min_val = 0.0;
+        let mut max_val = 0.0;
+        let mut min_loc = Point2i::default();
+        let mut max_loc = Point2i::default();
+        if unsafe {
+            cv_min_max_loc(
+                self.inner,
+                optional_mask(mask),
+                &mut min_val,
+                &mut max_val,
+                &mut min_loc,
+                &mut max_loc,
+            )
+        } {
+            Ok((min_val, max_val, min_loc, max_loc))
+        } else {
+            Err(ErrorKind::Operation("min_max_loc").into())
+        }
+    }
+
+    /// Finds the global minimum and maximum of a single-channel matrix and
+    /// returns `(min, max, min_idx, max_idx)`, where the indices hold one
+    /// coordinate per dimension (row first). Unlike
+    /// [min_max_loc](#method.min_max_loc), this works with matrices of any
+    /// dimension.
+    pub fn min_max_idx(&self, mask: Option<&Mat>) -> Result<(f64, f64, Vec<i32>, Vec<i32>)> {
+        let mut min_val = 0.0;
+        let mut max_val = 0.0;
+        let mut min_idx = vec![0; CV_MAX_DIM];
+        let mut max_idx = vec![0; CV_MAX_DIM];
+        let dims = unsafe {
+            cv_min_max_idx(
+                self.inner,
+                optional_mask(mask),
+                &mut min_val,
+                &mut max_val,
+                min_idx.as_mut_ptr(),
+                max_idx.as_mut_ptr(),
+            )
+        };
+        if dims < 0 {
+            return Err(ErrorKind::Operation("min_max_idx").into());
+        }
+        min_idx.truncate(dims as usize);
+        max_idx.truncate(dims as usize);
+        Ok((min_val, max_val, min_idx, max_idx))
+    }
+
+    /// Returns the absolute norm of the matrix, optionally only over the
+    /// elements where `mask` is non-zero.
+    pub fn norm(&self, norm_type: NormTypes, mask: Option<&Mat>) -> Result<f64> {
+        let mut norm = 0.0;
+        if unsafe {
+            cv_norm(
+                self.inner,
+                norm_type as c_int,
+                optional_mask(mask),
+                &mut norm,
+            )
+        } {
+            Ok(norm)
+        } else {
+            Err(ErrorKind::Operation("norm").into())
+        }
+    }
+
+    /// Returns the norm of the difference `self - other`. Fails if the
+    /// matrices don't have the same size and type.
+    pub fn norm_diff(&self, other: &Mat, norm_type: NormTypes, mask: Option<&Mat>) -> Result<f64> {
+        let mut norm = 0.0;
+        if unsafe {
+            cv_norm_diff(
+                self.inner,
+                other.inner,
+                norm_type as c_int,
+                optional_mask(mask),
+                &mut norm,
+            )
+        } {
+            Ok(norm)
+        } else {
+            Err(ErrorKind::Operation("norm_diff").into())
+        }
+    }
+
+    /// Reduces the matrix to a vector by treating its rows (or columns) as
+    /// a set of vectors and applying `rtype` to them. The values of
+    /// multi-channel matrices are interleaved.
+    pub fn reduce(&self, dim: ReduceDimension, rtype: ReduceTypes) -> Result<Vec<f64>> {
+        let mut result = CVecDouble::default();
+        if unsafe { cv_reduce(self.inner, dim as c_int, rtype as c_int, &mut result) } {
+            Ok(result.rustify())
+        } else {
+            Err(ErrorKind::Operation("reduce").into())
+        }
+    }
+
+    /// Returns the Peak Signal-to-Noise Ratio (in dB) between two `CV_8U`
+    /// matrices of the same size; identical matrices give a high value
+    /// (361 dB).
+    pub fn psnr(&self, other: &Mat) -> Result<f64> {
+        let mut psnr = 0.0;
+        if unsafe { cv_psnr(self.inner, other.inner, &mut psnr) } {
+            Ok(psnr)
+        } else {
+            Err(ErrorKind::Operation("psnr").into())
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 use libc::{c_double, c_int, size_t};
 use super::core::*;
+use super::errors::*;
 use super::objdetect::{CSvmDetector, HogParams, ObjectDetect, SvmDetector};
 
 /// Opaque data struct for C/C++ cv::cuda::GpuMat bindings
@@ -107,7 +108,7 @@ extern "C" {
         nbins: i32,
     ) -> *mut CGpuHog;
     fn cv_gpu_hog_drop(hog: *mut CGpuHog);
-    fn cv_gpu_hog_set_detector(hog: *mut CGpuHog, d: *const CSvmDetector);
+    fn cv_gpu_hog_set_detector(hog: *mut CGpuHog, d: *const CSvmDetector) -> bool;
     fn cv_gpu_hog_detect(hog: *mut CGpuHog, mat: *mut CGpuMat, found: *mut CVecOfRect);
     fn cv_gpu_hog_detect_with_conf(
         hog: *mut CGpuHog,
@@ -225,9 +226,14 @@ impl GpuHog {
         params.win_stride = unsafe { cv_gpu_hog_get_win_stride(inner) };
     }
 
-    /// Sets the SVM detector.
-    pub fn set_svm_detector(&mut self, detector: SvmDetector) {
-        unsafe { cv_gpu_hog_set_detector(self.inner, detector.inner) }
+    /// Sets the SVM detector. Fails if the detector doesn't match the
+    /// descriptor size.
+    pub fn set_svm_detector(&mut self, detector: SvmDetector) -> Result<()> {
+        if unsafe { cv_gpu_hog_set_detector(self.inner, detector.inner) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("set_svm_detector").into())
+        }
     }
 
     /// Detects according to the SVM detector specified.
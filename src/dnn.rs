@@ -0,0 +1,589 @@
+//! Deep Neural Network module, see [OpenCV
+//! dnn](https://docs.opencv.org/3.4.3/d6/d0f/group__dnn.html). Besides the
+//! bindings to `cv::dnn::Net`, this module provides ready-made detectors that
+//! decode the output of common detection networks and implement
+//! [ObjectDetect](../objdetect/trait.ObjectDetect.html).
+
+use super::core::*;
+use super::errors::*;
+use super::imgproc::ColorConversionCodes;
+use super::objdetect::{Detection, ObjectDetect};
+use libc::{c_char, c_double, c_float, c_int, size_t};
+use std::collections::BTreeMap;
+use std::ffi::CString;
+use std::path::Path;
+
+use super::wrapper::*;
+
+enum CDnnNet {}
+
+extern "C" {
+    fn cv_dnn_net_from_caffe(prototxt: *const c_char, model: *const c_char) -> *mut CDnnNet;
+    fn cv_dnn_net_from_tensorflow(model: *const c_char, config: *const c_char) -> *mut CDnnNet;
+    fn cv_dnn_net_from_darknet(cfg: *const c_char, model: *const c_char) -> *mut CDnnNet;
+    fn cv_dnn_net_from_onnx(model: *const c_char) -> *mut CDnnNet;
+    fn cv_dnn_net_drop(net: *mut CDnnNet);
+    fn cv_dnn_net_set_input(net: *mut CDnnNet, blob: *const CMat, name: *const c_char) -> bool;
+    fn cv_dnn_net_forward(net: *mut CDnnNet, output_name: *const c_char) -> *mut CMat;
+    fn cv_dnn_net_forward_unconnected(net: *mut CDnnNet, outputs: *mut CVecOfMat) -> bool;
+    fn cv_dnn_blob_from_image(
+        image: *const CMat,
+        scale: c_double,
+        size: Size2i,
+        mean: Scalar,
+        swap_rb: bool,
+        crop: bool,
+    ) -> *mut CMat;
+    fn cv_dnn_nms_boxes(
+        bboxes: *const Rect,
+        scores: *const c_float,
+        len: size_t,
+        score_threshold: c_float,
+        nms_threshold: c_float,
+        indices: *mut c_int,
+        num_kept: *mut size_t,
+    ) -> bool;
+}
+
+// =============================================================================
+//   Net
+// =============================================================================
+
+/// A deep neural network, loaded from one of the model formats supported by
+/// OpenCV.
+#[derive(Debug)]
+pub struct Net {
+    inner: *mut CDnnNet,
+}
+
+unsafe impl Send for Net {}
+
+impl Net {
+    fn from_raw<P: AsRef<Path>>(inner: *mut CDnnNet, path: P) -> Result<Net> {
+        if inner.is_null() {
+            Err(ErrorKind::InvalidPath(path.as_ref().to_path_buf()).into())
+        } else {
+            Ok(Net { inner: inner })
+        }
+    }
+
+    /// Loads a network stored in Caffe's format: `prototxt` describes the
+    /// network architecture and `model` holds the learned weights.
+    pub fn from_caffe<P: AsRef<Path>, M: AsRef<Path>>(prototxt: P, model: M) -> Result<Net> {
+        let p = path_to_cstring(&prototxt)?;
+        let m = path_to_cstring(&model)?;
+        let inner = unsafe { cv_dnn_net_from_caffe(p.as_ptr(), m.as_ptr()) };
+        Net::from_raw(inner, model)
+    }
+
+    /// Loads a network stored in TensorFlow's format: `model` is the frozen
+    /// graph (`.pb`) and `config` the text graph definition (`.pbtxt`).
+    pub fn from_tensorflow<M: AsRef<Path>, C: AsRef<Path>>(model: M, config: C) -> Result<Net> {
+        let m = path_to_cstring(&model)?;
+        let c = path_to_cstring(&config)?;
+        let inner = unsafe { cv_dnn_net_from_tensorflow(m.as_ptr(), c.as_ptr()) };
+        Net::from_raw(inner, model)
+    }
+
+    /// Loads a network stored in Darknet's format: `cfg` describes the
+    /// network architecture and `model` holds the learned weights.
+    pub fn from_darknet<C: AsRef<Path>, M: AsRef<Path>>(cfg: C, model: M) -> Result<Net> {
+        let c = path_to_cstring(&cfg)?;
+        let m = path_to_cstring(&model)?;
+        let inner = unsafe { cv_dnn_net_from_darknet(c.as_ptr(), m.as_ptr()) };
+        Net::from_raw(inner, model)
+    }
+
+    /// Loads a network stored in the [ONNX](https://onnx.ai/) format.
+    pub fn from_onnx<P: AsRef<Path>>(model: P) -> Result<Net> {
+        let m = path_to_cstring(&model)?;
+        let inner = unsafe { cv_dnn_net_from_onnx(m.as_ptr()) };
+        Net::from_raw(inner, model)
+    }
+
+    /// Sets the new value for the layer output blob `name`. An empty name
+    /// sets the default input of the network. Fails if the network has no
+    /// input called `name`.
+    pub fn set_input(&self, blob: &Mat, name: &str) -> Result<()> {
+        let s = CString::new(name)?;
+        if unsafe { cv_dnn_net_set_input(self.inner, blob.inner, s.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("set_input").into())
+        }
+    }
+
+    /// Runs a forward pass and returns the output blob of the layer
+    /// `output_name`. An empty name returns the output of the last layer.
+    pub fn forward(&self, output_name: &str) -> Result<Mat> {
+        let s = CString::new(output_name)?;
+        let m = unsafe { cv_dnn_net_forward(self.inner, s.as_ptr()) };
+        if m.is_null() {
+            Err(ErrorKind::Operation("forward").into())
+        } else {
+            Ok(Mat::from_raw(m))
+        }
+    }
+
+    /// Runs a forward pass and returns the output blobs of all layers that
+    /// don't feed into any other layer (e.g. the three YOLOv3 heads).
+    pub fn forward_all(&self) -> Result<Vec<Mat>> {
+        let mut outputs = CVecOfMat::default();
+        if unsafe { cv_dnn_net_forward_unconnected(self.inner, &mut outputs) } {
+            Ok(outputs.rustify())
+        } else {
+            Err(ErrorKind::Operation("forward_all").into())
+        }
+    }
+}
+
+impl Drop for Net {
+    fn drop(&mut self) {
+        unsafe {
+            cv_dnn_net_drop(self.inner);
+        }
+    }
+}
+
+/// Creates a 4-dimensional blob (NCHW) from `image`. The image is resized to
+/// `size` (or cropped after resizing if `crop` is set), `mean` is subtracted,
+/// values are multiplied by `scale`, and the first and last channels are
+/// swapped if `swap_rb` is set.
+pub fn blob_from_image(
+    image: &Mat,
+    scale: f64,
+    size: Size2i,
+    mean: Scalar,
+    swap_rb: bool,
+    crop: bool,
+) -> Result<Mat> {
+    let m = unsafe { cv_dnn_blob_from_image(image.inner, scale, size, mean, swap_rb, crop) };
+    if m.is_null() {
+        Err(ErrorKind::Operation("blob_from_image").into())
+    } else {
+        Ok(Mat::from_raw(m))
+    }
+}
+
+/// Performs non maximum suppression given boxes and their scores. Boxes
+/// scoring below `score_threshold` are dropped, and of the boxes overlapping
+/// more than `nms_threshold` (intersection over union), only the best one is
+/// kept. Returns the indices of the kept boxes, or fails if there isn't one
+/// score per box.
+pub fn nms_boxes(
+    bboxes: &[Rect],
+    scores: &[f32],
+    score_threshold: f32,
+    nms_threshold: f32,
+) -> Result<Vec<usize>> {
+    if bboxes.len() != scores.len() {
+        let expected = vec![bboxes.len() as i32];
+        return Err(ErrorKind::ShapeMismatch(expected, vec![scores.len() as i32]).into());
+    }
+    let mut indices: Vec<c_int> = vec![0; bboxes.len()];
+    let mut kept = 0;
+    let ok = unsafe {
+        cv_dnn_nms_boxes(
+            bboxes.as_ptr(),
+            scores.as_ptr(),
+            bboxes.len(),
+            score_threshold,
+            nms_threshold,
+            indices.as_mut_ptr(),
+            &mut kept,
+        )
+    };
+    if !ok {
+        return Err(ErrorKind::Operation("nms_boxes").into());
+    }
+    Ok(indices
+        .into_iter()
+        .take(kept)
+        .map(|i| i as usize)
+        .collect::<Vec<_>>())
+}
+
+// =============================================================================
+//   Detectors
+// =============================================================================
+
+/// Parameters that control how the DNN-based detectors preprocess images and
+/// filter the raw network output.
+#[derive(Debug, Clone, Copy)]
+pub struct DnnDetectorParams {
+    /// Spatial size the image is resized to before being fed to the network.
+    pub input_size: Size2i,
+
+    /// Multiplier for the pixel values, applied after mean subtraction.
+    pub scale: f64,
+
+    /// Mean value subtracted from each channel.
+    pub mean: Scalar,
+
+    /// Whether to swap the first and the last channels (BGR to RGB).
+    pub swap_rb: bool,
+
+    /// Detections with a confidence below this threshold are dropped.
+    pub confidence_threshold: f32,
+
+    /// Overlap (intersection over union) above which the weaker of two
+    /// detections of the same class is suppressed.
+    pub nms_threshold: f32,
+}
+
+impl Default for DnnDetectorParams {
+    fn default() -> DnnDetectorParams {
+        DnnDetectorParams {
+            input_size: Size2i::new(300, 300),
+            scale: 1.0,
+            mean: Scalar::default(),
+            swap_rb: false,
+            confidence_threshold: 0.5,
+            nms_threshold: 0.4,
+        }
+    }
+}
+
+impl DnnDetectorParams {
+    fn blob(&self, image: &Mat) -> Result<Mat> {
+        // The networks are trained on color images
+        if image.mat_type().channels() == 1 {
+            let bgr = image.cvt_color(ColorConversionCodes::GRAY2BGR);
+            blob_from_image(
+                &bgr,
+                self.scale,
+                self.input_size,
+                self.mean,
+                self.swap_rb,
+                false,
+            )
+        } else {
+            blob_from_image(
+                image,
+                self.scale,
+                self.input_size,
+                self.mean,
+                self.swap_rb,
+                false,
+            )
+        }
+    }
+
+    fn run(&self, net: &Net, image: &Mat) -> Result<Vec<Mat>> {
+        let blob = self.blob(image)?;
+        net.set_input(&blob, "")?;
+        net.forward_all()
+    }
+
+    /// Applies non maximum suppression separately for each class.
+    fn suppress(&self, detections: Vec<Detection>) -> Result<Vec<Detection>> {
+        let mut classes = BTreeMap::new();
+        for d in detections {
+            classes.entry(d.class_id).or_insert_with(Vec::new).push(d);
+        }
+
+        let mut kept = Vec::new();
+        for (_, dets) in classes {
+            let rects = dets.iter().map(|d| d.rect).collect::<Vec<_>>();
+            let scores = dets.iter().map(|d| d.score as f32).collect::<Vec<_>>();
+            let indices = nms_boxes(
+                &rects,
+                &scores,
+                self.confidence_threshold,
+                self.nms_threshold,
+            )?;
+            kept.extend(indices.into_iter().map(|i| dets[i].clone()));
+        }
+        Ok(kept)
+    }
+
+    /// Runs `net` on `image`, decodes each of its outputs with `decode` and
+    /// suppresses duplicate detections. Empty images have no detections.
+    fn detect<F>(&self, net: &Net, image: &Mat, decode: F) -> Result<Vec<Detection>>
+    where
+        F: Fn(&Mat, Size2i, f32) -> Vec<Detection>,
+    {
+        if image.total() == 0 {
+            return Ok(Vec::new());
+        }
+        let detections = self
+            .run(net, image)?
+            .iter()
+            .flat_map(|out| decode(out, image.size(), self.confidence_threshold))
+            .collect::<Vec<_>>();
+        self.suppress(detections)
+    }
+}
+
+/// Fills in the labels of `detections` from their class ids.
+fn label(mut detections: Vec<Detection>, labels: &[String]) -> Vec<Detection> {
+    for d in &mut detections {
+        d.label = labels.get(d.class_id).cloned();
+    }
+    detections
+}
+
+fn strip_meta(detections: Vec<Detection>) -> Vec<(Rect, f64)> {
+    detections
+        .into_iter()
+        .map(|d| (d.rect, d.score))
+        .collect::<Vec<_>>()
+}
+
+/// Returns the elements of `mat`, or `None` unless it's a continuous 32-bit
+/// float matrix.
+fn mat_as_f32_slice(mat: &Mat) -> Option<&[f32]> {
+    if mat.mat_type().depth() != MatDepth::Float32 || !mat.is_continuous() {
+        return None;
+    }
+    let len = mat.total() * mat.mat_type().channels() as usize;
+    Some(unsafe { ::std::slice::from_raw_parts(mat.data() as *const f32, len) })
+}
+
+fn rect_from_corners(left: f32, top: f32, right: f32, bottom: f32) -> Rect {
+    Rect::new(
+        left as i32,
+        top as i32,
+        (right - left) as i32,
+        (bottom - top) as i32,
+    )
+}
+
+/// Decodes the output of an SSD-style network: a `1x1xNx7` blob whose rows
+/// are `[image_id, class_id, confidence, left, top, right, bottom]`, with
+/// coordinates relative to the image size. The boxes are reported in pixels
+/// of `frame_size`, without labels. Outputs that are not continuous 32-bit
+/// float blobs have no detections.
+pub fn decode_ssd(output: &Mat, frame_size: Size2i, confidence_threshold: f32) -> Vec<Detection> {
+    let data = match mat_as_f32_slice(output) {
+        Some(data) => data,
+        None => return Vec::new(),
+    };
+
+    let w = frame_size.width as f32;
+    let h = frame_size.height as f32;
+    data.chunks(7)
+        .filter(|row| row.len() == 7 && row[2] >= confidence_threshold)
+        .map(|row| {
+            let rect = rect_from_corners(row[3] * w, row[4] * h, row[5] * w, row[6] * h);
+            Detection {
+                class_id: row[1] as usize,
+                ..Detection::new(rect, row[2] as f64)
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Decodes the output of a YOLO-style (Darknet region) layer: a `Nx(5+C)`
+/// blob, optionally with a leading batch dimension, whose rows are
+/// `[center_x, center_y, width, height, objectness, class scores...]`, with
+/// coordinates relative to the image size. Each row is assigned its best
+/// scoring class, whose score becomes the confidence. The boxes are reported
+/// in pixels of `frame_size`, without labels. Outputs that are not continuous
+/// 32-bit float blobs of 2 or 3 dimensions have no detections.
+pub fn decode_yolo(output: &Mat, frame_size: Size2i, confidence_threshold: f32) -> Vec<Detection> {
+    let dims = output.dims();
+    if dims != 2 && dims != 3 {
+        return Vec::new();
+    }
+    // `cols()` is -1 for blobs of more than 2 dimensions
+    let cols = output.sizes().last().cloned().unwrap_or(0) as usize;
+    if cols <= 5 {
+        return Vec::new();
+    }
+    let data = match mat_as_f32_slice(output) {
+        Some(data) => data,
+        None => return Vec::new(),
+    };
+
+    let w = frame_size.width as f32;
+    let h = frame_size.height as f32;
+    data.chunks(cols)
+        .filter_map(|row| {
+            let (class_id, score) =
+                row[5..]
+                    .iter()
+                    .cloned()
+                    .enumerate()
+                    .fold(
+                        (0, 0f32),
+                        |best, (i, s)| if s > best.1 { (i, s) } else { best },
+                    );
+            if score < confidence_threshold {
+                return None;
+            }
+
+            let (cx, cy) = (row[0] * w, row[1] * h);
+            let (bw, bh) = (row[2] * w, row[3] * h);
+            let rect =
+                rect_from_corners(cx - bw / 2.0, cy - bh / 2.0, cx + bw / 2.0, cy + bh / 2.0);
+            Some(Detection {
+                class_id: class_id,
+                ..Detection::new(rect, score as f64)
+            })
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Detector for SSD-style networks (e.g. MobileNet-SSD), see
+/// [decode_ssd](fn.decode_ssd.html) for the expected output format.
+#[derive(Debug)]
+pub struct SsdDetector {
+    net: Net,
+
+    /// Preprocessing and filtering parameters.
+    pub params: DnnDetectorParams,
+
+    /// Class names, indexed by class id. Used to label the detections.
+    pub labels: Vec<String>,
+}
+
+impl SsdDetector {
+    /// Creates an SSD detector from a loaded network.
+    pub fn new(net: Net, params: DnnDetectorParams) -> SsdDetector {
+        SsdDetector {
+            net: net,
+            params: params,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Detects objects in `image`. Unlike the `ObjectDetect` methods, which
+    /// report no detections in that case, this fails if the network can't
+    /// process the image.
+    pub fn try_detect(&self, image: &Mat) -> Result<Vec<Detection>> {
+        let detections = self.params.detect(&self.net, image, decode_ssd)?;
+        Ok(label(detections, &self.labels))
+    }
+}
+
+impl ObjectDetect for SsdDetector {
+    fn detect(&self, image: &Mat) -> Vec<(Rect, f64)> {
+        strip_meta(self.detect_with_meta(image))
+    }
+
+    fn detect_with_meta(&self, image: &Mat) -> Vec<Detection> {
+        self.try_detect(image).unwrap_or_default()
+    }
+}
+
+/// Detector for YOLO-style networks, see [decode_yolo](fn.decode_yolo.html)
+/// for the expected output format. Outputs of all heads are merged.
+#[derive(Debug)]
+pub struct YoloDetector {
+    net: Net,
+
+    /// Preprocessing and filtering parameters.
+    pub params: DnnDetectorParams,
+
+    /// Class names, indexed by class id. Used to label the detections.
+    pub labels: Vec<String>,
+}
+
+impl YoloDetector {
+    /// Creates a YOLO detector from a loaded network.
+    pub fn new(net: Net, params: DnnDetectorParams) -> YoloDetector {
+        YoloDetector {
+            net: net,
+            params: params,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Loads a Darknet YOLO model and uses the usual YOLO preprocessing: a
+    /// 416x416 RGB input scaled to `[0, 1]`.
+    pub fn from_darknet<C: AsRef<Path>, M: AsRef<Path>>(cfg: C, model: M) -> Result<YoloDetector> {
+        let net = Net::from_darknet(cfg, model)?;
+        let params = DnnDetectorParams {
+            input_size: Size2i::new(416, 416),
+            scale: 1.0 / 255.0,
+            swap_rb: true,
+            ..DnnDetectorParams::default()
+        };
+        Ok(YoloDetector::new(net, params))
+    }
+
+    /// Detects objects in `image`. Unlike the `ObjectDetect` methods, which
+    /// report no detections in that case, this fails if the network can't
+    /// process the image.
+    pub fn try_detect(&self, image: &Mat) -> Result<Vec<Detection>> {
+        let detections = self.params.detect(&self.net, image, decode_yolo)?;
+        Ok(label(detections, &self.labels))
+    }
+}
+
+impl ObjectDetect for YoloDetector {
+    fn detect(&self, image: &Mat) -> Vec<(Rect, f64)> {
+        strip_meta(self.detect_with_meta(image))
+    }
+
+    fn detect_with_meta(&self, image: &Mat) -> Vec<Detection> {
+        self.try_detect(image).unwrap_or_default()
+    }
+}
+
+/// OpenCV's DNN face detector, a ResNet-10 based SSD
+/// (`res10_300x300_ssd_iter_140000.caffemodel` and its `deploy.prototxt`,
+/// or the quantized TensorFlow `opencv_face_detector_uint8.pb`).
+#[derive(Debug)]
+pub struct FaceDetector {
+    ssd: SsdDetector,
+}
+
+impl FaceDetector {
+    fn res10_params() -> DnnDetectorParams {
+        DnnDetectorParams {
+            mean: Scalar::new(104.0, 177.0, 123.0, 0.0),
+            ..DnnDetectorParams::default()
+        }
+    }
+
+    /// Creates a face detector from a loaded network, e.g. an export of the
+    /// face detector to another format.
+    pub fn new(net: Net) -> FaceDetector {
+        let mut ssd = SsdDetector::new(net, Self::res10_params());
+        // Class 0 is the background
+        ssd.labels = vec!["background".to_string(), "face".to_string()];
+        FaceDetector { ssd: ssd }
+    }
+
+    /// Loads the Caffe version of the face detector.
+    pub fn from_caffe<P: AsRef<Path>, M: AsRef<Path>>(prototxt: P, model: M) -> Result<Self> {
+        let net = Net::from_caffe(prototxt, model)?;
+        Ok(FaceDetector::new(net))
+    }
+
+    /// Loads the TensorFlow version of the face detector.
+    pub fn from_tensorflow<M: AsRef<Path>, C: AsRef<Path>>(model: M, config: C) -> Result<Self> {
+        let net = Net::from_tensorflow(model, config)?;
+        Ok(FaceDetector::new(net))
+    }
+
+    /// Sets the minimum confidence of the reported faces (default 0.5).
+    pub fn set_confidence_threshold(&mut self, threshold: f32) {
+        self.ssd.params.confidence_threshold = threshold;
+    }
+
+    /// Sets the overlap above which duplicate faces are suppressed (default
+    /// 0.4).
+    pub fn set_nms_threshold(&mut self, threshold: f32) {
+        self.ssd.params.nms_threshold = threshold;
+    }
+
+    /// Detects faces in `image`. Unlike the `ObjectDetect` methods, which
+    /// report no faces in that case, this fails if the network can't process
+    /// the image.
+    pub fn try_detect(&self, image: &Mat) -> Result<Vec<Detection>> {
+        self.ssd.try_detect(image)
+    }
+}
+
+impl ObjectDetect for FaceDetector {
+    fn detect(&self, image: &Mat) -> Vec<(Rect, f64)> {
+        self.ssd.detect(image)
+    }
+
+    fn detect_with_meta(&self, image: &Mat) -> Vec<Detection> {
+        self.ssd.detect_with_meta(image)
+    }
+}
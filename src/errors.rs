@@ -21,5 +21,80 @@ error_chain!{
             description("unsupported matrix channel count"),
             display("unsupported matrix channel count (max is {:?}): '{:?}'", max, supplied),
         }
+        Operation(operation: &'static str) {
+            description("OpenCV operation failed"),
+            display("OpenCV operation failed: '{}'", operation),
+        }
+        SingularMatrix {
+            description("matrix is singular"),
+            display("matrix is singular"),
+        }
+        StatModel(operation: &'static str) {
+            description("statistical model operation failed"),
+            display("statistical model operation failed: '{}'", operation),
+        }
+        IndexOutOfRange(idx: Vec<i32>) {
+            description("matrix index out of range"),
+            display("matrix index out of range: '{:?}'", idx),
+        }
+        ElementSizeMismatch(expected: usize, supplied: usize) {
+            description("element size doesn't match the matrix"),
+            display("element size doesn't match the matrix (expected {}): '{}'",
+                    expected, supplied),
+        }
+        BufferSize(expected: usize, supplied: usize) {
+            description("buffer size doesn't match the matrix"),
+            display("buffer size doesn't match the matrix (expected {}): '{}'",
+                    expected, supplied),
+        }
+        SvmDetectorSize(descriptor_size: usize, supplied: usize) {
+            description("SVM detector doesn't match the HOG descriptor size"),
+            display("SVM detector doesn't match the HOG descriptor (size {}): '{}'",
+                    descriptor_size, supplied),
+        }
+        UnsupportedMatType(depth: ::core::MatDepth, channels: u16) {
+            description("unsupported matrix type"),
+            display("unsupported matrix type: {:?} with {} channels", depth, channels),
+        }
+        ShapeMismatch(expected: Vec<i32>, supplied: Vec<i32>) {
+            description("matrix shape doesn't match"),
+            display("matrix shape doesn't match (expected {:?}): '{:?}'", expected, supplied),
+        }
+        MissingKey(key: String) {
+            description("key not found in file storage"),
+            display("key not found in file storage: '{}'", key),
+        }
+        UnexpectedNode(expected: &'static str) {
+            description("unexpected file storage node"),
+            display("unexpected file storage node, expected {}", expected),
+        }
+        ImageNotFound(p: PathBuf) {
+            description("image file not found"),
+            display("image file not found: '{:?}'", p),
+        }
+        UnsupportedImageFormat(p: PathBuf) {
+            description("unsupported image format"),
+            display("unsupported image format: '{:?}'", p),
+        }
+        ImageDecode(p: PathBuf) {
+            description("failed to decode image"),
+            display("failed to decode image: '{:?}'", p),
+        }
+        InvalidImageHeader(reason: &'static str) {
+            description("invalid image header"),
+            display("invalid image header: {}", reason),
+        }
+        InvalidImwriteParam(param: ::imgcodecs::ImwriteParam) {
+            description("image write parameter out of range"),
+            display("image write parameter out of range: {:?}", param),
+        }
+        VideoOpen(p: PathBuf) {
+            description("failed to open video"),
+            display("failed to open video: '{:?}'", p),
+        }
+        UnsupportedOperation(operation: &'static str) {
+            description("operation not supported by this OpenCV version"),
+            display("operation not supported by this OpenCV version: '{}'", operation),
+        }
     }
 }
@@ -0,0 +1,533 @@
+//! Reads the size, type and EXIF orientation of an encoded image from its
+//! header, without decoding any pixel.
+//!
+//! PNG, JPEG, BMP, PBM/PGM/PPM, TIFF and WebP headers are recognized.
+
+use core::{FlipCode, Mat, MatDepth, MatType};
+use errors::*;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use wrapper::Size2i;
+
+/// Bytes read from a file before falling back to reading all of it, which is
+/// only needed when metadata fills the start of the file.
+const PREFIX_LEN: u64 = 64 * 1024;
+
+/// Orientation of the stored pixels according to the EXIF metadata, naming
+/// where the first row and the first column are when displayed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExifOrientation {
+    /// The first row is at the top and the first column on the left, the
+    /// pixels are displayed as stored.
+    TopLeft = 1,
+    /// Mirrored horizontally.
+    TopRight = 2,
+    /// Rotated by 180 degrees.
+    BottomRight = 3,
+    /// Mirrored vertically.
+    BottomLeft = 4,
+    /// Mirrored along the main diagonal.
+    LeftTop = 5,
+    /// Needs a clockwise rotation by 90 degrees.
+    RightTop = 6,
+    /// Mirrored along the anti-diagonal.
+    RightBottom = 7,
+    /// Needs a counterclockwise rotation by 90 degrees.
+    LeftBottom = 8,
+}
+
+impl ExifOrientation {
+    /// Maps the value of the EXIF orientation tag, ignoring unknown values
+    /// like OpenCV does.
+    fn from_tag(value: u32) -> ExifOrientation {
+        match value {
+            2 => ExifOrientation::TopRight,
+            3 => ExifOrientation::BottomRight,
+            4 => ExifOrientation::BottomLeft,
+            5 => ExifOrientation::LeftTop,
+            6 => ExifOrientation::RightTop,
+            7 => ExifOrientation::RightBottom,
+            8 => ExifOrientation::LeftBottom,
+            _ => ExifOrientation::TopLeft,
+        }
+    }
+}
+
+impl Mat {
+    /// Transforms the image so that pixels stored with the given orientation
+    /// are displayed upright, like OpenCV does when reading an image without
+    /// `ImreadModes::ImreadIgnoreOrientation`. Fails for matrices of more than
+    /// 2 dimensions.
+    pub fn apply_exif_orientation(&mut self, orientation: ExifOrientation) -> Result<()> {
+        if self.dims() > 2 {
+            return Err(ErrorKind::Operation("apply_exif_orientation").into());
+        }
+        match orientation {
+            ExifOrientation::LeftTop
+            | ExifOrientation::RightTop
+            | ExifOrientation::RightBottom
+            | ExifOrientation::LeftBottom => *self = self.transpose()?,
+            _ => {}
+        }
+        match orientation {
+            ExifOrientation::TopRight | ExifOrientation::RightTop => self.flip(FlipCode::YAxis),
+            ExifOrientation::BottomRight | ExifOrientation::RightBottom => {
+                self.flip(FlipCode::XYAxis)
+            }
+            ExifOrientation::BottomLeft | ExifOrientation::LeftBottom => self.flip(FlipCode::XAxis),
+            ExifOrientation::TopLeft | ExifOrientation::LeftTop => {}
+        }
+        Ok(())
+    }
+}
+
+/// Size, type and orientation of an encoded image, read from its header.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ImageInfo {
+    /// Size of the stored pixels, before applying the EXIF orientation.
+    pub size: Size2i,
+    /// The type of the `Mat` decoded with `ImreadModes::ImreadUnchanged`.
+    pub mat_type: MatType,
+    /// EXIF orientation, `TopLeft` if the image has none.
+    pub orientation: ExifOrientation,
+}
+
+impl ImageInfo {
+    /// Reads the header of the image file at `path`.
+    ///
+    /// Fails with `ImageNotFound` if there's no file at `path`,
+    /// `UnsupportedImageFormat` if the format isn't recognized and
+    /// `ImageDecode` if the header is malformed.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<ImageInfo> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(ErrorKind::ImageNotFound(path.to_path_buf()).into());
+        }
+        let decode_error = || Error::from(ErrorKind::ImageDecode(path.to_path_buf()));
+        let mut file = File::open(path).map_err(|_| decode_error())?;
+        let mut buf = Vec::new();
+        file.by_ref()
+            .take(PREFIX_LEN)
+            .read_to_end(&mut buf)
+            .map_err(|_| decode_error())?;
+        let mut info = probe(&buf);
+        if info == Err(Probe::Truncated) && buf.len() as u64 == PREFIX_LEN {
+            file.read_to_end(&mut buf).map_err(|_| decode_error())?;
+            info = probe(&buf);
+        }
+        info.map_err(|e| match e {
+            Probe::Unsupported => ErrorKind::UnsupportedImageFormat(path.to_path_buf()).into(),
+            Probe::Truncated | Probe::Invalid => decode_error(),
+        })
+    }
+
+    /// Reads the header of an encoded image, which only needs to hold the
+    /// start of the file.
+    ///
+    /// Fails with `InvalidImageHeader` if the format isn't recognized or the
+    /// header is malformed or incomplete.
+    pub fn from_buffer(buf: &[u8]) -> Result<ImageInfo> {
+        probe(buf).map_err(|e| {
+            let reason = match e {
+                Probe::Unsupported => "unsupported format",
+                Probe::Truncated => "truncated header",
+                Probe::Invalid => "malformed header",
+            };
+            ErrorKind::InvalidImageHeader(reason).into()
+        })
+    }
+}
+
+/// Why a header couldn't be read.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Probe {
+    Unsupported,
+    Truncated,
+    Invalid,
+}
+
+type ProbeResult<T> = ::std::result::Result<T, Probe>;
+
+fn probe(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    if buf.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png(buf)
+    } else if buf.starts_with(&[0xff, 0xd8]) {
+        jpeg(buf)
+    } else if buf.starts_with(b"BM") {
+        bmp(buf)
+    } else if buf.starts_with(b"II*\0") || buf.starts_with(b"MM\0*") {
+        tiff(buf)
+    } else if buf.starts_with(b"RIFF") && buf.get(8..12) == Some(b"WEBP") {
+        webp(buf)
+    } else {
+        match buf.get(..2) {
+            Some(b"P1") | Some(b"P2") | Some(b"P3") | Some(b"P4") | Some(b"P5") | Some(b"P6") => {
+                pxm(buf)
+            }
+            _ => Err(Probe::Unsupported),
+        }
+    }
+}
+
+fn info(
+    width: u32,
+    height: u32,
+    depth: MatDepth,
+    channels: u16,
+    orientation: ExifOrientation,
+) -> ProbeResult<ImageInfo> {
+    let (width, height) = (width as i32, height as i32);
+    if width <= 0 || height <= 0 {
+        return Err(Probe::Invalid);
+    }
+    Ok(ImageInfo {
+        size: Size2i::new(width, height),
+        mat_type: MatType::new(depth, channels).map_err(|_| Probe::Invalid)?,
+        orientation,
+    })
+}
+
+/// Returns `len` bytes at `pos`, failing with `Truncated` past the end.
+fn bytes(buf: &[u8], pos: usize, len: usize) -> ProbeResult<&[u8]> {
+    pos.checked_add(len)
+        .and_then(|end| buf.get(pos..end))
+        .ok_or(Probe::Truncated)
+}
+
+fn byte(buf: &[u8], pos: usize) -> ProbeResult<u8> {
+    buf.get(pos).cloned().ok_or(Probe::Truncated)
+}
+
+fn be16(buf: &[u8], pos: usize) -> ProbeResult<u16> {
+    let b = bytes(buf, pos, 2)?;
+    Ok(u16::from(b[0]) << 8 | u16::from(b[1]))
+}
+
+fn be32(buf: &[u8], pos: usize) -> ProbeResult<u32> {
+    Ok(u32::from(be16(buf, pos)?) << 16 | u32::from(be16(buf, pos.saturating_add(2))?))
+}
+
+fn le16(buf: &[u8], pos: usize) -> ProbeResult<u16> {
+    let b = bytes(buf, pos, 2)?;
+    Ok(u16::from(b[1]) << 8 | u16::from(b[0]))
+}
+
+fn le24(buf: &[u8], pos: usize) -> ProbeResult<u32> {
+    Ok(u32::from(byte(buf, pos.saturating_add(2))?) << 16 | u32::from(le16(buf, pos)?))
+}
+
+fn le32(buf: &[u8], pos: usize) -> ProbeResult<u32> {
+    Ok(u32::from(le16(buf, pos.saturating_add(2))?) << 16 | u32::from(le16(buf, pos)?))
+}
+
+fn png(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    if bytes(buf, 12, 4)? != b"IHDR" {
+        return Err(Probe::Invalid);
+    }
+    let (width, height) = (be32(buf, 16)?, be32(buf, 20)?);
+    let bit_depth = byte(buf, 24)?;
+    let color_type = byte(buf, 25)?;
+
+    // A transparency chunk, which precedes the image data, adds an alpha
+    // channel to RGB and palette images.
+    let mut has_trns = false;
+    let mut pos = 8;
+    loop {
+        let len = be32(buf, pos)? as usize;
+        let chunk = bytes(buf, pos + 4, 4)?;
+        if chunk == b"IDAT" || chunk == b"IEND" {
+            break;
+        }
+        has_trns |= chunk == b"tRNS";
+        pos = pos.saturating_add(len).saturating_add(12);
+    }
+
+    let channels = match color_type {
+        0 => 1,
+        2 | 3 if has_trns => 4,
+        2 | 3 => 3,
+        4 | 6 => 4,
+        _ => return Err(Probe::Invalid),
+    };
+    let depth = if bit_depth == 16 {
+        MatDepth::Unsigned16
+    } else {
+        MatDepth::Unsigned8
+    };
+    info(width, height, depth, channels, ExifOrientation::TopLeft)
+}
+
+fn jpeg(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    let mut orientation = ExifOrientation::TopLeft;
+    let mut pos = 2;
+    loop {
+        if byte(buf, pos)? != 0xff {
+            return Err(Probe::Invalid);
+        }
+        // Markers may be preceded by any number of fill bytes.
+        while byte(buf, pos)? == 0xff {
+            pos += 1;
+        }
+        let marker = byte(buf, pos)?;
+        pos += 1;
+        match marker {
+            0x01 | 0xd0..=0xd7 => continue,
+            // The frame header comes before the end of image and the scans.
+            0xd9 | 0xda => return Err(Probe::Invalid),
+            _ => {}
+        }
+        let len = be16(buf, pos)? as usize;
+        if len < 2 {
+            return Err(Probe::Invalid);
+        }
+        match marker {
+            0xe1 => {
+                let segment = bytes(buf, pos + 2, len - 2)?;
+                if segment.starts_with(b"Exif\0\0") {
+                    orientation = exif_orientation(&segment[6..]);
+                }
+            }
+            // Start of frame, except DHT, JPG and DAC which share the range.
+            0xc0..=0xcf if marker != 0xc4 && marker != 0xc8 && marker != 0xcc => {
+                let (height, width) = (be16(buf, pos + 3)?, be16(buf, pos + 5)?);
+                let channels = if byte(buf, pos + 7)? == 1 { 1 } else { 3 };
+                return info(
+                    u32::from(width),
+                    u32::from(height),
+                    MatDepth::Unsigned8,
+                    channels,
+                    orientation,
+                );
+            }
+            _ => {}
+        }
+        pos += len;
+    }
+}
+
+fn bmp(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    let header_size = le32(buf, 14)? as usize;
+    let (width, height, bpp, compression, palette_entry) = if header_size == 12 {
+        let (width, height) = (le16(buf, 18)?, le16(buf, 20)?);
+        (u32::from(width), u32::from(height), le16(buf, 24)?, 0, 3)
+    } else if header_size >= 40 {
+        // Negative heights are used by images stored top-down.
+        let height = (le32(buf, 22)? as i32).wrapping_abs() as u32;
+        (le32(buf, 18)?, height, le16(buf, 28)?, le32(buf, 30)?, 4)
+    } else {
+        return Err(Probe::Invalid);
+    };
+
+    let channels = match bpp {
+        // Palette images are decoded as grayscale if every entry is gray.
+        1 | 4 | 8 => {
+            let max_colors = 1 << bpp;
+            let colors = if header_size >= 40 { le32(buf, 46)? } else { 0 };
+            let colors = if colors == 0 || colors > max_colors {
+                max_colors
+            } else {
+                colors
+            };
+            let palette = bytes(
+                buf,
+                header_size.saturating_add(14),
+                colors as usize * palette_entry,
+            )?;
+            if palette
+                .chunks(palette_entry)
+                .all(|c| c[0] == c[1] && c[1] == c[2])
+            {
+                1
+            } else {
+                3
+            }
+        }
+        16 | 24 => 3,
+        // Bit fields may hold an alpha channel.
+        32 if compression != 0 => 4,
+        32 => 3,
+        _ => return Err(Probe::Invalid),
+    };
+    info(
+        width,
+        height,
+        MatDepth::Unsigned8,
+        channels,
+        ExifOrientation::TopLeft,
+    )
+}
+
+fn pxm(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    let kind = buf[1];
+    // Bitmaps have no maximum value field.
+    let fields = if kind == b'1' || kind == b'4' { 2 } else { 3 };
+    let mut values = [1u32; 3];
+    let mut pos = 2;
+    for value in values.iter_mut().take(fields) {
+        loop {
+            match byte(buf, pos)? {
+                b'#' => {
+                    while byte(buf, pos)? != b'\n' {
+                        pos += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => pos += 1,
+                _ => break,
+            }
+        }
+        let start = pos;
+        while byte(buf, pos)?.is_ascii_digit() {
+            pos += 1;
+        }
+        *value = ::std::str::from_utf8(&buf[start..pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or(Probe::Invalid)?;
+    }
+
+    let [width, height, max_value] = values;
+    let depth = match max_value {
+        1..=255 => MatDepth::Unsigned8,
+        256..=65535 => MatDepth::Unsigned16,
+        _ => return Err(Probe::Invalid),
+    };
+    let channels = if kind == b'3' || kind == b'6' { 3 } else { 1 };
+    info(width, height, depth, channels, ExifOrientation::TopLeft)
+}
+
+/// Reads TIFF integers in the byte order given by the file's header.
+struct Tiff<'a> {
+    buf: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn u16(&self, pos: usize) -> ProbeResult<u16> {
+        if self.little_endian {
+            le16(self.buf, pos)
+        } else {
+            be16(self.buf, pos)
+        }
+    }
+
+    fn u32(&self, pos: usize) -> ProbeResult<u32> {
+        if self.little_endian {
+            le32(self.buf, pos)
+        } else {
+            be32(self.buf, pos)
+        }
+    }
+
+    /// Returns the tags of the first image file directory with their first
+    /// value, skipping those that aren't integers.
+    fn first_ifd(&self) -> ProbeResult<Vec<(u16, u32)>> {
+        let ifd = self.u32(4)? as usize;
+        let count = self.u16(ifd)? as usize;
+        // Checking the directory's extent first keeps the offsets below in
+        // range.
+        bytes(self.buf, ifd, 2 + count * 12)?;
+        let mut tags = Vec::with_capacity(count);
+        for i in 0..count {
+            let entry = ifd + 2 + i * 12;
+            let tag = self.u16(entry)?;
+            let field_type = self.u16(entry + 2)?;
+            let len = self.u32(entry + 4)?;
+            // Values that don't fit in the entry are stored at an offset.
+            let value = match (field_type, len) {
+                (3, 1) | (3, 2) => u32::from(self.u16(entry + 8)?),
+                (3, _) => u32::from(self.u16(self.u32(entry + 8)? as usize)?),
+                (4, 1) => self.u32(entry + 8)?,
+                (4, _) => self.u32(self.u32(entry + 8)? as usize)?,
+                _ => continue,
+            };
+            tags.push((tag, value));
+        }
+        Ok(tags)
+    }
+}
+
+fn tiff_tags(buf: &[u8]) -> ProbeResult<Vec<(u16, u32)>> {
+    let little_endian = match bytes(buf, 0, 4)? {
+        b"II*\0" => true,
+        b"MM\0*" => false,
+        _ => return Err(Probe::Unsupported),
+    };
+    Tiff { buf, little_endian }.first_ifd()
+}
+
+/// Reads the orientation tag from the TIFF structure of EXIF data. Malformed
+/// data is ignored, like OpenCV does.
+fn exif_orientation(buf: &[u8]) -> ExifOrientation {
+    tiff_tags(buf)
+        .ok()
+        .and_then(|tags| tags.into_iter().find(|t| t.0 == 274))
+        .map_or(ExifOrientation::TopLeft, |t| ExifOrientation::from_tag(t.1))
+}
+
+fn tiff(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    let tags = tiff_tags(buf)?;
+    let tag = |id: u16, default: Option<u32>| {
+        tags.iter()
+            .find(|t| t.0 == id)
+            .map(|t| t.1)
+            .or(default)
+            .ok_or(Probe::Invalid)
+    };
+    let (width, height) = (tag(256, None)?, tag(257, None)?);
+    let depth = match (tag(258, Some(1))?, tag(339, Some(1))?) {
+        (1, _) | (8, 1) => MatDepth::Unsigned8,
+        (8, 2) => MatDepth::Signed8,
+        (16, 1) => MatDepth::Unsigned16,
+        (16, 2) => MatDepth::Signed16,
+        (32, 2) => MatDepth::Signed32,
+        (32, 3) => MatDepth::Float32,
+        (64, 3) => MatDepth::Float64,
+        _ => return Err(Probe::Invalid),
+    };
+    // Palette images are decoded as BGR.
+    let channels = match (tag(262, Some(1))?, tag(277, Some(1))?) {
+        (3, _) => 3,
+        (_, samples @ 1..=4) => samples as u16,
+        _ => return Err(Probe::Invalid),
+    };
+    let orientation = ExifOrientation::from_tag(tag(274, Some(1))?);
+    info(width, height, depth, channels, orientation)
+}
+
+fn webp(buf: &[u8]) -> ProbeResult<ImageInfo> {
+    let (width, height, alpha) = match bytes(buf, 12, 4)? {
+        b"VP8 " => {
+            // A lossy key frame starts with a 3 byte tag and a start code.
+            if bytes(buf, 23, 3)? != [0x9d, 0x01, 0x2a] {
+                return Err(Probe::Invalid);
+            }
+            let (width, height) = (le16(buf, 26)? & 0x3fff, le16(buf, 28)? & 0x3fff);
+            (u32::from(width), u32::from(height), false)
+        }
+        b"VP8L" => {
+            if byte(buf, 20)? != 0x2f {
+                return Err(Probe::Invalid);
+            }
+            let bits = le32(buf, 21)?;
+            (
+                (bits & 0x3fff) + 1,
+                ((bits >> 14) & 0x3fff) + 1,
+                (bits >> 28) & 1 == 1,
+            )
+        }
+        b"VP8X" => {
+            let flags = byte(buf, 20)?;
+            (le24(buf, 24)? + 1, le24(buf, 27)? + 1, flags & 0x10 != 0)
+        }
+        _ => return Err(Probe::Invalid),
+    };
+    let channels = if alpha { 4 } else { 3 };
+    info(
+        width,
+        height,
+        MatDepth::Unsigned8,
+        channels,
+        ExifOrientation::TopLeft,
+    )
+}
@@ -0,0 +1,570 @@
+//! Image file reading and writing, see [OpenCV
+//! imgcodecs](http://docs.opencv.org/3.1.0/d4/da8/group__imgcodecs.html).
+
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+use super::core::Mat;
+use super::errors::*;
+use super::libc::c_int;
+
+use super::wrapper::*;
+
+mod header;
+pub use self::header::{ExifOrientation, ImageInfo};
+
+#[cfg(feature = "rust-codecs")]
+pub mod rust_codecs;
+
+// =============================================================================
+//  Imgproc
+// =============================================================================
+/// ImreadModes
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImreadModes {
+    /// If set, return the loaded image as is (with alpha channel, otherwise it
+    /// gets cropped
+    ImreadUnchanged = -1,
+    /// If set, always convert image to the single channel grayscale image.
+    ImreadGrayscale = 0,
+    /// If set, always convert image to the 3 channel BGR color image.
+    ImreadColor = 1,
+    /// If set, return 16-bit/32-bit image when the input has the corresponding
+    /// depth, otherwise convert it to 8-bit.
+    ImreadAnydepth = 2,
+    /// If set, the image is read in any possible color format.
+    ImreadAnycolor = 4,
+    /// If set, use the gdal driver for loading the image.
+    ImreadLoadGdal = 8,
+    /// If set, always convert image to the single channel grayscale image and
+    /// the image size reduced 1/2.
+    ImreadReducedGrayscale2 = 16,
+    /// If set, always convert image to the 3 channel BGR color image and the
+    /// image size reduced 1/2.
+    ImreadReducedColor2 = 17,
+    /// If set, always convert image to the single channel grayscale image and
+    /// the image size reduced 1/4.
+    ImreadReducedGrayscale4 = 32,
+    /// If set, always convert image to the 3 channel BGR color image and the
+    /// image size reduced 1/4.
+    ImreadReducedColor4 = 33,
+    /// If set, always convert image to the single channel grayscale image and
+    /// the image size reduced 1/8.
+    ImreadReducedGrayscale8 = 64,
+    /// If set, always convert image to the 3 channel BGR color image and the
+    /// image size reduced 1/8.
+    ImreadReducedColor8 = 65,
+    /// If set, do not rotate the image according to EXIF's orientation flag.
+    /// On its own, the image is converted to grayscale like with
+    /// `ImreadGrayscale`.
+    ImreadIgnoreOrientation = 128,
+    /// Combines `ImreadColor` and `ImreadIgnoreOrientation`.
+    ImreadColorIgnoreOrientation = 129,
+}
+
+/// Imwrite flags
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImwriteFlags {
+    /// For JPEG, it can be a quality from 0 to 100 (the higher is the
+    /// better). Default value is 95.
+    ImwriteJpegQuality = 1,
+    /// Enable JPEG features, 0 or 1, default is False.
+    ImwriteJpegProgressive = 2,
+    /// Enable JPEG features, 0 or 1, default is False.
+    ImwriteJpegOptimize = 3,
+    /// JPEG restart interval, 0 - 65535, default is 0 - no restart.
+    ImwriteJpegRstInterval = 4,
+    /// Separate luma quality level, 0 - 100, default is 0 - don't use.
+    ImwriteJpegLumaQuality = 5,
+    /// Separate chroma quality level, 0 - 100, default is 0 - don't use.
+    ImwriteJpegChromaQuality = 6,
+    /// For PNG, it can be the compression level from 0 to 9. A higher value
+    /// means a smaller size and longer compression time. Default value is
+    /// 3. Also strategy is changed to IMWRITE_PNG_STRATEGY_DEFAULT
+    /// (Z_DEFAULT_STRATEGY).
+    ImwritePngCompression = 16,
+    /// One of cv::ImwritePNGFlags, default is IMWRITE_PNG_STRATEGY_DEFAULT.
+    ImwritePngStrategy = 17,
+    /// Binary level PNG, 0 or 1, default is 0.
+    ImwritePngBilevel = 18,
+    /// For PPM, PGM, or PBM, it can be a binary format flag, 0 or 1. Default
+    /// value is 1.
+    ImwritePxmBinary = 32,
+    /// For WEBP, it can be a quality from 1 to 100 (the higher is the
+    /// better). By default (without any parameter) and for quality above 100
+    /// the lossless compression is used.
+    ImwriteWebpQuality = 64,
+    /// For EXR, one of `ImwriteExrTypeFlags`, default is
+    /// IMWRITE_EXR_TYPE_FLOAT.
+    ImwriteExrType = 48,
+    /// For PAM, sets the TUPLETYPE field to the corresponding string value that
+    /// is defined for the format
+    ImwritePamTupletype = 128,
+    /// For TIFF, the unit of the X and Y resolution, one of
+    /// `ImwriteTiffResunit`.
+    ImwriteTiffResunit = 256,
+    /// For TIFF, the X direction DPI.
+    ImwriteTiffXdpi = 257,
+    /// For TIFF, the Y direction DPI.
+    ImwriteTiffYdpi = 258,
+    /// For TIFF, the compression scheme, one of `ImwriteTiffCompression`.
+    ImwriteTiffCompression = 259,
+}
+
+/// Imwrite EXR type flag
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImwriteExrTypeFlags {
+    /// Store as half precision floats.
+    ImwriteExrTypeHalf = 1,
+    /// Store as single precision floats.
+    ImwriteExrTypeFloat = 2,
+}
+
+/// Unit of the TIFF resolution, as defined by libtiff.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImwriteTiffResunit {
+    /// No absolute unit.
+    None = 1,
+    /// Dots per inch.
+    Inch = 2,
+    /// Dots per centimeter.
+    Centimeter = 3,
+}
+
+/// TIFF compression scheme, as defined by libtiff.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImwriteTiffCompression {
+    /// No compression.
+    None = 1,
+    /// Lempel-Ziv-Welch compression.
+    Lzw = 5,
+    /// Deflate (zlib) compression.
+    Deflate = 8,
+    /// Macintosh run-length encoding.
+    PackBits = 32773,
+}
+
+/// Imwrite PNG flag
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImwritePngFlags {
+    /// Use this value for normal data.
+    ImwritePngStrategyDefault = 0,
+    ///  Use this value for data produced by a filter (or predictor).Filtered
+    ///  data consists mostly of small values with a somewhat random
+    ///  distribution. In this case, the compression algorithm is tuned to
+    ///  compress them better.
+    ImwritePngStrategyFiltered = 1,
+    /// Use this value to force Huffman encoding only (no string match).
+    ImwritePngStrategyHuffmanOnly = 2,
+    /// Use this value to limit match distances to one (run-length encoding).
+    ImwritePngStrategyRle = 3,
+    /// Using this value prevents the use of dynamic Huffman codes, allowing for
+    /// a simpler decoder for special applications.
+    ImwritePngStrategyFixed = 4,
+}
+
+/// The implementation that encoded or decoded an image.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CodecBackend {
+    /// OpenCV's own codecs.
+    OpenCv,
+    /// The pure-Rust codecs of the `rust-codecs` feature, used when OpenCV
+    /// has no codec for the image.
+    Rust,
+}
+
+/// A parameter passed to `Mat::write_to_path` or `Mat::imencode`, each
+/// variant being a flag from `ImwriteFlags` together with its value.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImwriteParam {
+    /// JPEG quality from 0 to 100, the higher the better. Default is 95.
+    JpegQuality(u8),
+    /// Whether to write a progressive JPEG. Default is false.
+    JpegProgressive(bool),
+    /// Whether to optimize the JPEG Huffman tables. Default is false.
+    JpegOptimize(bool),
+    /// JPEG restart interval, 0 meaning no restart. Default is 0.
+    JpegRstInterval(u16),
+    /// Separate JPEG luma quality from 0 to 100, 0 meaning unused. Default
+    /// is 0.
+    JpegLumaQuality(u8),
+    /// Separate JPEG chroma quality from 0 to 100, 0 meaning unused. Default
+    /// is 0.
+    JpegChromaQuality(u8),
+    /// PNG compression level from 0 to 9, the higher the smaller and slower.
+    /// Default is 3.
+    PngCompression(u8),
+    /// PNG compression strategy. Default is `ImwritePngStrategyDefault`.
+    PngStrategy(ImwritePngFlags),
+    /// Whether to write a bi-level PNG. Default is false.
+    PngBilevel(bool),
+    /// Whether PPM, PGM or PBM files are written in binary format. Default is
+    /// true.
+    PxmBinary(bool),
+    /// WEBP quality from 1 to 100, the higher the better. Without this
+    /// parameter, lossless compression is used.
+    WebpQuality(u8),
+    /// Precision of the floats stored in an EXR file.
+    ExrType(ImwriteExrTypeFlags),
+    /// Unit of the TIFF resolution.
+    TiffResunit(ImwriteTiffResunit),
+    /// TIFF X resolution, must be positive.
+    TiffXdpi(u16),
+    /// TIFF Y resolution, must be positive.
+    TiffYdpi(u16),
+    /// TIFF compression scheme.
+    TiffCompression(ImwriteTiffCompression),
+}
+
+impl ImwriteParam {
+    /// Returns the flag this parameter sets.
+    pub fn flag(&self) -> ImwriteFlags {
+        match *self {
+            ImwriteParam::JpegQuality(_) => ImwriteFlags::ImwriteJpegQuality,
+            ImwriteParam::JpegProgressive(_) => ImwriteFlags::ImwriteJpegProgressive,
+            ImwriteParam::JpegOptimize(_) => ImwriteFlags::ImwriteJpegOptimize,
+            ImwriteParam::JpegRstInterval(_) => ImwriteFlags::ImwriteJpegRstInterval,
+            ImwriteParam::JpegLumaQuality(_) => ImwriteFlags::ImwriteJpegLumaQuality,
+            ImwriteParam::JpegChromaQuality(_) => ImwriteFlags::ImwriteJpegChromaQuality,
+            ImwriteParam::PngCompression(_) => ImwriteFlags::ImwritePngCompression,
+            ImwriteParam::PngStrategy(_) => ImwriteFlags::ImwritePngStrategy,
+            ImwriteParam::PngBilevel(_) => ImwriteFlags::ImwritePngBilevel,
+            ImwriteParam::PxmBinary(_) => ImwriteFlags::ImwritePxmBinary,
+            ImwriteParam::WebpQuality(_) => ImwriteFlags::ImwriteWebpQuality,
+            ImwriteParam::ExrType(_) => ImwriteFlags::ImwriteExrType,
+            ImwriteParam::TiffResunit(_) => ImwriteFlags::ImwriteTiffResunit,
+            ImwriteParam::TiffXdpi(_) => ImwriteFlags::ImwriteTiffXdpi,
+            ImwriteParam::TiffYdpi(_) => ImwriteFlags::ImwriteTiffYdpi,
+            ImwriteParam::TiffCompression(_) => ImwriteFlags::ImwriteTiffCompression,
+        }
+    }
+
+    /// Returns the value passed to OpenCV along with the flag, or
+    /// `InvalidImwriteParam` if it's out of range.
+    pub fn value(&self) -> Result<c_int> {
+        let (value, valid) = match *self {
+            ImwriteParam::JpegQuality(q)
+            | ImwriteParam::JpegLumaQuality(q)
+            | ImwriteParam::JpegChromaQuality(q) => (c_int::from(q), q <= 100),
+            ImwriteParam::WebpQuality(q) => (c_int::from(q), q != 0 && q <= 100),
+            ImwriteParam::PngCompression(l) => (c_int::from(l), l <= 9),
+            ImwriteParam::JpegProgressive(b)
+            | ImwriteParam::JpegOptimize(b)
+            | ImwriteParam::PngBilevel(b)
+            | ImwriteParam::PxmBinary(b) => (c_int::from(b), true),
+            ImwriteParam::JpegRstInterval(i) => (c_int::from(i), true),
+            ImwriteParam::TiffXdpi(d) | ImwriteParam::TiffYdpi(d) => (c_int::from(d), d > 0),
+            ImwriteParam::PngStrategy(s) => (s as c_int, true),
+            ImwriteParam::ExrType(t) => (t as c_int, true),
+            ImwriteParam::TiffResunit(u) => (u as c_int, true),
+            ImwriteParam::TiffCompression(c) => (c as c_int, true),
+        };
+        if valid {
+            Ok(value)
+        } else {
+            Err(ErrorKind::InvalidImwriteParam(*self).into())
+        }
+    }
+}
+
+/// Validates `params` and flattens them into the `(flag, value)` pairs
+/// OpenCV expects.
+fn params_to_vec(params: &[ImwriteParam]) -> Result<Vec<c_int>> {
+    let mut pairs = Vec::with_capacity(params.len() * 2);
+    for param in params {
+        pairs.push(param.flag() as c_int);
+        pairs.push(param.value()?);
+    }
+    Ok(pairs)
+}
+
+/// Returns true if OpenCV can decode the image file at `path`.
+///
+/// OpenCV older than 3.4.10 (or 4.3.0) can't inspect the file's signature,
+/// so there the file only needs to exist and have an extension OpenCV can
+/// write.
+pub fn have_image_reader<P: AsRef<Path>>(path: P) -> bool {
+    match path_to_cstring(path) {
+        Ok(s) => unsafe { cv_have_image_reader(s.as_ptr()) },
+        Err(_) => false,
+    }
+}
+
+/// Returns true if OpenCV can encode images to `path`, judging by its
+/// extension.
+pub fn have_image_writer<P: AsRef<Path>>(path: P) -> bool {
+    match path_to_cstring(path) {
+        Ok(s) => unsafe { cv_have_image_writer(s.as_ptr()) },
+        Err(_) => false,
+    }
+}
+
+/// Converts `path` for OpenCV, failing with `ImageNotFound` if there's no file
+/// to read.
+fn readable_path(path: &Path) -> Result<CString> {
+    let s = path_to_cstring(path)?;
+    if path.is_file() {
+        Ok(s)
+    } else {
+        Err(ErrorKind::ImageNotFound(path.to_path_buf()).into())
+    }
+}
+
+/// Tells apart files no decoder handles from files a decoder failed on.
+fn read_error(path: &Path) -> Error {
+    if have_image_reader(path) {
+        ErrorKind::ImageDecode(path.to_path_buf()).into()
+    } else {
+        ErrorKind::UnsupportedImageFormat(path.to_path_buf()).into()
+    }
+}
+
+/// Converts `path` for OpenCV, failing with `UnsupportedImageFormat` if no
+/// encoder handles its extension.
+fn writable_path(path: &Path) -> Result<CString> {
+    let s = path_to_cstring(path)?;
+    if have_image_writer(path) {
+        Ok(s)
+    } else {
+        Err(ErrorKind::UnsupportedImageFormat(path.to_path_buf()).into())
+    }
+}
+
+/// Reads every page of a multi-page image, such as a TIFF stack. Single-page
+/// images yield one `Mat`.
+///
+/// Fails like `Mat::from_path` does.
+pub fn imreadmulti<P: AsRef<Path>>(path: P, flags: ImreadModes) -> Result<Vec<Mat>> {
+    let path = path.as_ref();
+    let s = readable_path(path)?;
+    let mut pages = CVecOfMat::default();
+    if unsafe { cv_imreadmulti(s.as_ptr(), flags as c_int, &mut pages) } {
+        Ok(pages.rustify())
+    } else {
+        Err(read_error(path))
+    }
+}
+
+/// Returns the number of pages of a multi-page image without keeping them in
+/// memory.
+///
+/// OpenCV older than 4.5.4 has no way to count pages, so there every page is
+/// decoded. Fails like `Mat::from_path` does.
+pub fn imcount<P: AsRef<Path>>(path: P, flags: ImreadModes) -> Result<usize> {
+    let path = path.as_ref();
+    let s = readable_path(path)?;
+    match unsafe { cv_imcount(s.as_ptr(), flags as c_int) } {
+        0 => Err(read_error(path)),
+        n => Ok(n),
+    }
+}
+
+/// Writes `mats` as the pages of a single image file. Only some formats, like
+/// TIFF, hold more than one page.
+///
+/// Fails like `Mat::write_to_path` does.
+pub fn imwritemulti<P: AsRef<Path>>(path: P, mats: &[&Mat], params: &[ImwriteParam]) -> Result<()> {
+    let s = writable_path(path.as_ref())?;
+    let params = params_to_vec(params)?;
+    let mats: Vec<*const CMat> = mats.iter().map(|m| m.inner as *const CMat).collect();
+    if unsafe {
+        cv_imwritemulti(
+            s.as_ptr(),
+            mats.as_ptr(),
+            mats.len(),
+            params.as_ptr(),
+            params.len(),
+        )
+    } {
+        Ok(())
+    } else {
+        Err(ErrorKind::Operation("imwritemulti").into())
+    }
+}
+
+/// Opens a multi-page image whose pages are decoded one at a time while
+/// iterating.
+///
+/// OpenCV older than 4.5.4 can't decode a single page, so there every page is
+/// decoded when opening and handed out while iterating. Fails like
+/// `Mat::from_path` does.
+pub fn imread_pages<P: AsRef<Path>>(path: P, flags: ImreadModes) -> Result<ImagePages> {
+    let path = path.as_ref();
+    let s = readable_path(path)?;
+    let inner = unsafe { cv_image_pages_new(s.as_ptr(), flags as c_int) };
+    if inner.is_null() {
+        return Err(read_error(path));
+    }
+    Ok(ImagePages {
+        inner,
+        path: path.to_path_buf(),
+        next: 0,
+        len: unsafe { cv_image_pages_len(inner) },
+    })
+}
+
+/// Iterator over the pages of a multi-page image, see `imread_pages`.
+#[derive(Debug)]
+pub struct ImagePages {
+    inner: *mut CImagePages,
+    path: PathBuf,
+    next: usize,
+    len: usize,
+}
+
+impl Iterator for ImagePages {
+    type Item = Result<Mat>;
+
+    fn next(&mut self) -> Option<Result<Mat>> {
+        if self.next >= self.len {
+            return None;
+        }
+        let page = unsafe { cv_image_pages_read(self.inner, self.next) };
+        self.next += 1;
+        if page.is_null() {
+            Some(Err(ErrorKind::ImageDecode(self.path.clone()).into()))
+        } else {
+            Some(Ok(Mat::from_raw(page)))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.next;
+        (remaining, Some(remaining))
+    }
+
+    /// Skips pages without decoding them.
+    fn nth(&mut self, n: usize) -> Option<Result<Mat>> {
+        self.next = self.next.saturating_add(n).min(self.len);
+        self.next()
+    }
+}
+
+impl ExactSizeIterator for ImagePages {}
+
+impl Drop for ImagePages {
+    fn drop(&mut self) {
+        unsafe {
+            cv_image_pages_drop(self.inner);
+        }
+    }
+}
+
+impl Mat {
+    /// Creates a `Mat` from reading the image specified by the path.
+    ///
+    /// Fails with `ImageNotFound` if there's no file at `path`,
+    /// `UnsupportedImageFormat` if no decoder handles it and `ImageDecode` if
+    /// the decoder fails to read it.
+    pub fn from_path<P: AsRef<Path>>(path: P, flags: ImreadModes) -> Result<Mat> {
+        let path = path.as_ref();
+        let s = readable_path(path)?;
+        let m = unsafe { cv_imread(s.as_ptr(), flags as c_int) };
+        if m.is_null() {
+            Err(read_error(path))
+        } else {
+            Ok(Mat::from_raw(m))
+        }
+    }
+
+    /// Writes the image to `path`, the format being chosen by its extension.
+    ///
+    /// Fails with `InvalidImwriteParam` if a parameter is out of range and
+    /// `UnsupportedImageFormat` if no encoder handles the extension.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P, params: &[ImwriteParam]) -> Result<()> {
+        let s = writable_path(path.as_ref())?;
+        let params = params_to_vec(params)?;
+        if unsafe { cv_imwrite(s.as_ptr(), self.inner, params.as_ptr(), params.len()) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("imwrite").into())
+        }
+    }
+
+    /// Decodes an image from `buf` according to the specified mode, returning
+    /// an empty `Mat` if it can't be decoded.
+    ///
+    /// With the `rust-codecs` feature, images OpenCV has no codec for may be
+    /// decoded in Rust, see `imdecode_with_backend`.
+    pub fn imdecode(buf: &[u8], mode: ImreadModes) -> Mat {
+        match Mat::imdecode_with_backend(buf, mode) {
+            Ok((mat, _)) => mat,
+            Err(_) => Mat::new(),
+        }
+    }
+
+    /// Decodes an image from `buf` according to the specified mode and tells
+    /// which backend decoded it.
+    ///
+    /// OpenCV is tried first. With the `rust-codecs` feature, PNG, JPEG, BMP
+    /// and PNM images it fails to decode are then decoded in Rust, into the
+    /// same layout. Fails with `Operation("imdecode")` if no backend decodes
+    /// the image.
+    pub fn imdecode_with_backend(buf: &[u8], mode: ImreadModes) -> Result<(Mat, CodecBackend)> {
+        if buf.is_empty() {
+            return Err(ErrorKind::Operation("imdecode").into());
+        }
+        let mat = Mat::from_raw(unsafe { cv_imdecode(buf.as_ptr(), buf.len(), mode as i32) });
+        if mat.total() > 0 {
+            return Ok((mat, CodecBackend::OpenCv));
+        }
+        #[cfg(feature = "rust-codecs")]
+        {
+            if rust_codecs::can_decode(buf) {
+                return rust_codecs::decode(buf, mode).map(|mat| (mat, CodecBackend::Rust));
+            }
+        }
+        Err(ErrorKind::Operation("imdecode").into())
+    }
+
+    /// Encodes an image; the encoding scheme depends on the extension
+    /// provided, such as ".png". If successful, returns an owned vector of the
+    /// encoded image.
+    ///
+    /// Fails like `write_to_path` does. With the `rust-codecs` feature,
+    /// images may be encoded in Rust, see `imencode_with_backend`.
+    pub fn imencode(&self, ext: &str, params: &[ImwriteParam]) -> Result<Vec<u8>> {
+        self.imencode_with_backend(ext, params).map(|(buf, _)| buf)
+    }
+
+    /// Encodes an image like `imencode` does and tells which backend encoded
+    /// it.
+    ///
+    /// With the `rust-codecs` feature, PNG, JPEG, BMP and PNM images are
+    /// encoded in Rust when OpenCV has no encoder for the extension.
+    pub fn imencode_with_backend(
+        &self,
+        ext: &str,
+        params: &[ImwriteParam],
+    ) -> Result<(Vec<u8>, CodecBackend)> {
+        let c_params = params_to_vec(params)?;
+        let c_ext = CString::new(ext)?;
+        if !have_image_writer(ext) {
+            #[cfg(feature = "rust-codecs")]
+            {
+                if rust_codecs::can_encode(ext) {
+                    return rust_codecs::encode(self, ext, params)
+                        .map(|buf| (buf, CodecBackend::Rust));
+                }
+            }
+            return Err(ErrorKind::UnsupportedImageFormat(ext.into()).into());
+        }
+        let r = unsafe {
+            cv_imencode(
+                c_ext.as_ptr(),
+                self.inner,
+                c_params.as_ptr(),
+                c_params.len(),
+            )
+        };
+        let encoded = if r.status {
+            Ok((
+                unsafe { ::std::slice::from_raw_parts(r.buf, r.size) }.to_vec(),
+                CodecBackend::OpenCv,
+            ))
+        } else {
+            Err(ErrorKind::Operation("imencode").into())
+        };
+        unsafe { cv_imencode_result_drop(r) };
+        encoded
+    }
+}
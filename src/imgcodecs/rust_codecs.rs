@@ -0,0 +1,189 @@
+//! Pure-Rust PNG, JPEG, BMP and PNM (PBM, PGM and PPM) codecs, used by
+//! `Mat::imdecode` and `Mat::imencode` when OpenCV is built without the
+//! matching codec.
+//!
+//! Decoded `Mat`s have the layout OpenCV's own decoders produce for the mode:
+//! BGR(A) channel order and the same `MatType`. Only 8-bit images are
+//! supported, and the reduced and GDAL modes are not.
+
+use super::{ImageInfo, ImreadModes, ImwriteParam};
+use core::{Mat, MatDepth};
+use errors::*;
+use image::bmp::BMPEncoder;
+use image::jpeg::JPEGEncoder;
+use image::png::PNGEncoder;
+use image::pnm::{PNMEncoder, PNMSubtype, SampleEncoding};
+use image::{self, ColorType, DynamicImage, GrayImage, ImageFormat, RgbImage, RgbaImage};
+use imgproc::ColorConversionCodes;
+use std::convert::TryFrom;
+
+/// OpenCV's default JPEG quality.
+const DEFAULT_JPEG_QUALITY: u8 = 95;
+
+fn decoding_format(buf: &[u8]) -> Option<ImageFormat> {
+    match image::guess_format(buf) {
+        Ok(ImageFormat::PNG) => Some(ImageFormat::PNG),
+        Ok(ImageFormat::JPEG) => Some(ImageFormat::JPEG),
+        Ok(ImageFormat::BMP) => Some(ImageFormat::BMP),
+        // PAM files aren't supported.
+        Ok(ImageFormat::PNM) if !buf.starts_with(b"P7") => Some(ImageFormat::PNM),
+        _ => None,
+    }
+}
+
+/// Returns true if `buf` starts like an image these codecs decode.
+pub fn can_decode(buf: &[u8]) -> bool {
+    decoding_format(buf).is_some()
+}
+
+/// Decodes an image from `buf` according to the specified mode.
+///
+/// Fails with `Operation("imdecode")` if the format, its depth or the mode
+/// isn't supported, or if the image is malformed.
+pub fn decode(buf: &[u8], mode: ImreadModes) -> Result<Mat> {
+    let fail = || Error::from(ErrorKind::Operation("imdecode"));
+    let format = decoding_format(buf).ok_or_else(fail)?;
+    let info = ImageInfo::from_buffer(buf).map_err(|_| fail())?;
+    if info.mat_type.depth() != MatDepth::Unsigned8 {
+        return Err(fail());
+    }
+
+    // Channels of the decoded `Mat` and whether the EXIF orientation applies.
+    let native = info.mat_type.channels();
+    let (channels, orient) = match mode {
+        ImreadModes::ImreadUnchanged => (native, false),
+        ImreadModes::ImreadGrayscale | ImreadModes::ImreadAnydepth => (1, true),
+        ImreadModes::ImreadIgnoreOrientation => (1, false),
+        ImreadModes::ImreadColor => (3, true),
+        ImreadModes::ImreadColorIgnoreOrientation => (3, false),
+        ImreadModes::ImreadAnycolor if native == 1 => (1, true),
+        ImreadModes::ImreadAnycolor => (3, true),
+        _ => return Err(fail()),
+    };
+
+    let image = image::load_from_memory_with_format(buf, format).map_err(|_| fail())?;
+    let mut mat = match (channels, image) {
+        (1, DynamicImage::ImageLuma8(ref gray)) => Mat::from(gray),
+        (1, ref image) => Mat::from(&image.to_rgb()).cvt_color(ColorConversionCodes::BGR2GRAY),
+        (3, ref image) => Mat::from(&image.to_rgb()),
+        (_, ref image) => Mat::from(&image.to_rgba()),
+    };
+    if orient {
+        mat.apply_exif_orientation(info.orientation)?;
+    }
+    Ok(mat)
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Encoding {
+    Png,
+    Jpeg,
+    Bmp,
+    Pgm,
+    Ppm,
+    Pnm,
+}
+
+/// Maps an extension like ".png", or a file name, to its encoding.
+fn encoding(ext: &str) -> Option<Encoding> {
+    let ext = ext.rsplit('.').next().unwrap_or(ext).to_lowercase();
+    match &*ext {
+        "png" => Some(Encoding::Png),
+        "jpg" | "jpeg" | "jpe" => Some(Encoding::Jpeg),
+        "bmp" | "dib" => Some(Encoding::Bmp),
+        "pgm" => Some(Encoding::Pgm),
+        "ppm" => Some(Encoding::Ppm),
+        "pnm" => Some(Encoding::Pnm),
+        _ => None,
+    }
+}
+
+/// Returns true if these codecs encode images with the extension `ext`, such
+/// as ".png".
+pub fn can_encode(ext: &str) -> bool {
+    encoding(ext).is_some()
+}
+
+/// Encodes an 8-bit image with 1, 3 or 4 channels; the encoding scheme
+/// depends on the extension provided, such as ".png".
+///
+/// Like OpenCV, PGM files are always grayscale, PPM files always color, and
+/// only PNG keeps the alpha channel. `JpegQuality` and `PxmBinary` are
+/// honored, other parameters are ignored.
+pub fn encode(mat: &Mat, ext: &str, params: &[ImwriteParam]) -> Result<Vec<u8>> {
+    let encoding = encoding(ext).ok_or_else(|| ErrorKind::UnsupportedImageFormat(ext.into()))?;
+    for param in params {
+        param.value()?;
+    }
+    let mut quality = DEFAULT_JPEG_QUALITY;
+    let mut binary = true;
+    for param in params {
+        match *param {
+            ImwriteParam::JpegQuality(q) => quality = q.max(1),
+            ImwriteParam::PxmBinary(b) => binary = b,
+            _ => {}
+        }
+    }
+
+    let channels = mat.mat_type().channels();
+    let (width, height, data, color) = match encoding {
+        Encoding::Pgm => gray_samples(mat)?,
+        Encoding::Ppm => rgb_samples(mat)?,
+        Encoding::Png if channels == 4 => {
+            let image = RgbaImage::try_from(mat)?;
+            (
+                image.width(),
+                image.height(),
+                image.into_raw(),
+                ColorType::RGBA(8),
+            )
+        }
+        _ if channels == 1 => gray_samples(mat)?,
+        _ => rgb_samples(mat)?,
+    };
+
+    let mut buf = Vec::new();
+    let encoded = match encoding {
+        Encoding::Png => PNGEncoder::new(&mut buf).encode(&data, width, height, color),
+        Encoding::Jpeg => {
+            JPEGEncoder::new_with_quality(&mut buf, quality).encode(&data, width, height, color)
+        }
+        Encoding::Bmp => BMPEncoder::new(&mut buf).encode(&data, width, height, color),
+        Encoding::Pgm | Encoding::Ppm | Encoding::Pnm => {
+            let samples = if binary {
+                SampleEncoding::Binary
+            } else {
+                SampleEncoding::Ascii
+            };
+            let subtype = match color {
+                ColorType::Gray(_) => PNMSubtype::Graymap(samples),
+                _ => PNMSubtype::Pixmap(samples),
+            };
+            PNMEncoder::new(&mut buf)
+                .with_subtype(subtype)
+                .encode(&data[..], width, height, color)
+        }
+    };
+    encoded.map_err(|_| ErrorKind::Operation("imencode"))?;
+    Ok(buf)
+}
+
+fn gray_samples(mat: &Mat) -> Result<(u32, u32, Vec<u8>, ColorType)> {
+    let image = GrayImage::try_from(mat)?;
+    Ok((
+        image.width(),
+        image.height(),
+        image.into_raw(),
+        ColorType::Gray(8),
+    ))
+}
+
+fn rgb_samples(mat: &Mat) -> Result<(u32, u32, Vec<u8>, ColorType)> {
+    let image = RgbImage::try_from(mat)?;
+    Ok((
+        image.width(),
+        image.height(),
+        image.into_raw(),
+        ColorType::RGB(8),
+    ))
+}
@@ -2,7 +2,9 @@
 //! imgproc](http://docs.opencv.org/3.1.0/d7/dbd/group__imgproc.html).
 
 use super::core::*;
+use super::errors::*;
 use libc::c_int;
+use std::ptr;
 
 use super::wrapper::*;
 
@@ -151,6 +153,17 @@ pub enum ColorConversionCodes {
     COLORCVT_MAX = 139,
 }
 
+/// OpenCV's aliases for codes that perform the same channel swap.
+#[allow(missing_docs)]
+impl ColorConversionCodes {
+    pub const RGB2BGR: ColorConversionCodes = ColorConversionCodes::BGR2RGB;
+    pub const RGBA2BGRA: ColorConversionCodes = ColorConversionCodes::BGRA2RGBA;
+    pub const RGB2BGRA: ColorConversionCodes = ColorConversionCodes::BGR2RGBA;
+    pub const BGRA2RGB: ColorConversionCodes = ColorConversionCodes::RGBA2BGR;
+    pub const GRAY2RGB: ColorConversionCodes = ColorConversionCodes::GRAY2BGR;
+    pub const GRAY2RGBA: ColorConversionCodes = ColorConversionCodes::GRAY2BGRA;
+}
+
 /// Interpolation algorithm
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum InterpolationFlag {
@@ -185,7 +198,12 @@ pub enum InterpolationFlag {
 impl Mat {
     /// Draws a simple, thick, or filled up-right rectangle.
     pub fn rectangle(&self, rect: Rect) {
-        self.rectangle_custom(rect, Scalar::new(255, 255, 0, 255), 1, LineTypes::Line8);
+        self.rectangle_custom(
+            rect,
+            Scalar::new(255.0, 255.0, 0.0, 255.0),
+            1,
+            LineTypes::Line8,
+        );
     }
 
     /// Draws a simple, thick, or filled up-right rectangle.
@@ -199,6 +217,22 @@ impl Mat {
         self.rectangle(abs_rect);
     }
 
+    /// Draws the outline of a rotated rectangle. Fails unless `thickness` is
+    /// positive (and at most 32767).
+    pub fn draw_rotated_rect(
+        &self,
+        rect: RotatedRect,
+        color: Scalar,
+        thickness: i32,
+        linetype: LineTypes,
+    ) -> Result<()> {
+        if unsafe { cv_draw_rotated_rect(self.inner, rect, color, thickness, linetype as i32) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("draw_rotated_rect").into())
+        }
+    }
+
     /// Draws a simple, thick ellipse
     pub fn ellipse(
         &self,
@@ -214,7 +248,7 @@ impl Mat {
             angle,
             start_angle,
             end_angle,
-            Scalar::new(255, 255, 0, 255),
+            Scalar::new(255.0, 255.0, 0.0, 255.0),
             1,
             LineTypes::Line8,
             0,
@@ -333,4 +367,103 @@ impl Mat {
         }
         Mat::from_raw(m)
     }
+
+    /// Detects the translational shift between `self` and `other` (both
+    /// `CV_32FC1` or `CV_64FC1` and of the same size) with the phase
+    /// correlation method, and returns it with the normalized peak response
+    /// (close to 1 for a clear single peak). An optional `window`, e.g. from
+    /// [create_hanning_window](fn.create_hanning_window.html), reduces edge
+    /// effects.
+    pub fn phase_correlate(&self, other: &Mat, window: Option<&Mat>) -> Result<(Point2f, f64)> {
+        let window = window.map_or(ptr::null(), |w| w.inner);
+        let mut shift = Point2f::default();
+        let mut response = 0.0;
+        if unsafe { cv_phase_correlate(self.inner, other.inner, window, &mut shift, &mut response) }
+        {
+            Ok((shift, response))
+        } else {
+            Err(ErrorKind::Operation("phase_correlate").into())
+        }
+    }
+}
+
+/// Computes a Hanning window of the given size, typically used with
+/// [phase_correlate](../struct.Mat.html#method.phase_correlate). `depth`
+/// must be `MatDepth::Float32` or `MatDepth::Float64`, and both dimensions
+/// of `size` greater than 1.
+pub fn create_hanning_window(size: Size2i, depth: MatDepth) -> Result<Mat> {
+    let m = Mat::new();
+    let mat_type = MatType::new(depth, 1).expect("single channel is always valid");
+    if unsafe { cv_create_hanning_window(m.inner, size, mat_type.as_opencv_value()) } {
+        Ok(m)
+    } else {
+        Err(ErrorKind::Operation("create_hanning_window").into())
+    }
+}
+
+/// How two rotated rectangles intersect, see
+/// [rotated_rectangle_intersection](fn.rotated_rectangle_intersection.html).
+#[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+pub enum RectanglesIntersectTypes {
+    /// No intersection.
+    None = 0,
+    /// There is a partial intersection.
+    Partial = 1,
+    /// One of the rectangles is fully enclosed in the other.
+    Full = 2,
+}
+
+/// Finds out if there is any intersection between two rotated rectangles and
+/// returns the intersection type together with the vertices of the
+/// intersecting region.
+pub fn rotated_rectangle_intersection(
+    rect1: &RotatedRect,
+    rect2: &RotatedRect,
+) -> Result<(RectanglesIntersectTypes, Vec<Point2f>)> {
+    let mut vertices = [Point2f::default(); 8];
+    let mut len = 0;
+    let result = unsafe {
+        cv_rotated_rectangle_intersection(*rect1, *rect2, vertices.as_mut_ptr(), &mut len)
+    };
+    let kind: RectanglesIntersectTypes = ::num::FromPrimitive::from_i32(result)
+        .ok_or_else(|| Error::from(ErrorKind::Operation("rotated_rectangle_intersection")))?;
+    Ok((kind, vertices[..len].to_vec()))
+}
+
+impl RotatedRect {
+    /// Returns the area of the intersection of two rotated rectangles.
+    pub fn intersection_area(&self, other: &RotatedRect) -> Result<f64> {
+        let (_, mut vertices) = rotated_rectangle_intersection(self, other)?;
+        let n = vertices.len();
+        if n < 3 {
+            return Ok(0.0);
+        }
+
+        // The vertices of the (convex) region aren't ordered, so sort them by
+        // their angle around the centroid.
+        let centroid =
+            vertices.iter().fold(Point2f::default(), |acc, &p| acc + p) * (1.0 / n as f32);
+        let angle = |p: &Point2f| (p.y - centroid.y).atan2(p.x - centroid.x);
+        vertices.sort_by(|a, b| {
+            angle(a)
+                .partial_cmp(&angle(b))
+                .unwrap_or(::std::cmp::Ordering::Equal)
+        });
+        let twice_area: f64 = (0..n)
+            .map(|i| f64::from(vertices[i].cross(vertices[(i + 1) % n])))
+            .sum();
+        Ok(twice_area.abs() / 2.0)
+    }
+
+    /// Returns the intersection over union (Jaccard index) of two rotated
+    /// rectangles, a value between 0 (disjoint) and 1 (identical).
+    pub fn iou(&self, other: &RotatedRect) -> Result<f64> {
+        let inter = self.intersection_area(other)?;
+        let union = f64::from(self.area()) + f64::from(other.area()) - inter;
+        if union > 0.0 {
+            Ok(inter / union)
+        } else {
+            Ok(0.0)
+        }
+    }
 }
@@ -0,0 +1,172 @@
+//! Conversions between `Mat` and `image::ImageBuffer`.
+//!
+//! `Mat`s are assumed to follow OpenCV's BGR(A) channel order, while `image`
+//! uses RGB(A). Every conversion names the `ColorConversionCodes` it applies,
+//! and grayscale or alpha channels are added or dropped as needed.
+
+use core::{Mat, MatDepth, MatType};
+use errors::*;
+use image::{GrayImage, ImageBuffer, Luma, Pixel, RgbImage, RgbaImage};
+use imgproc::ColorConversionCodes;
+use std::convert::TryFrom;
+use std::slice;
+
+/// Copies a 2-dimensional `Mat` of the given depth into an image buffer.
+/// `code` maps the channel count to the conversion to apply, or returns
+/// `None` if the channel count isn't supported. Empty matrices aren't
+/// converted, since OpenCV rejects them.
+fn mat_to_buffer<P, F>(
+    mat: &Mat,
+    depth: MatDepth,
+    code: F,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>>
+where
+    P: Pixel + 'static,
+    F: Fn(u16) -> Option<Option<ColorConversionCodes>>,
+{
+    let mat_type = mat.mat_type();
+    let unsupported = || ErrorKind::UnsupportedMatType(mat_type.depth(), mat_type.channels());
+    if mat.dims() > 2 || mat_type.depth() != depth {
+        return Err(unsupported().into());
+    }
+    let converted;
+    let src = match code(mat_type.channels()) {
+        Some(Some(code)) if mat.total() > 0 => {
+            converted = mat.cvt_color(code);
+            &converted
+        }
+        Some(_) if mat.is_continuous() => mat,
+        Some(_) => {
+            converted = mat.clone();
+            &converted
+        }
+        None => return Err(unsupported().into()),
+    };
+
+    let (width, height) = (src.cols().max(0) as u32, src.rows().max(0) as u32);
+    let len = width as usize * height as usize * P::channel_count() as usize;
+    let data = if len == 0 {
+        Vec::new()
+    } else {
+        unsafe { slice::from_raw_parts(src.data() as *const P::Subpixel, len) }.to_vec()
+    };
+    ImageBuffer::from_raw(width, height, data).ok_or_else(|| unsupported().into())
+}
+
+/// Copies an image buffer into a new `Mat` of the given depth, then applies
+/// `code` if any and the image isn't empty.
+fn buffer_to_mat<P>(
+    image: &ImageBuffer<P, Vec<P::Subpixel>>,
+    depth: MatDepth,
+    code: Option<ColorConversionCodes>,
+) -> Mat
+where
+    P: Pixel + 'static,
+{
+    let mat_type =
+        MatType::new(depth, u16::from(P::channel_count())).expect("image channel count is valid");
+    let raw: &[P::Subpixel] = image;
+    let len = ::std::mem::size_of_val(raw);
+    let bytes = unsafe { slice::from_raw_parts(raw.as_ptr() as *const u8, len) };
+    let mat = Mat::from_buffer(image.height() as i32, image.width() as i32, mat_type, bytes)
+        .expect("image buffer matches its dimensions");
+    match code {
+        Some(code) if !raw.is_empty() => mat.cvt_color(code),
+        _ => mat,
+    }
+}
+
+/// Converts an 8-bit `Mat` with 1 (`GRAY2RGB`), 3 (`BGR2RGB`) or 4
+/// (`BGRA2RGB`) channels.
+impl<'a> TryFrom<&'a Mat> for RgbImage {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<RgbImage> {
+        mat_to_buffer(mat, MatDepth::Unsigned8, |channels| match channels {
+            1 => Some(Some(ColorConversionCodes::GRAY2RGB)),
+            3 => Some(Some(ColorConversionCodes::BGR2RGB)),
+            4 => Some(Some(ColorConversionCodes::BGRA2RGB)),
+            _ => None,
+        })
+    }
+}
+
+/// Converts an 8-bit `Mat` with 1 (`GRAY2RGBA`), 3 (`BGR2RGBA`) or 4
+/// (`BGRA2RGBA`) channels.
+impl<'a> TryFrom<&'a Mat> for RgbaImage {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<RgbaImage> {
+        mat_to_buffer(mat, MatDepth::Unsigned8, |channels| match channels {
+            1 => Some(Some(ColorConversionCodes::GRAY2RGBA)),
+            3 => Some(Some(ColorConversionCodes::BGR2RGBA)),
+            4 => Some(Some(ColorConversionCodes::BGRA2RGBA)),
+            _ => None,
+        })
+    }
+}
+
+/// Converts an 8-bit `Mat` with 1, 3 (`BGR2GRAY`) or 4 (`BGRA2GRAY`)
+/// channels.
+impl<'a> TryFrom<&'a Mat> for GrayImage {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<GrayImage> {
+        mat_to_buffer(mat, MatDepth::Unsigned8, gray_code)
+    }
+}
+
+/// Converts a 16-bit `Mat` with 1, 3 (`BGR2GRAY`) or 4 (`BGRA2GRAY`)
+/// channels.
+impl<'a> TryFrom<&'a Mat> for ImageBuffer<Luma<u16>, Vec<u16>> {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<ImageBuffer<Luma<u16>, Vec<u16>>> {
+        mat_to_buffer(mat, MatDepth::Unsigned16, gray_code)
+    }
+}
+
+fn gray_code(channels: u16) -> Option<Option<ColorConversionCodes>> {
+    match channels {
+        1 => Some(None),
+        3 => Some(Some(ColorConversionCodes::BGR2GRAY)),
+        4 => Some(Some(ColorConversionCodes::BGRA2GRAY)),
+        _ => None,
+    }
+}
+
+/// Creates a `CV_8UC3` BGR `Mat` (`RGB2BGR`).
+impl<'a> From<&'a RgbImage> for Mat {
+    fn from(image: &'a RgbImage) -> Mat {
+        buffer_to_mat(
+            image,
+            MatDepth::Unsigned8,
+            Some(ColorConversionCodes::RGB2BGR),
+        )
+    }
+}
+
+/// Creates a `CV_8UC4` BGRA `Mat` (`RGBA2BGRA`).
+impl<'a> From<&'a RgbaImage> for Mat {
+    fn from(image: &'a RgbaImage) -> Mat {
+        buffer_to_mat(
+            image,
+            MatDepth::Unsigned8,
+            Some(ColorConversionCodes::RGBA2BGRA),
+        )
+    }
+}
+
+/// Creates a `CV_8UC1` `Mat`.
+impl<'a> From<&'a GrayImage> for Mat {
+    fn from(image: &'a GrayImage) -> Mat {
+        buffer_to_mat(image, MatDepth::Unsigned8, None)
+    }
+}
+
+/// Creates a `CV_16UC1` `Mat`.
+impl<'a> From<&'a ImageBuffer<Luma<u16>, Vec<u16>>> for Mat {
+    fn from(image: &'a ImageBuffer<Luma<u16>, Vec<u16>>) -> Mat {
+        buffer_to_mat(image, MatDepth::Unsigned16, None)
+    }
+}
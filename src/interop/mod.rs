@@ -0,0 +1,9 @@
+//! Conversions between `Mat` and the types of other crates, each behind the
+//! cargo feature of the same name.
+
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "ndarray")]
+mod ndarray;
@@ -0,0 +1,71 @@
+//! Conversions between small single-channel `Mat`s, such as camera matrices
+//! and affine transforms, and `nalgebra` matrices.
+
+use core::{Mat, MatElement, MatType};
+use errors::*;
+use nalgebra::{Matrix2x3, Matrix3, Scalar};
+use std::convert::TryFrom;
+
+/// Reads a `rows` x `cols` single-channel `Mat` in row-major order.
+fn read_values<T: MatElement>(mat: &Mat, rows: i32, cols: i32) -> Result<Vec<T>> {
+    let mat_type = mat.mat_type();
+    if mat_type.depth() != T::DEPTH || mat_type.channels() != 1 {
+        return Err(ErrorKind::UnsupportedMatType(mat_type.depth(), mat_type.channels()).into());
+    }
+    let sizes = mat.sizes();
+    if sizes != [rows, cols] {
+        return Err(ErrorKind::ShapeMismatch(vec![rows, cols], sizes).into());
+    }
+    let mut values = Vec::with_capacity((rows * cols) as usize);
+    for r in 0..rows {
+        for c in 0..cols {
+            values.push(mat.at(&[r, c])?);
+        }
+    }
+    Ok(values)
+}
+
+/// Creates a `rows` x `cols` single-channel `Mat` from values in row-major
+/// order.
+fn write_values<T: MatElement>(rows: i32, cols: i32, values: &[T]) -> Mat {
+    let mat_type = MatType::new(T::DEPTH, 1).expect("single channel is valid");
+    let mut mat = Mat::new_nd(&[rows, cols], mat_type).expect("matrix size is valid");
+    for (i, value) in values.iter().enumerate() {
+        let i = i as i32;
+        mat.set_at(&[i / cols, i % cols], *value)
+            .expect("index is in range");
+    }
+    mat
+}
+
+/// Converts a 3x3 `Mat` whose depth matches `N`.
+impl<'a, N: MatElement + Scalar> TryFrom<&'a Mat> for Matrix3<N> {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<Matrix3<N>> {
+        Ok(Matrix3::from_row_slice(&read_values(mat, 3, 3)?))
+    }
+}
+
+/// Converts a 2x3 `Mat`, like an affine transform, whose depth matches `N`.
+impl<'a, N: MatElement + Scalar> TryFrom<&'a Mat> for Matrix2x3<N> {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<Matrix2x3<N>> {
+        Ok(Matrix2x3::from_row_slice(&read_values(mat, 2, 3)?))
+    }
+}
+
+/// Creates a 3x3 single-channel `Mat`.
+impl<'a, N: MatElement + Scalar> From<&'a Matrix3<N>> for Mat {
+    fn from(matrix: &'a Matrix3<N>) -> Mat {
+        write_values(3, 3, matrix.transpose().as_slice())
+    }
+}
+
+/// Creates a 2x3 single-channel `Mat`.
+impl<'a, N: MatElement + Scalar> From<&'a Matrix2x3<N>> for Mat {
+    fn from(matrix: &'a Matrix2x3<N>) -> Mat {
+        write_values(2, 3, matrix.transpose().as_slice())
+    }
+}
@@ -0,0 +1,116 @@
+//! Conversions between `Mat` and `ndarray` arrays.
+//!
+//! Views borrow the pixels of a 2-dimensional `Mat` without copying them.
+//! Their shape is `[rows, cols]` or `[rows, cols, channels]` and their strides
+//! follow the `Mat`'s step, so regions of interest can be viewed as well.
+
+use core::{Mat, MatElement, MatType};
+use errors::*;
+use ndarray::{ArrayBase, ArrayView2, ArrayView3, Data, Ix2, Ix3, ShapeBuilder};
+use std::convert::TryFrom;
+use std::ptr::{self, NonNull};
+
+/// Shape, strides (in elements) and data pointer of a `Mat` seen as
+/// `[rows, cols, channels]`.
+struct Layout<T> {
+    shape: (usize, usize, usize),
+    strides: (usize, usize, usize),
+    data: *const T,
+}
+
+fn layout<T: MatElement>(mat: &Mat) -> Result<Layout<T>> {
+    let mat_type = mat.mat_type();
+    if mat.dims() > 2 || mat_type.depth() != T::DEPTH {
+        return Err(ErrorKind::UnsupportedMatType(mat_type.depth(), mat_type.channels()).into());
+    }
+    let channels = mat_type.channels() as usize;
+    if mat.total() == 0 {
+        return Ok(Layout {
+            shape: (0, 0, channels),
+            strides: (0, 0, 0),
+            data: NonNull::dangling().as_ptr(),
+        });
+    }
+
+    let size = ::std::mem::size_of::<T>();
+    let steps = mat.steps();
+    Ok(Layout {
+        shape: (mat.rows() as usize, mat.cols() as usize, channels),
+        strides: (steps[0] / size, steps[1] / size, 1),
+        data: mat.data() as *const T,
+    })
+}
+
+/// Views a single-channel `Mat` as `[rows, cols]`.
+impl<'a, T: MatElement> TryFrom<&'a Mat> for ArrayView2<'a, T> {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<ArrayView2<'a, T>> {
+        let layout = layout::<T>(mat)?;
+        let (rows, cols, channels) = layout.shape;
+        if channels != 1 {
+            return Err(ErrorKind::UnsupportedMatType(T::DEPTH, channels as u16).into());
+        }
+        let shape = (rows, cols).strides((layout.strides.0, layout.strides.1));
+        Ok(unsafe { ArrayView2::from_shape_ptr(shape, layout.data) })
+    }
+}
+
+/// Views a `Mat` as `[rows, cols, channels]`.
+impl<'a, T: MatElement> TryFrom<&'a Mat> for ArrayView3<'a, T> {
+    type Error = Error;
+
+    fn try_from(mat: &'a Mat) -> Result<ArrayView3<'a, T>> {
+        let layout = layout::<T>(mat)?;
+        let shape = layout.shape.strides(layout.strides);
+        Ok(unsafe { ArrayView3::from_shape_ptr(shape, layout.data) })
+    }
+}
+
+/// Copies `values`, given in row-major order, into a new `rows` x `cols` `Mat`.
+fn array_to_mat<'a, T, I>(rows: usize, cols: usize, channels: usize, values: I) -> Result<Mat>
+where
+    T: MatElement + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    if channels == 0 || channels >= MatType::MAX_CHANNELS as usize {
+        return Err(
+            ErrorKind::UnsupportedChannelCount(channels as u16, MatType::MAX_CHANNELS).into(),
+        );
+    }
+    let mat_type = MatType::new(T::DEPTH, channels as u16)?;
+    let mat = Mat::new_nd(&[rows as i32, cols as i32], mat_type)?;
+    let data = mat.data() as *mut T;
+    for (i, value) in values.enumerate() {
+        unsafe { ptr::write(data.offset(i as isize), *value) };
+    }
+    Ok(mat)
+}
+
+/// Creates a single-channel `Mat`.
+impl<'a, S, T> TryFrom<&'a ArrayBase<S, Ix2>> for Mat
+where
+    S: Data<Elem = T>,
+    T: MatElement + 'a,
+{
+    type Error = Error;
+
+    fn try_from(array: &'a ArrayBase<S, Ix2>) -> Result<Mat> {
+        let (rows, cols) = array.dim();
+        array_to_mat(rows, cols, 1, array.iter())
+    }
+}
+
+/// Creates a `Mat` whose channels are the last axis.
+impl<'a, S, T> TryFrom<&'a ArrayBase<S, Ix3>> for Mat
+where
+    S: Data<Elem = T>,
+    T: MatElement + 'a,
+{
+    type Error = Error;
+
+    fn try_from(array: &'a ArrayBase<S, Ix3>) -> Result<Mat> {
+        let (rows, cols, channels) = array.dim();
+        array_to_mat(rows, cols, channels, array.iter())
+    }
+}
@@ -25,22 +25,62 @@ extern crate libc;
 extern crate num;
 #[macro_use]
 extern crate num_derive;
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
 
 mod core;
+mod interop;
 mod wrapper;
+#[cfg(feature = "serde")]
+mod serialization;
 pub use core::CvType;
 pub use core::FlipCode;
 pub use core::LineTypes;
 pub use core::Mat;
 pub use core::MatType;
 pub use core::MatDepth;
+pub use core::{MatElement, MatValue};
 pub use core::NormTypes;
+pub use core::KmeansFlags;
+pub use core::Pca;
+pub use core::PcaLayout;
+pub use core::DecompTypes;
+pub use core::GemmFlags;
+pub use core::Svd;
+pub use core::SvdFlags;
+pub use core::DftFlags;
+pub use core::get_optimal_dft_size;
+pub use core::ReduceDimension;
+pub use core::ReduceTypes;
+pub use core::BorderTypes;
+pub use wrapper::Point;
+pub use wrapper::Point2d;
 pub use wrapper::Point2f;
 pub use wrapper::Point2i;
+pub use wrapper::Point3;
+pub use wrapper::Point3d;
+pub use wrapper::Point3f;
+pub use wrapper::Point3i;
 pub use wrapper::Rect;
+pub use wrapper::Rect2d;
+pub use wrapper::Rect2f;
+pub use wrapper::Rect2i;
+pub use wrapper::RotatedRect;
 pub use wrapper::Scalar;
+pub use wrapper::Size;
+pub use wrapper::Size2d;
 pub use wrapper::Size2f;
 pub use wrapper::Size2i;
+pub use wrapper::{Vec2, Vec2d, Vec2f, Vec2i};
+pub use wrapper::{Vec3, Vec3b, Vec3d, Vec3f, Vec3i};
+pub use wrapper::{Vec4, Vec4b, Vec4d, Vec4f, Vec4i};
 
 pub mod errors;
 pub mod imgproc;
@@ -49,6 +89,9 @@ pub mod videoio;
 pub mod highgui;
 pub mod video;
 pub mod objdetect;
+pub mod dnn;
+pub mod ml;
+pub mod persistence;
 
 #[cfg(feature = "gpu")]
 pub mod cuda;
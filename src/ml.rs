@@ -0,0 +1,958 @@
+//! Machine Learning, see [OpenCV
+//! ml](https://docs.opencv.org/3.4.3/dd/ded/group__ml.html). Every model
+//! implements [StatModel](trait.StatModel.html) and is trained from a
+//! [TrainData](struct.TrainData.html) set.
+
+use super::core::sealed;
+use super::core::*;
+use super::errors::*;
+use super::objdetect::SvmDetector;
+use super::video::tracking::TermCriteria;
+use libc::{c_char, c_double, c_float, c_int, size_t};
+use std::path::Path;
+use std::ptr;
+
+use super::wrapper::*;
+
+enum CTrainData {}
+
+/// Opaque type for C/C++ `cv::ml::StatModel` objects.
+#[derive(Debug, Clone, Copy)]
+pub enum CStatModel {}
+
+extern "C" {
+    fn cv_ml_train_data_new(
+        samples: *const CMat,
+        layout: c_int,
+        responses: *const CMat,
+        var_idx: *const CMat,
+        sample_idx: *const CMat,
+        sample_weights: *const CMat,
+        response_type: c_int,
+    ) -> *mut CTrainData;
+    fn cv_ml_train_data_drop(data: *mut CTrainData);
+    fn cv_ml_train_data_set_train_test_split_ratio(
+        data: *mut CTrainData,
+        ratio: c_double,
+        shuffle: bool,
+    ) -> bool;
+    fn cv_ml_train_data_get_n_samples(data: *const CTrainData) -> c_int;
+    fn cv_ml_train_data_get_n_vars(data: *const CTrainData) -> c_int;
+
+    fn cv_ml_stat_model_drop(model: *mut CStatModel);
+    fn cv_ml_stat_model_train(model: *mut CStatModel, data: *mut CTrainData, flags: c_int) -> bool;
+    fn cv_ml_stat_model_predict(
+        model: *mut CStatModel,
+        samples: *const CMat,
+        results: *mut CMat,
+        flags: c_int,
+    ) -> bool;
+    fn cv_ml_stat_model_calc_error(
+        model: *mut CStatModel,
+        data: *mut CTrainData,
+        test: bool,
+        error: *mut c_float,
+    ) -> bool;
+    fn cv_ml_stat_model_save(model: *const CStatModel, path: *const c_char) -> bool;
+    fn cv_ml_stat_model_is_trained(model: *const CStatModel) -> bool;
+    fn cv_ml_stat_model_is_classifier(model: *const CStatModel) -> bool;
+    fn cv_ml_stat_model_get_var_count(model: *const CStatModel) -> c_int;
+
+    fn cv_ml_svm_new() -> *mut CStatModel;
+    fn cv_ml_svm_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_svm_set_type(model: *mut CStatModel, svm_type: c_int);
+    fn cv_ml_svm_set_kernel(model: *mut CStatModel, kernel_type: c_int);
+    fn cv_ml_svm_set_c(model: *mut CStatModel, c: c_double);
+    fn cv_ml_svm_set_gamma(model: *mut CStatModel, gamma: c_double);
+    fn cv_ml_svm_set_nu(model: *mut CStatModel, nu: c_double);
+    fn cv_ml_svm_set_p(model: *mut CStatModel, p: c_double);
+    fn cv_ml_svm_set_degree(model: *mut CStatModel, degree: c_double);
+    fn cv_ml_svm_set_coef0(model: *mut CStatModel, coef0: c_double);
+    fn cv_ml_svm_set_term_criteria(model: *mut CStatModel, criteria: *mut CTermCriteria);
+    fn cv_ml_svm_get_type(model: *const CStatModel) -> c_int;
+    fn cv_ml_svm_get_kernel_type(model: *const CStatModel) -> c_int;
+    fn cv_ml_svm_get_c(model: *const CStatModel) -> c_double;
+    fn cv_ml_svm_get_gamma(model: *const CStatModel) -> c_double;
+    fn cv_ml_svm_train_auto(
+        model: *mut CStatModel,
+        data: *mut CTrainData,
+        k_fold: c_int,
+        balanced: bool,
+    ) -> bool;
+    fn cv_ml_svm_get_support_vectors(model: *const CStatModel) -> *mut CMat;
+    fn cv_ml_svm_get_decision_function(
+        model: *const CStatModel,
+        i: c_int,
+        alpha: *mut CMat,
+        svidx: *mut CMat,
+    ) -> c_double;
+    fn cv_ml_svm_to_hog_detector(model: *const CStatModel) -> *mut CSvmDetector;
+
+    fn cv_ml_knearest_new() -> *mut CStatModel;
+    fn cv_ml_knearest_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_knearest_set_default_k(model: *mut CStatModel, k: c_int);
+    fn cv_ml_knearest_set_is_classifier(model: *mut CStatModel, is_classifier: bool);
+    fn cv_ml_knearest_set_algorithm_type(model: *mut CStatModel, algorithm: c_int);
+    fn cv_ml_knearest_find_nearest(
+        model: *mut CStatModel,
+        samples: *const CMat,
+        k: c_int,
+        results: *mut CMat,
+        neighbor_responses: *mut CMat,
+        dist: *mut CMat,
+    ) -> bool;
+
+    fn cv_ml_dtrees_set_max_depth(model: *mut CStatModel, max_depth: c_int);
+    fn cv_ml_dtrees_set_min_sample_count(model: *mut CStatModel, count: c_int);
+
+    fn cv_ml_rtrees_new() -> *mut CStatModel;
+    fn cv_ml_rtrees_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_rtrees_set_active_var_count(model: *mut CStatModel, count: c_int);
+    fn cv_ml_rtrees_set_calculate_var_importance(model: *mut CStatModel, value: bool);
+    fn cv_ml_rtrees_set_term_criteria(model: *mut CStatModel, criteria: *mut CTermCriteria);
+    fn cv_ml_rtrees_get_var_importance(model: *const CStatModel) -> *mut CMat;
+
+    fn cv_ml_boost_new() -> *mut CStatModel;
+    fn cv_ml_boost_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_boost_set_boost_type(model: *mut CStatModel, boost_type: c_int);
+    fn cv_ml_boost_set_weak_count(model: *mut CStatModel, count: c_int);
+    fn cv_ml_boost_set_weight_trim_rate(model: *mut CStatModel, rate: c_double);
+
+    fn cv_ml_logistic_regression_new() -> *mut CStatModel;
+    fn cv_ml_logistic_regression_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_logistic_regression_set_learning_rate(model: *mut CStatModel, rate: c_double);
+    fn cv_ml_logistic_regression_set_iterations(model: *mut CStatModel, iterations: c_int);
+    fn cv_ml_logistic_regression_set_regularization(model: *mut CStatModel, regularization: c_int);
+    fn cv_ml_logistic_regression_set_train_method(model: *mut CStatModel, method: c_int);
+    fn cv_ml_logistic_regression_set_mini_batch_size(model: *mut CStatModel, size: c_int);
+    fn cv_ml_logistic_regression_set_term_criteria(
+        model: *mut CStatModel,
+        criteria: *mut CTermCriteria,
+    );
+
+    fn cv_ml_normal_bayes_new() -> *mut CStatModel;
+    fn cv_ml_normal_bayes_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_normal_bayes_predict_prob(
+        model: *mut CStatModel,
+        samples: *const CMat,
+        outputs: *mut CMat,
+        output_probs: *mut CMat,
+    ) -> bool;
+
+    fn cv_ml_ann_mlp_new() -> *mut CStatModel;
+    fn cv_ml_ann_mlp_load(path: *const c_char) -> *mut CStatModel;
+    fn cv_ml_ann_mlp_set_layer_sizes(
+        model: *mut CStatModel,
+        sizes: *const c_int,
+        len: size_t,
+    ) -> bool;
+    fn cv_ml_ann_mlp_set_activation_function(
+        model: *mut CStatModel,
+        function: c_int,
+        param1: c_double,
+        param2: c_double,
+    );
+    fn cv_ml_ann_mlp_set_train_method(
+        model: *mut CStatModel,
+        method: c_int,
+        param1: c_double,
+        param2: c_double,
+    );
+    fn cv_ml_ann_mlp_set_term_criteria(model: *mut CStatModel, criteria: *mut CTermCriteria);
+}
+
+fn optional_mat(mat: Option<&Mat>) -> *const CMat {
+    mat.map_or(ptr::null(), |m| m.inner)
+}
+
+// =============================================================================
+//   TrainData
+// =============================================================================
+
+/// How the training samples are laid out in the samples matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleTypes {
+    /// Each training sample is a row of samples.
+    RowSample = 0,
+    /// Each training sample occupies a column of samples.
+    ColSample = 1,
+}
+
+/// How the responses of a training set are interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseType {
+    /// Let OpenCV decide: integer responses are categorical, floating point
+    /// responses are ordered.
+    Auto = -1,
+    /// Responses are ordered (regression).
+    Ordered = 0,
+    /// Responses are class labels (classification).
+    Categorical = 1,
+}
+
+/// A training set: the samples, their responses and optional weights and
+/// subsets. Build one with [TrainData::builder](#method.builder) or
+/// [TrainData::new](#method.new).
+#[derive(Debug)]
+pub struct TrainData {
+    inner: *mut CTrainData,
+}
+
+unsafe impl Send for TrainData {}
+
+impl TrainData {
+    /// Creates a training set from `samples` (one sample per row, `CV_32F`)
+    /// and `responses` (one response per sample, `CV_32F` or `CV_32S`).
+    pub fn new(samples: &Mat, responses: &Mat) -> Result<TrainData> {
+        TrainData::builder(samples, responses).build()
+    }
+
+    /// Starts building a training set from `samples` and `responses`.
+    pub fn builder<'a>(samples: &'a Mat, responses: &'a Mat) -> TrainDataBuilder<'a> {
+        TrainDataBuilder {
+            samples: samples,
+            responses: responses,
+            layout: SampleTypes::RowSample,
+            response_type: ResponseType::Auto,
+            var_idx: None,
+            sample_idx: None,
+            sample_weights: None,
+        }
+    }
+
+    /// Splits the set into a training and a test subset: `ratio` is the
+    /// fraction of samples used for training. Models are then trained on the
+    /// training subset only, and
+    /// [StatModel::calc_error](trait.StatModel.html#method.calc_error) can
+    /// evaluate them on the test subset. Fails if `ratio` isn't within
+    /// `[0, 1]`.
+    pub fn set_train_test_split_ratio(&mut self, ratio: f64, shuffle: bool) -> Result<()> {
+        if unsafe { cv_ml_train_data_set_train_test_split_ratio(self.inner, ratio, shuffle) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::StatModel("set the train/test split ratio").into())
+        }
+    }
+
+    /// Returns the number of samples.
+    pub fn n_samples(&self) -> i32 {
+        unsafe { cv_ml_train_data_get_n_samples(self.inner) }
+    }
+
+    /// Returns the number of variables (features) per sample.
+    pub fn n_vars(&self) -> i32 {
+        unsafe { cv_ml_train_data_get_n_vars(self.inner) }
+    }
+}
+
+impl Drop for TrainData {
+    fn drop(&mut self) {
+        unsafe {
+            cv_ml_train_data_drop(self.inner);
+        }
+    }
+}
+
+/// Builder for [TrainData](struct.TrainData.html).
+#[derive(Debug, Clone, Copy)]
+pub struct TrainDataBuilder<'a> {
+    samples: &'a Mat,
+    responses: &'a Mat,
+    layout: SampleTypes,
+    response_type: ResponseType,
+    var_idx: Option<&'a Mat>,
+    sample_idx: Option<&'a Mat>,
+    sample_weights: Option<&'a Mat>,
+}
+
+impl<'a> TrainDataBuilder<'a> {
+    /// Sets how the samples are laid out, defaults to
+    /// `SampleTypes::RowSample`.
+    pub fn layout(mut self, layout: SampleTypes) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets how the responses are interpreted, defaults to
+    /// `ResponseType::Auto`.
+    pub fn response_type(mut self, response_type: ResponseType) -> Self {
+        self.response_type = response_type;
+        self
+    }
+
+    /// Restricts training to the variables listed in `var_idx` (`CV_32S`
+    /// indices or a `CV_8U` mask).
+    pub fn var_idx(mut self, var_idx: &'a Mat) -> Self {
+        self.var_idx = Some(var_idx);
+        self
+    }
+
+    /// Restricts training to the samples listed in `sample_idx` (`CV_32S`
+    /// indices or a `CV_8U` mask).
+    pub fn sample_idx(mut self, sample_idx: &'a Mat) -> Self {
+        self.sample_idx = Some(sample_idx);
+        self
+    }
+
+    /// Weights each sample (`CV_32F`, one weight per sample).
+    pub fn sample_weights(mut self, sample_weights: &'a Mat) -> Self {
+        self.sample_weights = Some(sample_weights);
+        self
+    }
+
+    /// Builds the training set. Fails if the matrices are inconsistent, for
+    /// example if there are not as many responses as samples.
+    pub fn build(self) -> Result<TrainData> {
+        let inner = unsafe {
+            cv_ml_train_data_new(
+                self.samples.inner,
+                self.layout as c_int,
+                self.responses.inner,
+                optional_mat(self.var_idx),
+                optional_mat(self.sample_idx),
+                optional_mat(self.sample_weights),
+                self.response_type as c_int,
+            )
+        };
+        if inner.is_null() {
+            Err(ErrorKind::StatModel("create training data").into())
+        } else {
+            Ok(TrainData { inner: inner })
+        }
+    }
+}
+
+// =============================================================================
+//   StatModel
+// =============================================================================
+
+/// Flag for [StatModel::predict_with_flags](trait.StatModel.html#method.predict_with_flags).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PredictFlags {
+    /// Returns the predicted class (classifiers) or value (regressors).
+    Default = 0,
+    /// Returns the raw output of the model, e.g. the decision function value
+    /// of an SVM instead of the class label.
+    RawOutput = 1,
+}
+
+/// Common interface of the statistical models of the ml module.
+///
+/// The trait is sealed, since its methods hand the model pointer to OpenCV.
+pub trait StatModel: Sized + sealed::Sealed {
+    /// Returns the pointer to the underlying `cv::ml::StatModel`.
+    #[doc(hidden)]
+    fn as_raw(&self) -> *mut CStatModel;
+
+    /// Loads a model previously stored with [save](#method.save).
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self>;
+
+    /// Trains the model on `data`.
+    fn train(&mut self, data: &TrainData) -> Result<()> {
+        if unsafe { cv_ml_stat_model_train(self.as_raw(), data.inner, 0) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::StatModel("train").into())
+        }
+    }
+
+    /// Predicts the response of each sample (one per row) in `samples`. The
+    /// result has one row per sample.
+    fn predict(&self, samples: &Mat) -> Result<Mat> {
+        self.predict_with_flags(samples, PredictFlags::Default)
+    }
+
+    /// Same as [predict](#method.predict), with explicit flags.
+    fn predict_with_flags(&self, samples: &Mat, flags: PredictFlags) -> Result<Mat> {
+        let results = Mat::new();
+        if unsafe {
+            cv_ml_stat_model_predict(self.as_raw(), samples.inner, results.inner, flags as c_int)
+        } {
+            Ok(results)
+        } else {
+            Err(ErrorKind::StatModel("predict").into())
+        }
+    }
+
+    /// Computes the error of the model on the test (`test` is true) or
+    /// training subset of `data`: the percentage of misclassified samples for
+    /// classifiers, the mean squared error for regressors.
+    fn calc_error(&self, data: &TrainData, test: bool) -> Result<f32> {
+        let mut error = 0.0;
+        if unsafe { cv_ml_stat_model_calc_error(self.as_raw(), data.inner, test, &mut error) } {
+            Ok(error)
+        } else {
+            Err(ErrorKind::StatModel("calculate error").into())
+        }
+    }
+
+    /// Stores the model in a file; the format (XML, YAML or JSON) is deduced
+    /// from the extension.
+    fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let s = path_to_cstring(&path)?;
+        if unsafe { cv_ml_stat_model_save(self.as_raw(), s.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::InvalidPath(path.as_ref().to_path_buf()).into())
+        }
+    }
+
+    /// Returns true if the model is trained.
+    fn is_trained(&self) -> bool {
+        unsafe { cv_ml_stat_model_is_trained(self.as_raw()) }
+    }
+
+    /// Returns true if the model is a classifier, false if it is a regressor.
+    fn is_classifier(&self) -> bool {
+        unsafe { cv_ml_stat_model_is_classifier(self.as_raw()) }
+    }
+
+    /// Returns the number of variables in the training samples.
+    fn var_count(&self) -> i32 {
+        unsafe { cv_ml_stat_model_get_var_count(self.as_raw()) }
+    }
+}
+
+macro_rules! stat_model {
+    ($model:ident, $new:ident, $load:ident) => {
+        impl $model {
+            /// Creates an empty model with the default parameters.
+            pub fn new() -> $model {
+                $model {
+                    inner: unsafe { $new() },
+                }
+            }
+        }
+
+        impl Default for $model {
+            fn default() -> $model {
+                $model::new()
+            }
+        }
+
+        unsafe impl Send for $model {}
+
+        impl sealed::Sealed for $model {}
+
+        impl StatModel for $model {
+            fn as_raw(&self) -> *mut CStatModel {
+                self.inner
+            }
+
+            fn load<P: AsRef<Path>>(path: P) -> Result<$model> {
+                let s = path_to_cstring(&path)?;
+                let inner = unsafe { $load(s.as_ptr()) };
+                if inner.is_null() {
+                    Err(ErrorKind::InvalidPath(path.as_ref().to_path_buf()).into())
+                } else {
+                    Ok($model { inner: inner })
+                }
+            }
+        }
+
+        impl Drop for $model {
+            fn drop(&mut self) {
+                unsafe {
+                    cv_ml_stat_model_drop(self.inner);
+                }
+            }
+        }
+    };
+}
+
+// =============================================================================
+//   SVM
+// =============================================================================
+
+/// Type of an SVM formulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
+pub enum SvmType {
+    /// C-Support Vector Classification, n classes with imperfect separation.
+    CSvc = 100,
+    /// ν-Support Vector Classification, ν controls the number of support
+    /// vectors.
+    NuSvc = 101,
+    /// Distribution estimation (one-class SVM).
+    OneClass = 102,
+    /// ε-Support Vector Regression.
+    EpsSvr = 103,
+    /// ν-Support Vector Regression.
+    NuSvr = 104,
+}
+
+/// SVM kernel type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
+pub enum SvmKernel {
+    /// Returned when a custom kernel has been set.
+    Custom = -1,
+    /// No mapping, the fastest option: `K(x, y) = x·y`.
+    Linear = 0,
+    /// Polynomial kernel: `K(x, y) = (γ x·y + coef0)^degree`.
+    Poly = 1,
+    /// Radial basis function, a good choice in most cases:
+    /// `K(x, y) = exp(-γ |x - y|²)`.
+    Rbf = 2,
+    /// Sigmoid kernel: `K(x, y) = tanh(γ x·y + coef0)`.
+    Sigmoid = 3,
+    /// Exponential Chi2 kernel, similar to the RBF kernel.
+    Chi2 = 4,
+    /// Histogram intersection kernel.
+    Inter = 5,
+}
+
+/// Support Vector Machines.
+#[derive(Debug)]
+pub struct Svm {
+    inner: *mut CStatModel,
+}
+
+stat_model!(Svm, cv_ml_svm_new, cv_ml_svm_load);
+
+impl Svm {
+    /// Sets the type of SVM formulation, defaults to `SvmType::CSvc`.
+    pub fn set_type(&mut self, svm_type: SvmType) {
+        unsafe { cv_ml_svm_set_type(self.inner, svm_type as c_int) }
+    }
+
+    /// Sets the kernel, defaults to `SvmKernel::Rbf`.
+    pub fn set_kernel(&mut self, kernel: SvmKernel) {
+        unsafe { cv_ml_svm_set_kernel(self.inner, kernel as c_int) }
+    }
+
+    /// Sets the parameter C of the `CSvc`, `EpsSvr` and `NuSvr` formulations.
+    pub fn set_c(&mut self, c: f64) {
+        unsafe { cv_ml_svm_set_c(self.inner, c) }
+    }
+
+    /// Sets the parameter γ of the `Poly`, `Rbf`, `Sigmoid` and `Chi2`
+    /// kernels.
+    pub fn set_gamma(&mut self, gamma: f64) {
+        unsafe { cv_ml_svm_set_gamma(self.inner, gamma) }
+    }
+
+    /// Sets the parameter ν of the `NuSvc`, `OneClass` and `NuSvr`
+    /// formulations.
+    pub fn set_nu(&mut self, nu: f64) {
+        unsafe { cv_ml_svm_set_nu(self.inner, nu) }
+    }
+
+    /// Sets the parameter ε of the `EpsSvr` formulation.
+    pub fn set_p(&mut self, p: f64) {
+        unsafe { cv_ml_svm_set_p(self.inner, p) }
+    }
+
+    /// Sets the degree of the `Poly` kernel.
+    pub fn set_degree(&mut self, degree: f64) {
+        unsafe { cv_ml_svm_set_degree(self.inner, degree) }
+    }
+
+    /// Sets the parameter coef0 of the `Poly` and `Sigmoid` kernels.
+    pub fn set_coef0(&mut self, coef0: f64) {
+        unsafe { cv_ml_svm_set_coef0(self.inner, coef0) }
+    }
+
+    /// Sets the termination criteria of the iterative training.
+    pub fn set_term_criteria(&mut self, criteria: &TermCriteria) {
+        unsafe { cv_ml_svm_set_term_criteria(self.inner, criteria.c_criteria) }
+    }
+
+    /// Returns the type of SVM formulation.
+    pub fn svm_type(&self) -> SvmType {
+        let t = unsafe { cv_ml_svm_get_type(self.inner) };
+        ::num::FromPrimitive::from_i32(t).unwrap_or(SvmType::CSvc)
+    }
+
+    /// Returns the kernel type.
+    pub fn kernel(&self) -> SvmKernel {
+        let k = unsafe { cv_ml_svm_get_kernel_type(self.inner) };
+        ::num::FromPrimitive::from_i32(k).unwrap_or(SvmKernel::Custom)
+    }
+
+    /// Returns the parameter C.
+    pub fn c(&self) -> f64 {
+        unsafe { cv_ml_svm_get_c(self.inner) }
+    }
+
+    /// Returns the parameter γ.
+    pub fn gamma(&self) -> f64 {
+        unsafe { cv_ml_svm_get_gamma(self.inner) }
+    }
+
+    /// Trains the SVM with the optimal parameters: C, γ, p, ν, coef0 and
+    /// degree are chosen by `k_fold` cross-validation over OpenCV's default
+    /// grids. If `balanced` is set, the folds keep the class proportions of
+    /// the whole set.
+    pub fn train_auto(&mut self, data: &TrainData, k_fold: i32, balanced: bool) -> Result<()> {
+        if unsafe { cv_ml_svm_train_auto(self.inner, data.inner, k_fold, balanced) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::StatModel("train").into())
+        }
+    }
+
+    /// Returns the support vectors, one per row. A linear SVM keeps a single
+    /// compressed vector.
+    pub fn support_vectors(&self) -> Mat {
+        Mat::from_raw(unsafe { cv_ml_svm_get_support_vectors(self.inner) })
+    }
+
+    /// Returns the `i`-th decision function as `(alpha, svidx, rho)`: the
+    /// weights and indices of its support vectors and its bias.
+    pub fn decision_function(&self, i: i32) -> (Mat, Mat, f64) {
+        let alpha = Mat::new();
+        let svidx = Mat::new();
+        let rho =
+            unsafe { cv_ml_svm_get_decision_function(self.inner, i, alpha.inner, svidx.inner) };
+        (alpha, svidx, rho)
+    }
+
+    /// Converts a trained linear SVM into a detector that can be given to
+    /// `HogDescriptor::set_svm_detector`, provided it was trained on HOG
+    /// descriptors. Fails if the SVM is not trained or its kernel is not
+    /// `SvmKernel::Linear`.
+    pub fn to_svm_detector(&self) -> Result<SvmDetector> {
+        let inner = unsafe { cv_ml_svm_to_hog_detector(self.inner) };
+        if inner.is_null() {
+            Err(ErrorKind::StatModel("convert to a linear detector").into())
+        } else {
+            Ok(SvmDetector { inner: inner })
+        }
+    }
+}
+
+// =============================================================================
+//   KNearest
+// =============================================================================
+
+/// Algorithm used by [KNearest](struct.KNearest.html) to find neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KNearestAlgorithm {
+    /// Compares against every training sample.
+    BruteForce = 1,
+    /// Uses a k-d tree.
+    KdTree = 2,
+}
+
+/// The k-nearest neighbors model.
+#[derive(Debug)]
+pub struct KNearest {
+    inner: *mut CStatModel,
+}
+
+stat_model!(KNearest, cv_ml_knearest_new, cv_ml_knearest_load);
+
+impl KNearest {
+    /// Sets the number of neighbors used by `predict`, defaults to 10.
+    pub fn set_default_k(&mut self, k: i32) {
+        unsafe { cv_ml_knearest_set_default_k(self.inner, k) }
+    }
+
+    /// Sets whether the model is a classifier (the default) or a regressor.
+    pub fn set_is_classifier(&mut self, is_classifier: bool) {
+        unsafe { cv_ml_knearest_set_is_classifier(self.inner, is_classifier) }
+    }
+
+    /// Sets the neighbor search algorithm.
+    pub fn set_algorithm_type(&mut self, algorithm: KNearestAlgorithm) {
+        unsafe { cv_ml_knearest_set_algorithm_type(self.inner, algorithm as c_int) }
+    }
+
+    /// Finds the `k` nearest neighbors of each sample (one per row) and
+    /// returns `(results, neighbor_responses, distances)`: the predictions,
+    /// the responses of the neighbors and the distances to them, one row per
+    /// sample.
+    pub fn find_nearest(&self, samples: &Mat, k: i32) -> Result<(Mat, Mat, Mat)> {
+        let results = Mat::new();
+        let neighbors = Mat::new();
+        let dist = Mat::new();
+        if unsafe {
+            cv_ml_knearest_find_nearest(
+                self.inner,
+                samples.inner,
+                k,
+                results.inner,
+                neighbors.inner,
+                dist.inner,
+            )
+        } {
+            Ok((results, neighbors, dist))
+        } else {
+            Err(ErrorKind::StatModel("find nearest").into())
+        }
+    }
+}
+
+// =============================================================================
+//   RTrees
+// =============================================================================
+
+/// Random trees (random forest).
+#[derive(Debug)]
+pub struct RTrees {
+    inner: *mut CStatModel,
+}
+
+stat_model!(RTrees, cv_ml_rtrees_new, cv_ml_rtrees_load);
+
+impl RTrees {
+    /// Sets the maximum depth of each tree.
+    pub fn set_max_depth(&mut self, max_depth: i32) {
+        unsafe { cv_ml_dtrees_set_max_depth(self.inner, max_depth) }
+    }
+
+    /// Sets the minimum number of samples a node needs to be split.
+    pub fn set_min_sample_count(&mut self, count: i32) {
+        unsafe { cv_ml_dtrees_set_min_sample_count(self.inner, count) }
+    }
+
+    /// Sets the size of the random subset of variables tested at each node;
+    /// 0 uses the square root of the number of variables.
+    pub fn set_active_var_count(&mut self, count: i32) {
+        unsafe { cv_ml_rtrees_set_active_var_count(self.inner, count) }
+    }
+
+    /// Computes the variable importance during training, see
+    /// [var_importance](#method.var_importance).
+    pub fn set_calculate_var_importance(&mut self, value: bool) {
+        unsafe { cv_ml_rtrees_set_calculate_var_importance(self.inner, value) }
+    }
+
+    /// Sets when to stop growing the forest: after a number of trees or once
+    /// the out-of-bag error is small enough.
+    pub fn set_term_criteria(&mut self, criteria: &TermCriteria) {
+        unsafe { cv_ml_rtrees_set_term_criteria(self.inner, criteria.c_criteria) }
+    }
+
+    /// Returns the importance of each variable, empty unless enabled with
+    /// [set_calculate_var_importance](#method.set_calculate_var_importance).
+    pub fn var_importance(&self) -> Mat {
+        Mat::from_raw(unsafe { cv_ml_rtrees_get_var_importance(self.inner) })
+    }
+}
+
+// =============================================================================
+//   Boost
+// =============================================================================
+
+/// Boosting algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoostType {
+    /// Discrete AdaBoost.
+    Discrete = 0,
+    /// Real AdaBoost, uses confidence-rated predictions.
+    Real = 1,
+    /// LogitBoost.
+    Logit = 2,
+    /// Gentle AdaBoost, puts less weight on outliers.
+    Gentle = 3,
+}
+
+/// Boosted tree classifier.
+#[derive(Debug)]
+pub struct Boost {
+    inner: *mut CStatModel,
+}
+
+stat_model!(Boost, cv_ml_boost_new, cv_ml_boost_load);
+
+impl Boost {
+    /// Sets the boosting algorithm, defaults to `BoostType::Real`.
+    pub fn set_boost_type(&mut self, boost_type: BoostType) {
+        unsafe { cv_ml_boost_set_boost_type(self.inner, boost_type as c_int) }
+    }
+
+    /// Sets the number of weak classifiers.
+    pub fn set_weak_count(&mut self, count: i32) {
+        unsafe { cv_ml_boost_set_weak_count(self.inner, count) }
+    }
+
+    /// Sets the threshold (between 0 and 1) used to skip samples with small
+    /// weights; 0 disables trimming.
+    pub fn set_weight_trim_rate(&mut self, rate: f64) {
+        unsafe { cv_ml_boost_set_weight_trim_rate(self.inner, rate) }
+    }
+
+    /// Sets the maximum depth of the weak trees.
+    pub fn set_max_depth(&mut self, max_depth: i32) {
+        unsafe { cv_ml_dtrees_set_max_depth(self.inner, max_depth) }
+    }
+}
+
+// =============================================================================
+//   LogisticRegression
+// =============================================================================
+
+/// Regularization of a [LogisticRegression](struct.LogisticRegression.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regularization {
+    /// No regularization.
+    Disable = -1,
+    /// L1 norm.
+    L1 = 0,
+    /// L2 norm.
+    L2 = 1,
+}
+
+/// Training method of a [LogisticRegression](struct.LogisticRegression.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogisticRegressionMethod {
+    /// Batch gradient descent.
+    Batch = 0,
+    /// Mini-batch gradient descent, see
+    /// [set_mini_batch_size](struct.LogisticRegression.html#method.set_mini_batch_size).
+    MiniBatch = 1,
+}
+
+/// Logistic regression classifier. Responses must be `CV_32F`.
+#[derive(Debug)]
+pub struct LogisticRegression {
+    inner: *mut CStatModel,
+}
+
+stat_model!(
+    LogisticRegression,
+    cv_ml_logistic_regression_new,
+    cv_ml_logistic_regression_load
+);
+
+impl LogisticRegression {
+    /// Sets the learning rate.
+    pub fn set_learning_rate(&mut self, rate: f64) {
+        unsafe { cv_ml_logistic_regression_set_learning_rate(self.inner, rate) }
+    }
+
+    /// Sets the number of iterations.
+    pub fn set_iterations(&mut self, iterations: i32) {
+        unsafe { cv_ml_logistic_regression_set_iterations(self.inner, iterations) }
+    }
+
+    /// Sets the kind of regularization.
+    pub fn set_regularization(&mut self, regularization: Regularization) {
+        unsafe { cv_ml_logistic_regression_set_regularization(self.inner, regularization as c_int) }
+    }
+
+    /// Sets the training method.
+    pub fn set_train_method(&mut self, method: LogisticRegressionMethod) {
+        unsafe { cv_ml_logistic_regression_set_train_method(self.inner, method as c_int) }
+    }
+
+    /// Sets the number of samples per step of mini-batch gradient descent.
+    pub fn set_mini_batch_size(&mut self, size: i32) {
+        unsafe { cv_ml_logistic_regression_set_mini_batch_size(self.inner, size) }
+    }
+
+    /// Sets the termination criteria of the training.
+    pub fn set_term_criteria(&mut self, criteria: &TermCriteria) {
+        unsafe { cv_ml_logistic_regression_set_term_criteria(self.inner, criteria.c_criteria) }
+    }
+}
+
+// =============================================================================
+//   NormalBayesClassifier
+// =============================================================================
+
+/// Bayes classifier for normally distributed data.
+#[derive(Debug)]
+pub struct NormalBayesClassifier {
+    inner: *mut CStatModel,
+}
+
+stat_model!(
+    NormalBayesClassifier,
+    cv_ml_normal_bayes_new,
+    cv_ml_normal_bayes_load
+);
+
+impl NormalBayesClassifier {
+    /// Predicts the class of each sample (one per row) and returns
+    /// `(outputs, probabilities)`: the predicted classes and, for each
+    /// sample, the probability of every class.
+    pub fn predict_prob(&self, samples: &Mat) -> Result<(Mat, Mat)> {
+        let outputs = Mat::new();
+        let probs = Mat::new();
+        if unsafe {
+            cv_ml_normal_bayes_predict_prob(self.inner, samples.inner, outputs.inner, probs.inner)
+        } {
+            Ok((outputs, probs))
+        } else {
+            Err(ErrorKind::StatModel("predict").into())
+        }
+    }
+}
+
+// =============================================================================
+//   ANN_MLP
+// =============================================================================
+
+/// Activation function of the neurons of an [AnnMlp](struct.AnnMlp.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationFunction {
+    /// Identity function: `f(x) = x`.
+    Identity = 0,
+    /// Symmetrical sigmoid: `f(x) = β (1 - e^(-αx)) / (1 + e^(-αx))`.
+    SigmoidSym = 1,
+    /// Gaussian function: `f(x) = β e^(-αx²)`.
+    Gaussian = 2,
+    /// ReLU function: `f(x) = max(0, x)`.
+    Relu = 3,
+    /// Leaky ReLU function: `f(x) = x` for `x > 0`, `αx` otherwise.
+    LeakyRelu = 4,
+}
+
+/// Training method of an [AnnMlp](struct.AnnMlp.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnMlpTrainMethod {
+    /// Back-propagation; the parameters are the weight gradient and momentum
+    /// scales.
+    Backprop = 0,
+    /// Resilient back-propagation; the parameters are the initial update
+    /// value and its lower limit.
+    Rprop = 1,
+    /// Simulated annealing.
+    Anneal = 2,
+}
+
+/// Multi-layer perceptron. Responses are expected as one row per sample
+/// with one column per output neuron.
+#[derive(Debug)]
+pub struct AnnMlp {
+    inner: *mut CStatModel,
+}
+
+stat_model!(AnnMlp, cv_ml_ann_mlp_new, cv_ml_ann_mlp_load);
+
+impl AnnMlp {
+    /// Sets the number of neurons of each layer, including the input and
+    /// output layers. Fails if there are fewer than 2 layers or a layer has
+    /// no neurons.
+    pub fn set_layer_sizes(&mut self, sizes: &[i32]) -> Result<()> {
+        if unsafe { cv_ml_ann_mlp_set_layer_sizes(self.inner, sizes.as_ptr(), sizes.len()) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::StatModel("set layer sizes").into())
+        }
+    }
+
+    /// Sets the activation function of every neuron; `param1` and `param2`
+    /// are its α and β.
+    pub fn set_activation_function(
+        &mut self,
+        function: ActivationFunction,
+        param1: f64,
+        param2: f64,
+    ) {
+        unsafe {
+            cv_ml_ann_mlp_set_activation_function(self.inner, function as c_int, param1, param2)
+        }
+    }
+
+    /// Sets the training method and its two parameters.
+    pub fn set_train_method(&mut self, method: AnnMlpTrainMethod, param1: f64, param2: f64) {
+        unsafe { cv_ml_ann_mlp_set_train_method(self.inner, method as c_int, param1, param2) }
+    }
+
+    /// Sets the termination criteria of the training.
+    pub fn set_term_criteria(&mut self, criteria: &TermCriteria) {
+        unsafe { cv_ml_ann_mlp_set_term_criteria(self.inner, criteria.c_criteria) }
+    }
+}
@@ -3,7 +3,8 @@
 
 use super::core::*;
 use super::errors::*;
-use libc::{c_char, c_double, c_int};
+use libc::{c_char, c_double, c_int, size_t};
+use std::cmp::Ordering;
 use std::ffi::CString;
 use std::path::Path;
 use std::vec::Vec;
@@ -15,6 +16,45 @@ pub trait ObjectDetect {
     /// Detects the object inside this image and returns a list of detections
     /// with their confidence.
     fn detect(&self, image: &Mat) -> Vec<(Rect, f64)>;
+
+    /// Detects the object inside this image and returns a list of detections
+    /// with their metadata. By default the results of `detect` are reported as
+    /// class 0 without a label.
+    fn detect_with_meta(&self, image: &Mat) -> Vec<Detection> {
+        self.detect(image)
+            .into_iter()
+            .map(|(rect, score)| Detection::new(rect, score))
+            .collect::<Vec<_>>()
+    }
+}
+
+/// A single object found by an [ObjectDetect](trait.ObjectDetect.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    /// Bounding box of the object.
+    pub rect: Rect,
+
+    /// Confidence of the detection. Its range depends on the detector, but a
+    /// higher score always means a more confident detection.
+    pub score: f64,
+
+    /// Index of the detected class; single class detectors use 0.
+    pub class_id: usize,
+
+    /// Name of the detected class, if known.
+    pub label: Option<String>,
+}
+
+impl Detection {
+    /// Creates a detection of class 0 without a label.
+    pub fn new(rect: Rect, score: f64) -> Detection {
+        Detection {
+            rect: rect,
+            score: score,
+            class_id: 0,
+            label: None,
+        }
+    }
 }
 
 /// Cascade classifier class for object detection.
@@ -40,14 +80,22 @@ extern "C" {
         min_size: Size2i,
         max_size: Size2i,
     );
+    fn cv_cascade_classifier_detect_with_weights(
+        cc: *mut CCascadeClassifier,
+        cmat: *mut CMat,
+        vec_of_rect: *mut CVecOfRect,
+        level_weights: *mut CVecDouble,
+        scale_factor: c_double,
+        min_neighbors: c_int,
+        flags: c_int,
+        min_size: Size2i,
+        max_size: Size2i,
+    );
 }
 
 impl ObjectDetect for CascadeClassifier {
     fn detect(&self, image: &Mat) -> Vec<(Rect, f64)> {
-        self.detect_multiscale(image)
-            .into_iter()
-            .map(|r| (r, 0f64))
-            .collect::<Vec<_>>()
+        self.detect_with_confidence(image, 1.1, 3, Size2i::default(), Size2i::default())
     }
 }
 
@@ -122,6 +170,41 @@ impl CascadeClassifier {
         }
         c_result.rustify()
     }
+
+    /// Detects the object using parameters specified (see
+    /// [detect_with_params](#method.detect_with_params)) and returns each
+    /// object with its confidence, which is the weight of the last cascade
+    /// stage the object passed. Higher weights mean more confident
+    /// detections.
+    pub fn detect_with_confidence(
+        &self,
+        mat: &Mat,
+        scale_factor: f32,
+        min_neighbors: i32,
+        min_size: Size2i,
+        max_size: Size2i,
+    ) -> Vec<(Rect, f64)> {
+        let mut c_result = CVecOfRect::default();
+        let mut c_weights = CVecDouble::default();
+        unsafe {
+            cv_cascade_classifier_detect_with_weights(
+                self.inner,
+                mat.inner,
+                &mut c_result,
+                &mut c_weights,
+                scale_factor as c_double,
+                min_neighbors,
+                0,
+                min_size,
+                max_size,
+            )
+        }
+        c_result
+            .rustify()
+            .into_iter()
+            .zip(c_weights.rustify())
+            .collect::<Vec<_>>()
+    }
 }
 
 impl Drop for CascadeClassifier {
@@ -132,6 +215,144 @@ impl Drop for CascadeClassifier {
     }
 }
 
+// =============================================================================
+//   Grouping and non-maximum suppression
+// =============================================================================
+
+extern "C" {
+    fn cv_group_rectangles(
+        rects: *const Rect,
+        len: size_t,
+        group_threshold: c_int,
+        eps: c_double,
+        grouped: *mut CVecOfRect,
+        weights: *mut CVecDouble,
+    );
+    fn cv_group_rectangles_meanshift(
+        rects: *const Rect,
+        weights: *const c_double,
+        scales: *const c_double,
+        len: size_t,
+        detect_threshold: c_double,
+        win_det_size: Size2i,
+        grouped: *mut CVecOfRect,
+        grouped_weights: *mut CVecDouble,
+    );
+}
+
+/// Groups similar rectangles, see OpenCV's `groupRectangles`. Clusters with
+/// `group_threshold` or fewer rectangles are rejected, the others are replaced
+/// by their average rectangle. `eps` is the relative difference between sides
+/// of rectangles to merge them into a group (0.2 is a common choice).
+///
+/// Returns the grouped rectangles along with the number of rectangles merged
+/// into each of them.
+pub fn group_rectangles(rects: &[Rect], group_threshold: i32, eps: f64) -> Vec<(Rect, f64)> {
+    let mut grouped = CVecOfRect::default();
+    let mut weights = CVecDouble::default();
+    unsafe {
+        cv_group_rectangles(
+            rects.as_ptr(),
+            rects.len(),
+            group_threshold,
+            eps,
+            &mut grouped,
+            &mut weights,
+        )
+    }
+    grouped
+        .rustify()
+        .into_iter()
+        .zip(weights.rustify())
+        .collect::<Vec<_>>()
+}
+
+/// Groups detections with mean-shift clustering, as done by HOG when
+/// [use_meanshift_grouping](struct.HogParams.html#structfield.use_meanshift_grouping)
+/// is set. `scales` holds the pyramid scale each detection was found at,
+/// clusters whose weight is below `detect_threshold` are rejected and
+/// `win_det_size` is the size of the detection window. Fails unless there's
+/// one scale per detection.
+pub fn group_rectangles_meanshift(
+    detections: &[(Rect, f64)],
+    scales: &[f64],
+    detect_threshold: f64,
+    win_det_size: Size2i,
+) -> Result<Vec<(Rect, f64)>> {
+    if detections.len() != scales.len() {
+        let expected = vec![detections.len() as i32];
+        return Err(ErrorKind::ShapeMismatch(expected, vec![scales.len() as i32]).into());
+    }
+    let rects = detections.iter().map(|d| d.0).collect::<Vec<_>>();
+    let weights = detections.iter().map(|d| d.1).collect::<Vec<_>>();
+    let mut grouped = CVecOfRect::default();
+    let mut grouped_weights = CVecDouble::default();
+    unsafe {
+        cv_group_rectangles_meanshift(
+            rects.as_ptr(),
+            weights.as_ptr(),
+            scales.as_ptr(),
+            rects.len(),
+            detect_threshold,
+            win_det_size,
+            &mut grouped,
+            &mut grouped_weights,
+        )
+    }
+    Ok(grouped
+        .rustify()
+        .into_iter()
+        .zip(grouped_weights.rustify())
+        .collect::<Vec<_>>())
+}
+
+fn by_descending_score(a: &(Rect, f64), b: &(Rect, f64)) -> Ordering {
+    b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal)
+}
+
+/// Greedy non-maximum suppression on the output of
+/// [ObjectDetect::detect](trait.ObjectDetect.html#tymethod.detect): starting
+/// with the most confident detection, every detection overlapping an already
+/// kept one by more than `iou_threshold` (intersection over union) is
+/// dropped. The result is sorted by descending confidence.
+pub fn nms(detections: &[(Rect, f64)], iou_threshold: f64) -> Vec<(Rect, f64)> {
+    let mut sorted = detections.to_vec();
+    sorted.sort_by(by_descending_score);
+
+    let mut kept: Vec<(Rect, f64)> = Vec::new();
+    for d in sorted {
+        if kept.iter().all(|k| k.0.iou(&d.0) <= iou_threshold) {
+            kept.push(d);
+        }
+    }
+    kept
+}
+
+/// Gaussian soft non-maximum suppression ([Bodla et al.
+/// 2017](https://arxiv.org/abs/1704.04503)). Instead of dropping overlapping
+/// detections, their confidence is decayed by `exp(-iou^2 / sigma)` every
+/// time a more confident detection is selected; detections whose confidence
+/// falls below `score_threshold` are dropped. The result is sorted by
+/// descending (decayed) confidence.
+pub fn soft_nms(detections: &[(Rect, f64)], sigma: f64, score_threshold: f64) -> Vec<(Rect, f64)> {
+    let mut remaining = detections.to_vec();
+    let mut kept = Vec::new();
+    while !remaining.is_empty() {
+        remaining.sort_by(by_descending_score);
+        let best = remaining.remove(0);
+        if best.1 < score_threshold {
+            break;
+        }
+
+        for d in &mut remaining {
+            let iou = best.0.iou(&d.0);
+            d.1 *= (-iou * iou / sigma).exp();
+        }
+        kept.push(best);
+    }
+    kept
+}
+
 /// SvmDetector
 #[derive(Debug)]
 pub struct SvmDetector {
@@ -142,6 +363,11 @@ pub struct SvmDetector {
 extern "C" {
     fn cv_hog_default_people_detector() -> *mut CSvmDetector;
     fn cv_hog_daimler_people_detector() -> *mut CSvmDetector;
+    fn cv_hog_detector_from_coefficients(
+        coefficients: *const f32,
+        len: size_t,
+    ) -> *mut CSvmDetector;
+    fn cv_hog_detector_len(d: *const CSvmDetector) -> size_t;
     fn cv_hog_detector_drop(d: *mut CSvmDetector);
 }
 
@@ -162,6 +388,39 @@ impl SvmDetector {
             inner: unsafe { cv_hog_daimler_people_detector() },
         }
     }
+
+    /// Creates a detector from the coefficients of a trained linear SVM: the
+    /// weights (one per element of the HOG descriptor) followed by the bias
+    /// (the free coefficient, which may be omitted). The size is checked when
+    /// the detector is set, see
+    /// [set_svm_detector](struct.HogDescriptor.html#method.set_svm_detector).
+    pub fn from_coefficients(coefficients: Vec<f32>) -> SvmDetector {
+        SvmDetector {
+            inner: unsafe {
+                cv_hog_detector_from_coefficients(coefficients.as_ptr(), coefficients.len())
+            },
+        }
+    }
+
+    /// Returns the number of coefficients of this detector.
+    pub fn len(&self) -> usize {
+        unsafe { cv_hog_detector_len(self.inner) }
+    }
+
+    /// Returns true if this detector has no coefficients.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Checks that an SVM detector with `len` coefficients matches a HOG
+/// descriptor of `descriptor_size`. An empty detector removes the detector.
+fn check_detector_size(descriptor_size: usize, len: usize) -> Result<()> {
+    if len == 0 || len == descriptor_size || len == descriptor_size + 1 {
+        Ok(())
+    } else {
+        Err(ErrorKind::SvmDetectorSize(descriptor_size, len).into())
+    }
 }
 
 impl Drop for SvmDetector {
@@ -174,6 +433,7 @@ impl Drop for SvmDetector {
 
 /// Parameters that controls the behavior of HOG.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HogParams {
     /// Detection window size. Align to block size and block stride. The default
     /// is 64x128, trained the same as original paper.
@@ -231,8 +491,8 @@ pub struct HogParams {
     /// The useMeanShiftGrouping parameter is a boolean indicating whether or
     /// not mean-shift grouping should be performed to handle potential
     /// overlapping bounding boxes. While this value should not be set and users
-    /// should employ non-maxima suppression instead, we support setting it as a
-    /// library function.
+    /// should employ non-maxima suppression (see [nms](fn.nms.html)) instead,
+    /// we support setting it as a library function.
     pub use_meanshift_grouping: bool,
 
     /// The `finalThreshold` parameter is mainly used to select the clusters
@@ -328,15 +588,167 @@ impl ObjectDetect for HogDescriptor {
 impl HogDescriptor {
     /// Creates a HogDescriptor with provided parameters.
     pub fn with_params(params: HogParams) -> HogDescriptor {
+        let inner = unsafe {
+            cv_hog_new_with_params(
+                params.win_size,
+                params.block_size,
+                params.block_stride,
+                params.cell_size,
+                params.nbins,
+                params.win_sigma,
+                params.l2hys_threshold,
+                params.gamma_correction,
+                params.nlevels as c_int,
+            )
+        };
         HogDescriptor {
-            inner: unsafe { cv_hog_new() },
+            inner: inner,
             params: params,
         }
     }
 
-    /// Sets the SVM detector.
-    pub fn set_svm_detector(&mut self, detector: SvmDetector) {
-        unsafe { cv_hog_set_svm_detector(self.inner, detector.inner) }
+    /// Creates a HogDescriptor from a configuration (and possibly its SVM
+    /// detector) saved with [save](#method.save).
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<HogDescriptor> {
+        let mut hog = HogDescriptor::default();
+        hog.load(path)?;
+        Ok(hog)
+    }
+
+    /// Sets the SVM detector. The detector must have either
+    /// `get_descriptor_size()` or `get_descriptor_size() + 1` coefficients.
+    pub fn set_svm_detector(&mut self, detector: SvmDetector) -> Result<()> {
+        check_detector_size(self.get_descriptor_size(), detector.len())?;
+        if unsafe { cv_hog_set_svm_detector(self.inner, detector.inner) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("set_svm_detector").into())
+        }
+    }
+
+    /// Returns the number of coefficients required for the classification,
+    /// i.e. the length of the descriptor of a single detection window.
+    pub fn get_descriptor_size(&self) -> usize {
+        unsafe { cv_hog_get_descriptor_size(self.inner) }
+    }
+
+    /// Checks whether the SVM detector that has been set matches the
+    /// descriptor size. No detector at all is also considered valid.
+    pub fn check_detector_size(&self) -> bool {
+        unsafe { cv_hog_check_detector_size(self.inner) }
+    }
+
+    /// Computes the HOG descriptors of `image` and returns them concatenated.
+    ///
+    /// * `win_stride` - Window stride. It must be a multiple of block stride.
+    /// * `padding` - Padding added around the image.
+    /// * `locations` - Top-left corners of the windows to compute. If empty,
+    ///   the image is scanned with `win_stride`.
+    pub fn compute(
+        &self,
+        image: &Mat,
+        win_stride: Size2i,
+        padding: Size2i,
+        locations: &[Point2i],
+    ) -> Result<Vec<f32>> {
+        let mut descriptors = CVecFloat::default();
+        let ok = unsafe {
+            cv_hog_compute(
+                self.inner,
+                image.inner,
+                &mut descriptors,
+                win_stride,
+                padding,
+                locations.as_ptr(),
+                locations.len(),
+            )
+        };
+        if ok {
+            Ok(descriptors.rustify())
+        } else {
+            Err(ErrorKind::Operation("compute").into())
+        }
+    }
+
+    /// Computes the gradients of `image`, padded by `padding_tl` and
+    /// `padding_br`. Returns the gradient magnitudes (`CV_32FC2`, split
+    /// between the two closest orientation bins) and the indices of these
+    /// bins (`CV_8UC2`).
+    pub fn compute_gradient(
+        &self,
+        image: &Mat,
+        padding_tl: Size2i,
+        padding_br: Size2i,
+    ) -> Result<(Mat, Mat)> {
+        let grad = Mat::new();
+        let angle_ofs = Mat::new();
+        let ok = unsafe {
+            cv_hog_compute_gradient(
+                self.inner,
+                image.inner,
+                grad.inner,
+                angle_ofs.inner,
+                padding_tl,
+                padding_br,
+            )
+        };
+        if ok {
+            Ok((grad, angle_ofs))
+        } else {
+            Err(ErrorKind::Operation("compute_gradient").into())
+        }
+    }
+
+    /// Saves the HOG configuration and SVM detector to a file. The format
+    /// (XML or YAML) is chosen from the extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let error = ErrorKind::InvalidPath(path.as_ref().to_path_buf());
+        if let Some(p) = path.as_ref().to_str() {
+            let s = CString::new(p)?;
+            if unsafe { cv_hog_save(self.inner, s.as_ptr()) } {
+                return Ok(());
+            }
+        }
+
+        Err(error.into())
+    }
+
+    /// Loads a HOG configuration and SVM detector saved with
+    /// [save](#method.save). The descriptor parameters in `params` are
+    /// updated accordingly; the detection parameters are left untouched.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let error = ErrorKind::InvalidPath(path.as_ref().to_path_buf());
+        if let Some(p) = path.as_ref().to_str() {
+            let s = CString::new(p)?;
+            if unsafe { cv_hog_load(self.inner, s.as_ptr()) } {
+                self.update_params();
+                return Ok(());
+            }
+        }
+
+        Err(error.into())
+    }
+
+    /// Reads the descriptor parameters back from the C++ object.
+    fn update_params(&mut self) {
+        let mut nbins: c_int = 0;
+        let mut nlevels: c_int = 0;
+        unsafe {
+            cv_hog_get_params(
+                self.inner,
+                &mut self.params.win_size,
+                &mut self.params.block_size,
+                &mut self.params.block_stride,
+                &mut self.params.cell_size,
+                &mut nbins,
+                &mut self.params.win_sigma,
+                &mut self.params.l2hys_threshold,
+                &mut self.params.gamma_correction,
+                &mut nlevels,
+            )
+        }
+        self.params.nbins = nbins;
+        self.params.nlevels = nlevels as usize;
     }
 }
 
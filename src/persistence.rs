@@ -0,0 +1,793 @@
+//! XML/YAML/JSON persistence, see [OpenCV
+//! FileStorage](https://docs.opencv.org/3.4.3/da/d56/classcv_1_1FileStorage.html).
+//! Values are written and read through the [Storable](trait.Storable.html)
+//! trait, using the same layout as the C++ API so that files can be shared
+//! with OpenCV tools.
+
+use super::core::*;
+use super::errors::*;
+use super::objdetect::HogParams;
+use super::video::tracking::{TermCriteria, TermType};
+use libc::{c_char, c_double, c_int, size_t};
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use super::wrapper::*;
+
+enum CFileStorage {}
+enum CFileNode {}
+enum CFileNodeIterator {}
+
+extern "C" {
+    fn cv_file_storage_new(source: *const c_char, flags: c_int) -> *mut CFileStorage;
+    fn cv_file_storage_drop(fs: *mut CFileStorage);
+    fn cv_file_storage_release(fs: *mut CFileStorage) -> bool;
+    fn cv_file_storage_release_and_get_string(fs: *mut CFileStorage) -> *mut c_char;
+    fn cv_string_drop(s: *mut c_char);
+
+    fn cv_file_storage_write_int(fs: *mut CFileStorage, name: *const c_char, value: c_int) -> bool;
+    fn cv_file_storage_write_double(
+        fs: *mut CFileStorage,
+        name: *const c_char,
+        value: c_double,
+    ) -> bool;
+    fn cv_file_storage_write_string(
+        fs: *mut CFileStorage,
+        name: *const c_char,
+        value: *const c_char,
+    ) -> bool;
+    fn cv_file_storage_write_mat(
+        fs: *mut CFileStorage,
+        name: *const c_char,
+        mat: *const CMat,
+    ) -> bool;
+    fn cv_file_storage_start_struct(
+        fs: *mut CFileStorage,
+        name: *const c_char,
+        flags: c_int,
+    ) -> bool;
+    fn cv_file_storage_end_struct(fs: *mut CFileStorage) -> bool;
+
+    fn cv_file_storage_root(fs: *mut CFileStorage) -> *mut CFileNode;
+    fn cv_file_node_get(node: *const CFileNode, name: *const c_char) -> *mut CFileNode;
+    fn cv_file_node_at(node: *const CFileNode, index: size_t) -> *mut CFileNode;
+    fn cv_file_node_drop(node: *mut CFileNode);
+    fn cv_file_node_iterator_new(node: *const CFileNode) -> *mut CFileNodeIterator;
+    fn cv_file_node_iterator_next(it: *mut CFileNodeIterator) -> *mut CFileNode;
+    fn cv_file_node_iterator_drop(it: *mut CFileNodeIterator);
+    fn cv_file_node_type(node: *const CFileNode) -> c_int;
+    fn cv_file_node_size(node: *const CFileNode) -> size_t;
+    fn cv_file_node_name(node: *const CFileNode) -> *mut c_char;
+    fn cv_file_node_read_int(node: *const CFileNode, value: *mut c_int) -> bool;
+    fn cv_file_node_read_double(node: *const CFileNode, value: *mut c_double) -> bool;
+    fn cv_file_node_read_string(node: *const CFileNode) -> *mut c_char;
+    fn cv_file_node_read_mat(node: *const CFileNode, mat: *mut CMat) -> bool;
+}
+
+const MEMORY: c_int = 4;
+const STRUCT_SEQ: c_int = 5;
+const STRUCT_MAP: c_int = 6;
+const STRUCT_FLOW: c_int = 8;
+
+/// Takes ownership of a string allocated by the C side.
+fn take_c_string(s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let result = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+    unsafe { cv_string_drop(s) };
+    Some(result)
+}
+
+/// How a [FileStorage](struct.FileStorage.html) is opened.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FileStorageMode {
+    /// Open the file for reading.
+    Read = 0,
+    /// Open the file for writing, replacing its content.
+    Write = 1,
+    /// Open the file for appending.
+    Append = 2,
+}
+
+/// The format used when writing to memory. Files pick the format from their
+/// extension (`.xml`, `.yml`/`.yaml` or `.json`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FileStorageFormat {
+    /// Let OpenCV decide; XML when writing to memory.
+    Auto = 0,
+    /// XML format.
+    Xml = 8,
+    /// YAML format.
+    Yaml = 16,
+    /// JSON format.
+    Json = 24,
+}
+
+/// The kind of value a [FileNode](struct.FileNode.html) holds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive)]
+pub enum FileNodeType {
+    /// Empty node.
+    None = 0,
+    /// Integer.
+    Int = 1,
+    /// Floating-point number.
+    Real = 2,
+    /// Text string.
+    String = 3,
+    /// Sequence of nodes.
+    Seq = 4,
+    /// Mapping from names to nodes.
+    Map = 5,
+}
+
+/// XML/YAML/JSON file storage, backed either by a file or by memory.
+#[derive(Debug)]
+pub struct FileStorage {
+    inner: *mut CFileStorage,
+}
+
+impl FileStorage {
+    fn from_source(source: &CString, flags: c_int) -> Result<FileStorage> {
+        let inner = unsafe { cv_file_storage_new(source.as_ptr(), flags) };
+        if inner.is_null() {
+            Err(ErrorKind::Operation("FileStorage::open").into())
+        } else {
+            Ok(FileStorage { inner: inner })
+        }
+    }
+
+    /// Opens a file. The format is deduced from the extension.
+    pub fn open<P: AsRef<Path>>(path: P, mode: FileStorageMode) -> Result<FileStorage> {
+        FileStorage::from_source(&path_to_cstring(&path)?, mode as c_int)
+    }
+
+    /// Parses an in-memory document for reading. The format is detected from
+    /// the content.
+    pub fn from_memory(content: &str) -> Result<FileStorage> {
+        FileStorage::from_source(
+            &CString::new(content)?,
+            FileStorageMode::Read as c_int | MEMORY,
+        )
+    }
+
+    /// Creates an in-memory storage for writing; retrieve the document with
+    /// [release_and_get_string](#method.release_and_get_string).
+    pub fn in_memory(format: FileStorageFormat) -> Result<FileStorage> {
+        let flags = FileStorageMode::Write as c_int | MEMORY | format as c_int;
+        FileStorage::from_source(&CString::default(), flags)
+    }
+
+    /// Writes `value` under `name`. Inside a sequence, `name` must be empty
+    /// (see [push](#method.push)).
+    pub fn write<T: Storable>(&mut self, name: &str, value: &T) -> Result<()> {
+        value.write_to(self, name)
+    }
+
+    /// Appends `value` to the sequence that is currently being written.
+    pub fn push<T: Storable>(&mut self, value: &T) -> Result<()> {
+        value.write_to(self, "")
+    }
+
+    /// Starts writing a mapping named `name`. Finish it with
+    /// [end_struct](#method.end_struct).
+    pub fn start_map(&mut self, name: &str) -> Result<()> {
+        self.start_struct(name, STRUCT_MAP)
+    }
+
+    /// Starts writing a sequence named `name`. Finish it with
+    /// [end_struct](#method.end_struct).
+    pub fn start_seq(&mut self, name: &str) -> Result<()> {
+        self.start_struct(name, STRUCT_SEQ)
+    }
+
+    /// Starts writing a sequence that is kept on a single line, like `[ 1, 2
+    /// ]`. Finish it with [end_struct](#method.end_struct).
+    pub fn start_flow_seq(&mut self, name: &str) -> Result<()> {
+        self.start_struct(name, STRUCT_SEQ | STRUCT_FLOW)
+    }
+
+    fn start_struct(&mut self, name: &str, flags: c_int) -> Result<()> {
+        let s = CString::new(name)?;
+        check_write(unsafe { cv_file_storage_start_struct(self.inner, s.as_ptr(), flags) })
+    }
+
+    /// Finishes the mapping or sequence that is currently being written.
+    pub fn end_struct(&mut self) -> Result<()> {
+        check_write(unsafe { cv_file_storage_end_struct(self.inner) })
+    }
+
+    /// Returns the top-level mapping of the document.
+    pub fn root(&self) -> Option<FileNode> {
+        FileNode::from_raw(unsafe { cv_file_storage_root(self.inner) })
+    }
+
+    /// Returns the top-level node named `name`.
+    pub fn get(&self, name: &str) -> Option<FileNode> {
+        self.root().and_then(|root| root.get(name))
+    }
+
+    /// Reads the top-level value named `name`.
+    pub fn read<T: Storable>(&self, name: &str) -> Result<T> {
+        match self.get(name) {
+            Some(node) => node.read(),
+            None => Err(ErrorKind::MissingKey(name.to_owned()).into()),
+        }
+    }
+
+    /// Closes the storage and flushes it to disk.
+    pub fn release(self) -> Result<()> {
+        if unsafe { cv_file_storage_release(self.inner) } {
+            Ok(())
+        } else {
+            Err(ErrorKind::Operation("FileStorage::release").into())
+        }
+    }
+
+    /// Closes an in-memory storage and returns the written document.
+    pub fn release_and_get_string(self) -> Result<String> {
+        let s = unsafe { cv_file_storage_release_and_get_string(self.inner) };
+        take_c_string(s)
+            .ok_or_else(|| ErrorKind::Operation("FileStorage::release_and_get_string").into())
+    }
+
+    fn write_int(&mut self, name: &str, value: i32) -> Result<()> {
+        let s = CString::new(name)?;
+        check_write(unsafe { cv_file_storage_write_int(self.inner, s.as_ptr(), value) })
+    }
+
+    fn write_double(&mut self, name: &str, value: f64) -> Result<()> {
+        let s = CString::new(name)?;
+        check_write(unsafe { cv_file_storage_write_double(self.inner, s.as_ptr(), value) })
+    }
+
+    fn write_string(&mut self, name: &str, value: &str) -> Result<()> {
+        let s = CString::new(name)?;
+        let v = CString::new(value)?;
+        check_write(unsafe { cv_file_storage_write_string(self.inner, s.as_ptr(), v.as_ptr()) })
+    }
+
+    fn write_mat(&mut self, name: &str, mat: &Mat) -> Result<()> {
+        let s = CString::new(name)?;
+        check_write(unsafe { cv_file_storage_write_mat(self.inner, s.as_ptr(), mat.inner) })
+    }
+}
+
+fn check_write(status: bool) -> Result<()> {
+    if status {
+        Ok(())
+    } else {
+        Err(ErrorKind::Operation("FileStorage::write").into())
+    }
+}
+
+impl Drop for FileStorage {
+    fn drop(&mut self) {
+        unsafe {
+            cv_file_storage_drop(self.inner);
+        }
+    }
+}
+
+/// A node of a document read by [FileStorage](struct.FileStorage.html). It
+/// can't outlive the storage.
+#[derive(Debug)]
+pub struct FileNode<'a> {
+    inner: *mut CFileNode,
+    storage: PhantomData<&'a FileStorage>,
+}
+
+impl<'a> FileNode<'a> {
+    fn from_raw(inner: *mut CFileNode) -> Option<FileNode<'a>> {
+        if inner.is_null() {
+            None
+        } else {
+            Some(FileNode {
+                inner: inner,
+                storage: PhantomData,
+            })
+        }
+    }
+
+    /// Returns the kind of value held by this node.
+    pub fn node_type(&self) -> FileNodeType {
+        let t = unsafe { cv_file_node_type(self.inner) };
+        ::num::FromPrimitive::from_i32(t).unwrap_or(FileNodeType::None)
+    }
+
+    /// Returns the number of elements of a sequence or mapping, 1 for other
+    /// values.
+    pub fn len(&self) -> usize {
+        unsafe { cv_file_node_size(self.inner) }
+    }
+
+    /// Returns true if a sequence or mapping has no element.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name of this node within its parent mapping, or an empty
+    /// string.
+    pub fn name(&self) -> String {
+        take_c_string(unsafe { cv_file_node_name(self.inner) }).unwrap_or_default()
+    }
+
+    /// Returns the names of the elements of a mapping, in file order.
+    pub fn keys(&self) -> Vec<String> {
+        if self.node_type() != FileNodeType::Map {
+            return Vec::new();
+        }
+        self.iter().map(|node| node.name()).collect()
+    }
+
+    /// Returns the element named `name` of a mapping.
+    pub fn get(&self, name: &str) -> Option<FileNode<'a>> {
+        let s = CString::new(name).ok()?;
+        FileNode::from_raw(unsafe { cv_file_node_get(self.inner, s.as_ptr()) })
+    }
+
+    /// Returns the `index`-th element of a sequence or mapping.
+    pub fn at(&self, index: usize) -> Option<FileNode<'a>> {
+        FileNode::from_raw(unsafe { cv_file_node_at(self.inner, index) })
+    }
+
+    /// Iterates over the elements of a sequence or mapping.
+    pub fn iter<'n>(&'n self) -> FileNodeIter<'n, 'a> {
+        FileNodeIter {
+            inner: unsafe { cv_file_node_iterator_new(self.inner) },
+            node: PhantomData,
+        }
+    }
+
+    /// Reads the value held by this node.
+    pub fn read<T: Storable>(&self) -> Result<T> {
+        T::read_from(self)
+    }
+
+    /// Reads the element named `name` of a mapping.
+    pub fn read_key<T: Storable>(&self, name: &str) -> Result<T> {
+        match self.get(name) {
+            Some(node) => node.read(),
+            None => Err(ErrorKind::MissingKey(name.to_owned()).into()),
+        }
+    }
+
+    /// Reads the element named `name` of a mapping, or returns `default` if
+    /// there is no such element.
+    pub fn read_key_or<T: Storable>(&self, name: &str, default: T) -> Result<T> {
+        match self.get(name) {
+            Some(node) => node.read(),
+            None => Ok(default),
+        }
+    }
+
+    fn expect(&self, node_type: FileNodeType, expected: &'static str) -> Result<()> {
+        if self.node_type() == node_type {
+            Ok(())
+        } else {
+            Err(ErrorKind::UnexpectedNode(expected).into())
+        }
+    }
+}
+
+impl<'a> Drop for FileNode<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            cv_file_node_drop(self.inner);
+        }
+    }
+}
+
+/// Iterator over the elements of a [FileNode](struct.FileNode.html).
+#[derive(Debug)]
+pub struct FileNodeIter<'n, 'a: 'n> {
+    // Null for nodes that are neither sequences nor mappings
+    inner: *mut CFileNodeIterator,
+    node: PhantomData<&'n FileNode<'a>>,
+}
+
+impl<'n, 'a> Iterator for FileNodeIter<'n, 'a> {
+    type Item = FileNode<'a>;
+
+    fn next(&mut self) -> Option<FileNode<'a>> {
+        if self.inner.is_null() {
+            return None;
+        }
+        FileNode::from_raw(unsafe { cv_file_node_iterator_next(self.inner) })
+    }
+}
+
+impl<'n, 'a> Drop for FileNodeIter<'n, 'a> {
+    fn drop(&mut self) {
+        unsafe {
+            cv_file_node_iterator_drop(self.inner);
+        }
+    }
+}
+
+/// Values that can be written to and read from a
+/// [FileStorage](struct.FileStorage.html).
+pub trait Storable: Sized {
+    /// Writes the value under `name`; `name` is empty inside sequences.
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()>;
+
+    /// Reads the value from a node.
+    fn read_from(node: &FileNode) -> Result<Self>;
+}
+
+impl Storable for i32 {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_int(name, *self)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let mut value = 0;
+        if unsafe { cv_file_node_read_int(node.inner, &mut value) } {
+            Ok(value)
+        } else {
+            Err(ErrorKind::UnexpectedNode("number").into())
+        }
+    }
+}
+
+impl Storable for u8 {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_int(name, i32::from(*self))
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let value = i32::read_from(node)?;
+        if value < 0 || value > 255 {
+            return Err(ErrorKind::NumFromPrimitive(i64::from(value)).into());
+        }
+        Ok(value as u8)
+    }
+}
+
+impl Storable for f64 {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_double(name, *self)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let mut value = 0.0;
+        if unsafe { cv_file_node_read_double(node.inner, &mut value) } {
+            Ok(value)
+        } else {
+            Err(ErrorKind::UnexpectedNode("number").into())
+        }
+    }
+}
+
+impl Storable for f32 {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_double(name, f64::from(*self))
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        f64::read_from(node).map(|v| v as f32)
+    }
+}
+
+/// Booleans are stored as 0 or 1, like in OpenCV.
+impl Storable for bool {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_int(name, *self as i32)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        i32::read_from(node).map(|v| v != 0)
+    }
+}
+
+impl Storable for String {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_string(name, self)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        take_c_string(unsafe { cv_file_node_read_string(node.inner) })
+            .ok_or_else(|| ErrorKind::UnexpectedNode("string").into())
+    }
+}
+
+/// Matrices use OpenCV's `opencv-matrix` layout.
+impl Storable for Mat {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.write_mat(name, self)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let mat = Mat::new();
+        if unsafe { cv_file_node_read_mat(node.inner, mat.inner) } {
+            Ok(mat)
+        } else {
+            Err(ErrorKind::UnexpectedNode("matrix").into())
+        }
+    }
+}
+
+impl<T: Storable> Storable for Vec<T> {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.start_seq(name)?;
+        for item in self {
+            fs.push(item)?;
+        }
+        fs.end_struct()
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        node.expect(FileNodeType::Seq, "sequence")?;
+        node.iter().map(|item| item.read()).collect()
+    }
+}
+
+fn write_flow<T: Storable>(fs: &mut FileStorage, name: &str, values: &[T]) -> Result<()> {
+    fs.start_flow_seq(name)?;
+    for value in values {
+        fs.push(value)?;
+    }
+    fs.end_struct()
+}
+
+fn read_flow<T: Storable>(node: &FileNode, len: usize) -> Result<Vec<T>> {
+    node.expect(FileNodeType::Seq, "sequence")?;
+    if node.len() != len {
+        return Err(ErrorKind::UnexpectedNode("sequence of a different length").into());
+    }
+    node.iter().map(|item| item.read()).collect()
+}
+
+/// Stored as `[ x, y ]`.
+impl<T: Storable + Copy> Storable for Point<T> {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        write_flow(fs, name, &[self.x, self.y])
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let v = read_flow(node, 2)?;
+        Ok(Point::new(v[0], v[1]))
+    }
+}
+
+/// Stored as `[ x, y, z ]`.
+impl<T: Storable + Copy> Storable for Point3<T> {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        write_flow(fs, name, &[self.x, self.y, self.z])
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let v = read_flow(node, 3)?;
+        Ok(Point3::new(v[0], v[1], v[2]))
+    }
+}
+
+/// Stored as `[ width, height ]`.
+impl<T: Storable + Copy> Storable for Size<T> {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        write_flow(fs, name, &[self.width, self.height])
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let v = read_flow(node, 2)?;
+        Ok(Size::new(v[0], v[1]))
+    }
+}
+
+/// Stored as `[ x, y, width, height ]`.
+impl<T: Storable + Copy> Storable for Rect<T> {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        write_flow(fs, name, &[self.x, self.y, self.width, self.height])
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let v = read_flow(node, 4)?;
+        Ok(Rect::new(v[0], v[1], v[2], v[3]))
+    }
+}
+
+/// Stored as `[ center.x, center.y, width, height, angle ]`.
+impl Storable for RotatedRect {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        let values = [
+            self.center.x,
+            self.center.y,
+            self.size.width,
+            self.size.height,
+            self.angle,
+        ];
+        write_flow(fs, name, &values)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let v = read_flow::<f32>(node, 5)?;
+        Ok(RotatedRect::new(
+            Point2f::new(v[0], v[1]),
+            Size2f::new(v[2], v[3]),
+            v[4],
+        ))
+    }
+}
+
+/// Stored as `[ v0, v1, v2, v3 ]`.
+impl Storable for Scalar {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        let values: [f64; 4] = (*self).into();
+        write_flow(fs, name, &values)
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        let v = read_flow(node, 4)?;
+        Ok(Scalar::new(v[0], v[1], v[2], v[3]))
+    }
+}
+
+macro_rules! storable_vec {
+    ($name:ident, $n:expr) => {
+        impl<T: Storable + Copy + Default> Storable for $name<T> {
+            fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+                write_flow(fs, name, &self.0)
+            }
+
+            fn read_from(node: &FileNode) -> Result<Self> {
+                let mut result = $name::<T>::default();
+                result.0.copy_from_slice(&read_flow(node, $n)?);
+                Ok(result)
+            }
+        }
+    };
+}
+
+storable_vec!(Vec2, 2);
+storable_vec!(Vec3, 3);
+storable_vec!(Vec4, 4);
+
+/// Stored as a mapping with `epsilon` and `iterations`, the layout used by
+/// OpenCV's ml models. Keys are only present for the active criteria.
+impl Storable for TermCriteria {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        let t = self.term_type();
+        fs.start_map(name)?;
+        if t != TermType::Count {
+            fs.write("epsilon", &self.epsilon())?;
+        }
+        if t != TermType::EPS {
+            fs.write("iterations", &self.max_count())?;
+        }
+        fs.end_struct()
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        node.expect(FileNodeType::Map, "mapping")?;
+        let epsilon = node.read_key_or("epsilon", 0.0)?;
+        let max_count = node.read_key_or("iterations", 0)?;
+        let t = match (epsilon > 0.0, max_count > 0) {
+            (true, true) => TermType::CountEps,
+            (true, false) => TermType::EPS,
+            (false, true) => TermType::Count,
+            (false, false) => return Err(ErrorKind::UnexpectedNode("epsilon or iterations").into()),
+        };
+        Ok(TermCriteria::new(t, max_count, epsilon))
+    }
+}
+
+/// Stored as a mapping that `cv::HOGDescriptor::read` understands. The
+/// detection parameters are stored as extra keys, and keys missing from the
+/// file keep their default value.
+impl Storable for HogParams {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.start_map(name)?;
+        fs.write("winSize", &self.win_size)?;
+        fs.write("blockSize", &self.block_size)?;
+        fs.write("blockStride", &self.block_stride)?;
+        fs.write("cellSize", &self.cell_size)?;
+        fs.write("nbins", &self.nbins)?;
+        fs.write("derivAperture", &1)?;
+        fs.write("winSigma", &self.win_sigma)?;
+        fs.write("histogramNormType", &0)?;
+        fs.write("L2HysThreshold", &self.l2hys_threshold)?;
+        fs.write("gammaCorrection", &self.gamma_correction)?;
+        fs.write("nlevels", &(self.nlevels as i32))?;
+        fs.write("signedGradient", &false)?;
+
+        fs.write("hitThreshold", &self.hit_threshold)?;
+        fs.write("winStride", &self.win_stride)?;
+        fs.write("padding", &self.padding)?;
+        fs.write("scale", &self.scale)?;
+        fs.write("groupThreshold", &self.group_threshold)?;
+        fs.write("useMeanshiftGrouping", &self.use_meanshift_grouping)?;
+        fs.write("finalThreshold", &self.final_threshold)?;
+        fs.end_struct()
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        node.expect(FileNodeType::Map, "mapping")?;
+        let d = HogParams::default();
+        let nlevels = node.read_key_or("nlevels", d.nlevels as i32)?;
+        if nlevels < 0 {
+            return Err(ErrorKind::UnexpectedNode("non-negative nlevels").into());
+        }
+        Ok(HogParams {
+            win_size: node.read_key_or("winSize", d.win_size)?,
+            block_size: node.read_key_or("blockSize", d.block_size)?,
+            block_stride: node.read_key_or("blockStride", d.block_stride)?,
+            cell_size: node.read_key_or("cellSize", d.cell_size)?,
+            nbins: node.read_key_or("nbins", d.nbins)?,
+            win_sigma: node.read_key_or("winSigma", d.win_sigma)?,
+            l2hys_threshold: node.read_key_or("L2HysThreshold", d.l2hys_threshold)?,
+            gamma_correction: node.read_key_or("gammaCorrection", d.gamma_correction)?,
+            nlevels: nlevels as usize,
+
+            hit_threshold: node.read_key_or("hitThreshold", d.hit_threshold)?,
+            win_stride: node.read_key_or("winStride", d.win_stride)?,
+            padding: node.read_key_or("padding", d.padding)?,
+            scale: node.read_key_or("scale", d.scale)?,
+            group_threshold: node.read_key_or("groupThreshold", d.group_threshold)?,
+            use_meanshift_grouping: node
+                .read_key_or("useMeanshiftGrouping", d.use_meanshift_grouping)?,
+            final_threshold: node.read_key_or("finalThreshold", d.final_threshold)?,
+        })
+    }
+}
+
+/// Intrinsic parameters of a calibrated camera.
+#[derive(Debug, Clone)]
+pub struct CameraIntrinsics {
+    /// 3x3 camera matrix.
+    pub camera_matrix: Mat,
+    /// Distortion coefficients `(k1, k2, p1, p2[, k3[, k4, k5, k6]])`.
+    pub dist_coeffs: Mat,
+    /// Size of the images used for calibration.
+    pub image_size: Size2i,
+}
+
+impl CameraIntrinsics {
+    /// Saves the parameters as top-level keys, like OpenCV's calibration
+    /// sample does.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut fs = FileStorage::open(path, FileStorageMode::Write)?;
+        self.write_entries(&mut fs)?;
+        fs.release()
+    }
+
+    /// Loads parameters saved by [save](#method.save) or by OpenCV's
+    /// calibration sample.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<CameraIntrinsics> {
+        let fs = FileStorage::open(path, FileStorageMode::Read)?;
+        let intrinsics = match fs.root() {
+            Some(root) => root.read(),
+            None => Err(ErrorKind::UnexpectedNode("mapping").into()),
+        };
+        intrinsics
+    }
+
+    fn write_entries(&self, fs: &mut FileStorage) -> Result<()> {
+        fs.write("image_width", &self.image_size.width)?;
+        fs.write("image_height", &self.image_size.height)?;
+        fs.write("camera_matrix", &self.camera_matrix)?;
+        fs.write("distortion_coefficients", &self.dist_coeffs)
+    }
+}
+
+/// Stored as a mapping with `image_width`, `image_height`, `camera_matrix` and
+/// `distortion_coefficients`.
+impl Storable for CameraIntrinsics {
+    fn write_to(&self, fs: &mut FileStorage, name: &str) -> Result<()> {
+        fs.start_map(name)?;
+        self.write_entries(fs)?;
+        fs.end_struct()
+    }
+
+    fn read_from(node: &FileNode) -> Result<Self> {
+        node.expect(FileNodeType::Map, "mapping")?;
+        Ok(CameraIntrinsics {
+            camera_matrix: node.read_key("camera_matrix")?,
+            dist_coeffs: node.read_key("distortion_coefficients")?,
+            image_size: Size2i::new(
+                node.read_key("image_width")?,
+                node.read_key("image_height")?,
+            ),
+        })
+    }
+}
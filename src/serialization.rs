@@ -0,0 +1,167 @@
+//! `serde` support for the types that can't simply derive it.
+//!
+//! `MatType` is validated when deserialized, and `Mat` is serialized as its
+//! shape, its type and its raw bytes in row-major order.
+
+use super::core::{Mat, MatDepth, MatType};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+use std::slice;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "MatType")]
+struct MatTypeRepr {
+    depth: MatDepth,
+    channels: u16,
+}
+
+impl Serialize for MatType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MatTypeRepr {
+            depth: self.depth(),
+            channels: self.channels(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MatType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = MatTypeRepr::deserialize(deserializer)?;
+        MatType::new(repr.depth, repr.channels).map_err(de::Error::custom)
+    }
+}
+
+/// Raw bytes that serialize compactly in binary formats.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteBufVisitor;
+
+        impl<'de> Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(b) = seq.next_element()? {
+                    bytes.push(b);
+                }
+                Ok(ByteBuf(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+/// Serialized as `{ sizes, mat_type, data }`. Non-continuous matrices are
+/// copied first so that `data` never contains padding.
+impl Serialize for Mat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let continuous;
+        let mat = if self.is_continuous() {
+            self
+        } else {
+            continuous = self.clone();
+            &continuous
+        };
+        let len = mat.total() * mat.elem_size();
+        let data = if len == 0 {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(mat.data(), len) }
+        };
+
+        let mut state = serializer.serialize_struct("Mat", 3)?;
+        state.serialize_field("sizes", &mat.sizes())?;
+        state.serialize_field("mat_type", &mat.mat_type())?;
+        state.serialize_field("data", &Bytes(data))?;
+        state.end()
+    }
+}
+
+/// Returns the size in bytes of a channel of the given depth, unless it's
+/// user-defined.
+fn depth_size(depth: MatDepth) -> Option<usize> {
+    match depth {
+        MatDepth::Unsigned8 | MatDepth::Signed8 => Some(1),
+        MatDepth::Unsigned16 | MatDepth::Signed16 => Some(2),
+        MatDepth::Signed32 | MatDepth::Float32 => Some(4),
+        MatDepth::Float64 => Some(8),
+        MatDepth::UserDefined => None,
+    }
+}
+
+impl<'de> Deserialize<'de> for Mat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Mat")]
+        struct MatRepr {
+            sizes: Vec<i32>,
+            mat_type: MatType,
+            data: ByteBuf,
+        }
+
+        let repr = MatRepr::deserialize(deserializer)?;
+        if repr.sizes.is_empty() {
+            if !repr.data.0.is_empty() {
+                return Err(de::Error::invalid_length(
+                    repr.data.0.len(),
+                    &"no data for an empty matrix",
+                ));
+            }
+            return Ok(Mat::new());
+        }
+
+        // Check the data before allocating, the sizes may be arbitrarily large
+        let depth_size = depth_size(repr.mat_type.depth()).ok_or_else(|| {
+            de::Error::custom("matrices of user-defined depth can't be deserialized")
+        })?;
+        let elem_size = depth_size * repr.mat_type.channels() as usize;
+        let len = repr
+            .sizes
+            .iter()
+            .try_fold(elem_size, |len, &size| {
+                if size < 0 {
+                    None
+                } else {
+                    len.checked_mul(size as usize)
+                }
+            })
+            .ok_or_else(|| de::Error::custom("invalid matrix sizes"))?;
+        if repr.data.0.len() != len {
+            return Err(de::Error::invalid_length(
+                repr.data.0.len(),
+                &"data matching the matrix size",
+            ));
+        }
+
+        let mat = Mat::new_nd(&repr.sizes, repr.mat_type).map_err(de::Error::custom)?;
+        unsafe {
+            ::std::ptr::copy_nonoverlapping(repr.data.0.as_ptr(), mat.data() as *mut u8, len)
+        };
+        Ok(mat)
+    }
+}
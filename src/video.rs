@@ -10,7 +10,7 @@ pub mod tracking {
     //   VideoTrack
     // =========================================================================
 
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive)]
     /// Term criteria type, can be one of: Count, Eps or Count + Eps
     pub enum TermType {
         /// The maximum number of iterations or elements to compute
@@ -19,12 +19,15 @@ pub mod tracking {
         /// the desired accuracy or change in parameters at which the iterative
         /// algorithm stops.
         EPS = 2,
+
+        /// Stops as soon as either `Count` or `EPS` is reached.
+        CountEps = 3,
     }
 
     /// Termination criteria for iterative algorithms.
     #[derive(Debug)]
     pub struct TermCriteria {
-        c_criteria: *mut CTermCriteria,
+        pub(crate) c_criteria: *mut CTermCriteria,
     }
 
     impl TermCriteria {
@@ -35,6 +38,22 @@ pub mod tracking {
                 c_criteria: c_criteria,
             }
         }
+
+        /// Returns the type of the termination criteria.
+        pub fn term_type(&self) -> TermType {
+            let t = unsafe { cv_term_criteria_type(self.c_criteria) };
+            ::num::FromPrimitive::from_i32(t).unwrap_or(TermType::CountEps)
+        }
+
+        /// Returns the maximum number of iterations or elements to compute.
+        pub fn max_count(&self) -> i32 {
+            unsafe { cv_term_criteria_max_count(self.c_criteria) }
+        }
+
+        /// Returns the desired accuracy.
+        pub fn epsilon(&self) -> f64 {
+            unsafe { cv_term_criteria_epsilon(self.c_criteria) }
+        }
     }
 
     impl Drop for TermCriteria {
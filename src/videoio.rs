@@ -2,7 +2,10 @@
 //! videoio](http://docs.opencv.org/3.1.0/dd/de7/group__videoio.html)
 
 use core::Mat;
+use errors::*;
 use libc::{c_char, c_int};
+use std::path::Path;
+use std::time::Duration;
 
 use super::wrapper::*;
 
@@ -21,6 +24,7 @@ unsafe impl Send for VideoCapture {}
 #[allow(missing_docs)]
 /// Video capture's property identifier.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CapProp {
     /// Current position of the video file in milliseconds or video capture
     /// timestamp.
@@ -114,12 +118,30 @@ impl VideoCapture {
     /// Creates a capture device with the path of a video file (eg. video.avi).
     /// This also supports image sequence, eg. img_%02d.jpg, which will read
     /// samples like img_00.jpg, img_01.jpg, img_02.jpg, ...).
+    ///
+    /// The capture may not be open, see `VideoCapture::open` for a fallible
+    /// version.
     pub fn from_path(path: &str) -> Self {
         let s = ::std::ffi::CString::new(path).unwrap();
         let cap = unsafe { cv_videocapture_from_file((&s).as_ptr()) };
         VideoCapture { inner: cap }
     }
 
+    /// Opens a video file, an image sequence or a URL like `from_path` does.
+    ///
+    /// Fails with `VideoOpen` if no backend can open it.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let s = path_to_cstring(&path)?;
+        let cap = VideoCapture {
+            inner: unsafe { cv_videocapture_from_file(s.as_ptr()) },
+        };
+        if cap.is_open() {
+            Ok(cap)
+        } else {
+            Err(ErrorKind::VideoOpen(path.as_ref().to_path_buf()).into())
+        }
+    }
+
     /// Returns true if video capturing has been initialized already.
     pub fn is_open(&self) -> bool {
         unsafe { cv_videocapture_is_opened(self.inner) }
@@ -133,12 +155,105 @@ impl VideoCapture {
     /// If no frames has been grabbed (camera has been disconnected, or there
     /// are no more frames in video file), the methods return `None`.
     pub fn read(&self) -> Option<Mat> {
-        let inner = CMat::new();
-        let status = unsafe { cv_videocapture_read(self.inner, inner) };
-        if status {
-            Some(Mat::from_raw(inner))
+        let mut mat = Mat::new();
+        match self.read_into(&mut mat) {
+            Ok(true) => Some(mat),
+            _ => None,
+        }
+    }
+
+    /// Grabs, decodes and stores the next video frame into `mat`, reusing its
+    /// buffer when the frame has the same size and type.
+    ///
+    /// Returns false, leaving `mat` empty, if no frame has been grabbed.
+    /// Fails with `Operation("VideoCapture::read")` if decoding fails.
+    pub fn read_into(&self, mat: &mut Mat) -> Result<bool> {
+        let mut grabbed = false;
+        if unsafe { cv_videocapture_read(self.inner, mat.inner, &mut grabbed) } {
+            Ok(grabbed)
         } else {
-            None
+            Err(ErrorKind::Operation("VideoCapture::read").into())
+        }
+    }
+
+    /// Grabs the next frame without decoding it, so that several captures can
+    /// be latched at nearly the same instant and decoded afterwards with
+    /// `VideoCapture::retrieve`.
+    ///
+    /// Returns false if no frame has been grabbed. Fails with
+    /// `Operation("VideoCapture::grab")` if grabbing fails.
+    pub fn grab(&self) -> Result<bool> {
+        let mut grabbed = false;
+        if unsafe { cv_videocapture_grab(self.inner, &mut grabbed) } {
+            Ok(grabbed)
+        } else {
+            Err(ErrorKind::Operation("VideoCapture::grab").into())
+        }
+    }
+
+    /// Decodes and returns the just grabbed frame. `channel` selects one of the
+    /// images some devices, like depth sensors, return, and is 0 otherwise.
+    ///
+    /// Returns `None` if no frame has been grabbed. Fails with
+    /// `Operation("VideoCapture::retrieve")` if decoding fails.
+    pub fn retrieve(&self, channel: i32) -> Result<Option<Mat>> {
+        let mat = Mat::new();
+        let mut retrieved = false;
+        if !unsafe { cv_videocapture_retrieve(self.inner, mat.inner, channel, &mut retrieved) } {
+            Err(ErrorKind::Operation("VideoCapture::retrieve").into())
+        } else if retrieved {
+            Ok(Some(mat))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Waits until at least one of `captures` has a frame ready to be
+    /// grabbed, or until `timeout` elapses, and returns the indices of the
+    /// ready captures, which is empty on timeout. `None` waits indefinitely.
+    ///
+    /// Only camera backends support waiting, such as V4L; others fail with
+    /// `Operation("VideoCapture::wait_any")`. Fails with
+    /// `UnsupportedOperation` before OpenCV 4.1.
+    pub fn wait_any(captures: &[&VideoCapture], timeout: Option<Duration>) -> Result<Vec<usize>> {
+        if !unsafe { cv_videocapture_have_wait_any() } {
+            return Err(ErrorKind::UnsupportedOperation("VideoCapture::wait_any").into());
+        }
+        // OpenCV waits indefinitely for a zero timeout.
+        let timeout_ns = match timeout {
+            Some(t) => (t.as_secs() as i64)
+                .saturating_mul(1_000_000_000)
+                .saturating_add(i64::from(t.subsec_nanos()))
+                .max(1),
+            None => 0,
+        };
+        let inners = captures.iter().map(|cap| cap.inner).collect::<Vec<_>>();
+        let mut ready = vec![false; captures.len()];
+        if unsafe {
+            cv_videocapture_wait_any(
+                inners.as_ptr(),
+                inners.len(),
+                timeout_ns,
+                ready.as_mut_ptr(),
+            )
+        } {
+            Ok(ready
+                .iter()
+                .enumerate()
+                .filter(|&(_, r)| *r)
+                .map(|(i, _)| i)
+                .collect())
+        } else {
+            Err(ErrorKind::Operation("VideoCapture::wait_any").into())
+        }
+    }
+
+    /// Returns an iterator over the remaining frames, which ends once no
+    /// frame can be grabbed, or after the first error.
+    pub fn frames(&self) -> Frames<'_> {
+        Frames {
+            cap: self,
+            done: false,
         }
     }
 
@@ -166,6 +281,105 @@ impl Drop for VideoCapture {
     }
 }
 
+/// An iterator over the frames of a `VideoCapture`, see
+/// `VideoCapture::frames`.
+#[derive(Debug)]
+pub struct Frames<'a> {
+    cap: &'a VideoCapture,
+    done: bool,
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Result<Mat>;
+
+    fn next(&mut self) -> Option<Result<Mat>> {
+        if self.done {
+            return None;
+        }
+        let mut mat = Mat::new();
+        match self.cap.read_into(&mut mat) {
+            Ok(true) => Some(Ok(mat)),
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// A frame read by a `CaptureGroup`.
+#[derive(Debug)]
+pub struct TimedFrame {
+    /// The decoded frame.
+    pub frame: Mat,
+
+    /// Position of the frame in milliseconds, see `CapProp::PosMsec`; cameras
+    /// report their capture timestamp.
+    pub timestamp_ms: f64,
+}
+
+/// Reads from several captures, like the cameras of a stereo rig, at nearly
+/// the same instant: every capture is grabbed before any frame is decoded.
+#[derive(Debug)]
+pub struct CaptureGroup {
+    captures: Vec<VideoCapture>,
+}
+
+impl CaptureGroup {
+    /// Creates a group reading from `captures`, in order.
+    pub fn new(captures: Vec<VideoCapture>) -> Self {
+        CaptureGroup { captures }
+    }
+
+    /// Opens every path with `VideoCapture::open`.
+    pub fn open<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let captures = paths
+            .iter()
+            .map(VideoCapture::open)
+            .collect::<Result<_>>()?;
+        Ok(CaptureGroup::new(captures))
+    }
+
+    /// Returns the captures, in order.
+    pub fn captures(&self) -> &[VideoCapture] {
+        &self.captures
+    }
+
+    /// Consumes the group and returns its captures.
+    pub fn into_captures(self) -> Vec<VideoCapture> {
+        self.captures
+    }
+
+    /// Grabs a frame from every capture, then decodes them and returns them
+    /// in the order of the captures.
+    ///
+    /// Returns `None` once a capture has no frame left.
+    pub fn read(&self) -> Result<Option<Vec<TimedFrame>>> {
+        for cap in &self.captures {
+            if !cap.grab()? {
+                return Ok(None);
+            }
+        }
+
+        let mut frames = Vec::with_capacity(self.captures.len());
+        for cap in &self.captures {
+            let timestamp_ms = cap.get(CapProp::PosMsec).unwrap_or(0.0);
+            match cap.retrieve(0)? {
+                Some(frame) => frames.push(TimedFrame {
+                    frame,
+                    timestamp_ms,
+                }),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(frames))
+    }
+}
+
 // =============================================================================
 //   VideoWriter
 // =============================================================================
@@ -273,6 +487,7 @@ impl Drop for VideoWriter {
 
 /// `VideoWriter`'s property identifier.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum VideoWriterProperty {
     /// Current quality of the encoded videostream.
     Quality = 1,
@@ -5,6 +5,7 @@ extern "C" {
     pub fn cv_mat_new() -> *mut CMat;
     pub fn cv_mat_new_with_size(rows: c_int, cols: c_int, mat_type: c_int) -> *mut CMat;
     pub fn cv_mat_zeros(rows: c_int, cols: c_int, mat_type: c_int) -> *mut CMat;
+    pub fn cv_mat_new_nd(ndims: c_int, sizes: *const c_int, mat_type: c_int) -> *mut CMat;
     pub fn cv_mat_is_valid(mat: *mut CMat) -> bool;
     pub fn cv_mat_rows(cmat: *const CMat) -> c_int;
     pub fn cv_mat_cols(cmat: *const CMat) -> c_int;
@@ -12,7 +13,37 @@ extern "C" {
     pub fn cv_mat_data(cmat: *const CMat) -> *const c_uchar;
     pub fn cv_mat_total(cmat: *const CMat) -> size_t;
     pub fn cv_mat_elem_size(cmat: *const CMat) -> size_t;
+    pub fn cv_mat_dims(cmat: *const CMat) -> c_int;
+    pub fn cv_mat_sizes(cmat: *const CMat, sizes: *mut c_int);
+    pub fn cv_mat_steps(cmat: *const CMat, steps: *mut size_t);
+    pub fn cv_mat_is_continuous(cmat: *const CMat) -> bool;
     pub fn cv_mat_roi(cmat: *const CMat, rect: Rect) -> *mut CMat;
+    pub fn cv_mat_clone(cmat: *const CMat) -> *mut CMat;
+    pub fn cv_mat_copy_to(src: *const CMat, dst: *mut CMat, mask: *const CMat) -> bool;
+    pub fn cv_mat_convert_to(
+        src: *const CMat,
+        dst: *mut CMat,
+        rtype: c_int,
+        alpha: c_double,
+        beta: c_double,
+    ) -> bool;
+    pub fn cv_mat_set_to(cmat: *mut CMat, value: Scalar, mask: *const CMat) -> bool;
+    pub fn cv_mat_reshape(cmat: *const CMat, cn: c_int, rows: c_int) -> *mut CMat;
+    pub fn cv_mat_row_range(cmat: *const CMat, start: c_int, end: c_int) -> *mut CMat;
+    pub fn cv_mat_col_range(cmat: *const CMat, start: c_int, end: c_int) -> *mut CMat;
+    pub fn cv_hconcat(srcs: *const *const CMat, len: size_t, dst: *mut CMat) -> bool;
+    pub fn cv_vconcat(srcs: *const *const CMat, len: size_t, dst: *mut CMat) -> bool;
+    pub fn cv_repeat(src: *const CMat, ny: c_int, nx: c_int, dst: *mut CMat) -> bool;
+    pub fn cv_copy_make_border(
+        src: *const CMat,
+        dst: *mut CMat,
+        top: c_int,
+        bottom: c_int,
+        left: c_int,
+        right: c_int,
+        border_type: c_int,
+        value: Scalar,
+    ) -> bool;
     pub fn cv_mat_logic_and(cimage: *mut CMat, cmask: *const CMat);
     pub fn cv_mat_flip(src: *mut CMat, code: c_int);
     pub fn cv_mat_drop(mat: *mut CMat);
@@ -39,6 +70,123 @@ extern "C" {
     pub fn cv_bitwise_xor(src1: *const CMat, src2: *const CMat, dst: *mut CMat);
     pub fn cv_count_non_zero(src: *const CMat) -> i32;
 
+    pub fn cv_kmeans(
+        data: *const CMat,
+        k: c_int,
+        labels: *mut CMat,
+        criteria: *const CTermCriteria,
+        attempts: c_int,
+        flags: c_int,
+        centers: *mut CMat,
+        compactness: *mut c_double,
+    ) -> bool;
+    pub fn cv_pca_new(data: *const CMat, flags: c_int, max_components: c_int) -> *mut CPca;
+    pub fn cv_pca_new_with_retained_variance(
+        data: *const CMat,
+        flags: c_int,
+        retained_variance: c_double,
+    ) -> *mut CPca;
+    pub fn cv_pca_drop(pca: *mut CPca);
+    pub fn cv_pca_project(pca: *const CPca, vec: *const CMat, result: *mut CMat) -> bool;
+    pub fn cv_pca_back_project(pca: *const CPca, vec: *const CMat, result: *mut CMat) -> bool;
+    pub fn cv_pca_eigenvectors(pca: *const CPca) -> *mut CMat;
+    pub fn cv_pca_eigenvalues(pca: *const CPca) -> *mut CMat;
+    pub fn cv_pca_mean(pca: *const CPca) -> *mut CMat;
+
+    pub fn cv_gemm(
+        src1: *const CMat,
+        src2: *const CMat,
+        alpha: c_double,
+        src3: *const CMat,
+        beta: c_double,
+        dst: *mut CMat,
+        flags: c_int,
+    ) -> bool;
+    pub fn cv_transpose(src: *const CMat, dst: *mut CMat) -> bool;
+    pub fn cv_invert(
+        src: *const CMat,
+        dst: *mut CMat,
+        method: c_int,
+        result: *mut c_double,
+    ) -> bool;
+    pub fn cv_solve(
+        src1: *const CMat,
+        src2: *const CMat,
+        dst: *mut CMat,
+        method: c_int,
+        solved: *mut bool,
+    ) -> bool;
+    pub fn cv_determinant(src: *const CMat, determinant: *mut c_double) -> bool;
+    pub fn cv_trace(src: *const CMat, trace: *mut c_double) -> bool;
+    pub fn cv_svd_compute(
+        src: *const CMat,
+        w: *mut CMat,
+        u: *mut CMat,
+        vt: *mut CMat,
+        flags: c_int,
+    ) -> bool;
+    pub fn cv_eigen(src: *const CMat, eigenvalues: *mut CMat, eigenvectors: *mut CMat) -> bool;
+    pub fn cv_mul_transposed(
+        src: *const CMat,
+        dst: *mut CMat,
+        a_top: bool,
+        delta: *const CMat,
+        scale: c_double,
+    ) -> bool;
+
+    pub fn cv_dft(src: *const CMat, dst: *mut CMat, flags: c_int, nonzero_rows: c_int) -> bool;
+    pub fn cv_idft(src: *const CMat, dst: *mut CMat, flags: c_int, nonzero_rows: c_int) -> bool;
+    pub fn cv_dct(src: *const CMat, dst: *mut CMat, flags: c_int) -> bool;
+    pub fn cv_idct(src: *const CMat, dst: *mut CMat, flags: c_int) -> bool;
+    pub fn cv_get_optimal_dft_size(vecsize: c_int) -> c_int;
+    pub fn cv_mul_spectrums(
+        a: *const CMat,
+        b: *const CMat,
+        dst: *mut CMat,
+        flags: c_int,
+        conj_b: bool,
+    ) -> bool;
+
+    pub fn cv_mean(src: *const CMat, mask: *const CMat, mean: *mut Scalar) -> bool;
+    pub fn cv_mean_std_dev(
+        src: *const CMat,
+        mask: *const CMat,
+        mean: *mut Scalar,
+        stddev: *mut Scalar,
+    ) -> bool;
+    pub fn cv_sum(src: *const CMat, sum: *mut Scalar) -> bool;
+    pub fn cv_min_max_loc(
+        src: *const CMat,
+        mask: *const CMat,
+        min_val: *mut c_double,
+        max_val: *mut c_double,
+        min_loc: *mut Point2i,
+        max_loc: *mut Point2i,
+    ) -> bool;
+    pub fn cv_min_max_idx(
+        src: *const CMat,
+        mask: *const CMat,
+        min_val: *mut c_double,
+        max_val: *mut c_double,
+        min_idx: *mut c_int,
+        max_idx: *mut c_int,
+    ) -> c_int;
+    pub fn cv_norm(
+        src: *const CMat,
+        norm_type: c_int,
+        mask: *const CMat,
+        norm: *mut c_double,
+    ) -> bool;
+    pub fn cv_norm_diff(
+        src1: *const CMat,
+        src2: *const CMat,
+        norm_type: c_int,
+        mask: *const CMat,
+        norm: *mut c_double,
+    ) -> bool;
+    pub fn cv_reduce(src: *const CMat, dim: c_int, rtype: c_int, result: *mut CVecDouble) -> bool;
+    pub fn cv_psnr(src1: *const CMat, src2: *const CMat, psnr: *mut c_double) -> bool;
+
     pub fn cv_named_window(name: *const c_char, flags: c_int);
     pub fn cv_destroy_window(name: *const c_char);
     pub fn cv_set_mouse_callback(
@@ -57,6 +205,28 @@ extern "C" {
         flag_ptr: *const c_int,
         flag_size: size_t,
     ) -> ImencodeResult;
+    pub fn cv_imencode_result_drop(result: ImencodeResult);
+    pub fn cv_imwrite(
+        filename: *const c_char,
+        inner: *const CMat,
+        params: *const c_int,
+        params_len: size_t,
+    ) -> bool;
+    pub fn cv_have_image_reader(filename: *const c_char) -> bool;
+    pub fn cv_have_image_writer(filename: *const c_char) -> bool;
+    pub fn cv_imreadmulti(filename: *const c_char, flags: c_int, pages: *mut CVecOfMat) -> bool;
+    pub fn cv_imcount(filename: *const c_char, flags: c_int) -> size_t;
+    pub fn cv_imwritemulti(
+        filename: *const c_char,
+        mats: *const *const CMat,
+        len: size_t,
+        params: *const c_int,
+        params_len: size_t,
+    ) -> bool;
+    pub fn cv_image_pages_new(filename: *const c_char, flags: c_int) -> *mut CImagePages;
+    pub fn cv_image_pages_drop(pages: *mut CImagePages);
+    pub fn cv_image_pages_len(pages: *const CImagePages) -> size_t;
+    pub fn cv_image_pages_read(pages: *mut CImagePages, index: size_t) -> *mut CMat;
 
     pub fn cv_rectangle(
         cmat: *mut CMat,
@@ -79,6 +249,14 @@ extern "C" {
         shift: c_int,
     );
 
+    pub fn cv_draw_rotated_rect(
+        cmat: *mut CMat,
+        rect: RotatedRect,
+        color: Scalar,
+        thickness: c_int,
+        linetype: c_int,
+    ) -> bool;
+
     pub fn cv_cvt_color(cmat: *const CMat, output: *mut CMat, code: i32);
     pub fn cv_pyr_down(cmat: *const CMat, output: *mut CMat);
     pub fn cv_resize(
@@ -107,10 +285,68 @@ extern "C" {
         cback_project: *mut CMat,
         ranges: *const *const c_float,
     );
+    pub fn cv_phase_correlate(
+        src1: *const CMat,
+        src2: *const CMat,
+        window: *const CMat,
+        shift: *mut Point2f,
+        response: *mut c_double,
+    ) -> bool;
+    pub fn cv_create_hanning_window(dst: *mut CMat, size: Size2i, mat_type: c_int) -> bool;
+    pub fn cv_rotated_rectangle_intersection(
+        rect1: RotatedRect,
+        rect2: RotatedRect,
+        vertices: *mut Point2f,
+        len: *mut size_t,
+    ) -> c_int;
 
     pub fn cv_hog_new() -> *mut CHogDescriptor;
+    pub fn cv_hog_new_with_params(
+        win_size: Size2i,
+        block_size: Size2i,
+        block_stride: Size2i,
+        cell_size: Size2i,
+        nbins: c_int,
+        win_sigma: c_double,
+        l2hys_threshold: c_double,
+        gamma_correction: bool,
+        nlevels: c_int,
+    ) -> *mut CHogDescriptor;
+    pub fn cv_hog_get_params(
+        hog: *const CHogDescriptor,
+        win_size: *mut Size2i,
+        block_size: *mut Size2i,
+        block_stride: *mut Size2i,
+        cell_size: *mut Size2i,
+        nbins: *mut c_int,
+        win_sigma: *mut c_double,
+        l2hys_threshold: *mut c_double,
+        gamma_correction: *mut bool,
+        nlevels: *mut c_int,
+    );
     pub fn cv_hog_drop(hog: *mut CHogDescriptor);
-    pub fn cv_hog_set_svm_detector(hog: *mut CHogDescriptor, svm: *mut CSvmDetector);
+    pub fn cv_hog_set_svm_detector(hog: *mut CHogDescriptor, svm: *mut CSvmDetector) -> bool;
+    pub fn cv_hog_get_descriptor_size(hog: *const CHogDescriptor) -> size_t;
+    pub fn cv_hog_check_detector_size(hog: *const CHogDescriptor) -> bool;
+    pub fn cv_hog_compute(
+        hog: *mut CHogDescriptor,
+        image: *mut CMat,
+        descriptors: *mut CVecFloat,
+        win_stride: Size2i,
+        padding: Size2i,
+        locations: *const Point2i,
+        num_locations: size_t,
+    ) -> bool;
+    pub fn cv_hog_compute_gradient(
+        hog: *mut CHogDescriptor,
+        image: *mut CMat,
+        grad: *mut CMat,
+        angle_ofs: *mut CMat,
+        padding_tl: Size2i,
+        padding_br: Size2i,
+    ) -> bool;
+    pub fn cv_hog_save(hog: *const CHogDescriptor, path: *const c_char) -> bool;
+    pub fn cv_hog_load(hog: *mut CHogDescriptor, path: *const c_char) -> bool;
     pub fn cv_hog_detect(
         hog: *mut CHogDescriptor,
         image: *mut CMat,
@@ -125,6 +361,9 @@ extern "C" {
 
     pub fn cv_term_criteria_new(t: i32, count: i32, epsilon: f64) -> *mut CTermCriteria;
     pub fn cv_term_criteria_drop(criteria: *mut CTermCriteria);
+    pub fn cv_term_criteria_type(criteria: *const CTermCriteria) -> i32;
+    pub fn cv_term_criteria_max_count(criteria: *const CTermCriteria) -> i32;
+    pub fn cv_term_criteria_epsilon(criteria: *const CTermCriteria) -> f64;
     pub fn cv_camshift(image: *mut CMat, w: Rect, c_criteria: *const CTermCriteria) -> RotatedRect;
 
     pub fn cv_videowriter_default() -> *mut CvVideoWriter;
@@ -0,0 +1,787 @@
+//! Generic geometric primitives, laid out like their OpenCV counterparts so
+//! they can be passed to C directly.
+
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+/// 2D points specified by its coordinates `x` and `y`, like OpenCV's
+/// `Point_`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Point<T> {
+    /// x coordinate
+    pub x: T,
+
+    /// y coordinate
+    pub y: T,
+}
+
+/// 2D integer points.
+pub type Point2i = Point<i32>;
+/// 2D floating points.
+pub type Point2f = Point<f32>;
+/// 2D double precision points.
+pub type Point2d = Point<f64>;
+
+impl<T> Point<T> {
+    /// Creates a new point.
+    pub fn new(x: T, y: T) -> Self {
+        Point { x: x, y: y }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    /// Returns the dot product of two points (as vectors).
+    pub fn dot(&self, other: Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z component of the cross product of two points (as
+    /// vectors).
+    pub fn cross(&self, other: Point<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Copy + Into<f64>> Point<T> {
+    /// Returns the euclidean distance between two points.
+    pub fn distance(&self, other: Point<T>) -> f64 {
+        let dx = self.x.into() - other.x.into();
+        let dy = self.y.into() - other.y.into();
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Returns the euclidean length of the point (as a vector).
+    pub fn norm(&self) -> f64 {
+        let x = self.x.into();
+        let y = self.y.into();
+        (x * x + y * y).sqrt()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, factor: T) -> Point<T> {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// 3D points specified by its coordinates `x`, `y` and `z`, like OpenCV's
+/// `Point3_`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Point3<T> {
+    /// x coordinate
+    pub x: T,
+
+    /// y coordinate
+    pub y: T,
+
+    /// z coordinate
+    pub z: T,
+}
+
+/// 3D integer points.
+pub type Point3i = Point3<i32>;
+/// 3D floating points.
+pub type Point3f = Point3<f32>;
+/// 3D double precision points.
+pub type Point3d = Point3<f64>;
+
+impl<T> Point3<T> {
+    /// Creates a new point.
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x: x, y: y, z: z }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point3<T> {
+    /// Returns the dot product of two points (as vectors).
+    pub fn dot(&self, other: Point3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product of two points (as vectors).
+    pub fn cross(&self, other: Point3<T>) -> Point3<T> {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T: Copy + Into<f64>> Point3<T> {
+    /// Returns the euclidean distance between two points.
+    pub fn distance(&self, other: Point3<T>) -> f64 {
+        let dx = self.x.into() - other.x.into();
+        let dy = self.y.into() - other.y.into();
+        let dz = self.z.into() - other.z.into();
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, factor: T) -> Point3<T> {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// The size (width and height) of an image or rectangle, like OpenCV's
+/// `Size_`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Size<T> {
+    /// The width.
+    pub width: T,
+
+    /// The height.
+    pub height: T,
+}
+
+/// Integral sizes.
+pub type Size2i = Size<i32>;
+/// Floating point sizes.
+pub type Size2f = Size<f32>;
+/// Double precision sizes.
+pub type Size2d = Size<f64>;
+
+impl<T> Size<T> {
+    /// Initializes a `Size` with the specified width and height.
+    pub fn new(width: T, height: T) -> Self {
+        Size {
+            width: width,
+            height: height,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Size<T> {
+    /// Returns the area (width * height).
+    pub fn area(&self) -> T {
+        self.width * self.height
+    }
+}
+
+impl<T: Copy + Default + PartialOrd> Size<T> {
+    /// Returns true if the size has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= T::default() || self.height <= T::default()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Size<T> {
+    type Output = Size<T>;
+
+    fn add(self, other: Size<T>) -> Size<T> {
+        Size::new(self.width + other.width, self.height + other.height)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Size<T> {
+    type Output = Size<T>;
+
+    fn sub(self, other: Size<T>) -> Size<T> {
+        Size::new(self.width - other.width, self.height - other.height)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Size<T> {
+    type Output = Size<T>;
+
+    fn mul(self, factor: T) -> Size<T> {
+        Size::new(self.width * factor, self.height * factor)
+    }
+}
+
+/// An up-right rectangle, like OpenCV's `Rect_`. `Rect` without a type
+/// parameter is the integral rectangle.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Rect<T = i32> {
+    /// x coordinate of the left-top corner
+    pub x: T,
+    /// y coordinate of the left-top corner
+    pub y: T,
+    /// width of this rectangle
+    pub width: T,
+    /// height of this rectangle
+    pub height: T,
+}
+
+/// Integral rectangles.
+pub type Rect2i = Rect<i32>;
+/// Floating point rectangles.
+pub type Rect2f = Rect<f32>;
+/// Double precision rectangles.
+pub type Rect2d = Rect<f64>;
+
+impl<T> Rect<T> {
+    /// Creates a new `Rect` with (x, y, width, height) parameters.
+    pub fn new(x: T, y: T, width: T, height: T) -> Self {
+        Rect {
+            x: x,
+            y: y,
+            width: width,
+            height: height,
+        }
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Creates the rectangle spanned by two opposite corners.
+    pub fn from_points(a: Point<T>, b: Point<T>) -> Self {
+        let (x0, x1) = if a.x <= b.x { (a.x, b.x) } else { (b.x, a.x) };
+        let (y0, y1) = if a.y <= b.y { (a.y, b.y) } else { (b.y, a.y) };
+        Rect::new(x0, y0, x1 - x0, y1 - y0)
+    }
+
+    /// Creates a rectangle from its top-left corner and size.
+    pub fn from_point_size(tl: Point<T>, size: Size<T>) -> Self {
+        Rect::new(tl.x, tl.y, size.width, size.height)
+    }
+
+    /// Returns the top-left corner.
+    pub fn tl(&self) -> Point<T> {
+        Point::new(self.x, self.y)
+    }
+
+    /// Returns the bottom-right corner, which lies just outside of the
+    /// rectangle.
+    pub fn br(&self) -> Point<T> {
+        Point::new(self.x + self.width, self.y + self.height)
+    }
+
+    /// Returns the size of the rectangle.
+    pub fn size(&self) -> Size<T> {
+        Size::new(self.width, self.height)
+    }
+
+    /// Returns the area (width * height) of the rectangle.
+    pub fn area(&self) -> T {
+        self.width * self.height
+    }
+
+    /// Returns true if the rectangle has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= T::default() || self.height <= T::default()
+    }
+
+    /// Checks whether the rectangle contains the point. Like OpenCV, the top
+    /// and left edges are inside the rectangle, the bottom and right edges are
+    /// not.
+    pub fn contains(&self, pt: Point<T>) -> bool {
+        self.x <= pt.x
+            && pt.x < self.x + self.width
+            && self.y <= pt.y
+            && pt.y < self.y + self.height
+    }
+
+    /// Returns the overlapping part of two rectangles, or `None` if they don't
+    /// overlap.
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let x = max(self.x, other.x);
+        let y = max(self.y, other.y);
+        let width = min(self.x + self.width, other.x + other.width) - x;
+        let height = min(self.y + self.height, other.y + other.height) - y;
+        let r = Rect::new(x, y, width, height);
+        if r.is_empty() {
+            None
+        } else {
+            Some(r)
+        }
+    }
+
+    /// Returns the minimal rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+
+        let x = min(self.x, other.x);
+        let y = min(self.y, other.y);
+        let width = max(self.x + self.width, other.x + other.width) - x;
+        let height = max(self.y + self.height, other.y + other.height) - y;
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + Add<Output = T> + Div<Output = T> + From<u8>,
+{
+    /// Returns the center of the rectangle.
+    pub fn center(&self) -> Point<T> {
+        let two = T::from(2);
+        Point::new(self.x + self.width / two, self.y + self.height / two)
+    }
+}
+
+impl Rect {
+    /// Scales the rectangle by the specified ratio.
+    pub fn scale(&self, ratio: f32) -> Rect {
+        let new_x = ((1.0 - ratio) * (self.width as f32) / 2.0) as i32 + self.x;
+        let new_y = ((1.0 - ratio) * (self.height as f32) / 2.0) as i32 + self.y;
+        let new_w = ((self.width as f32) * ratio) as i32;
+        let new_h = ((self.height as f32) * ratio) as i32;
+        Rect {
+            x: new_x,
+            y: new_y,
+            width: new_w,
+            height: new_h,
+        }
+    }
+
+    /// Returns the intersection over union (Jaccard index) of two rectangles,
+    /// a value between 0 (disjoint) and 1 (identical).
+    pub fn iou(&self, other: &Rect) -> f64 {
+        let inter = self.intersection(other).map_or(0, |r| r.area()) as f64;
+        let union = self.area() as f64 + other.area() as f64 - inter;
+        if union > 0.0 {
+            inter / union
+        } else {
+            0.0
+        }
+    }
+
+    /// Clips the rectangle to an image of the specified size. Returns `None`
+    /// if the rectangle lies outside of the image.
+    pub fn clip_to(&self, size: Size2i) -> Option<Rect> {
+        self.intersection(&Rect::new(0, 0, size.width, size.height))
+    }
+}
+
+impl<T: Add<Output = T>> Add<Point<T>> for Rect<T> {
+    type Output = Rect<T>;
+
+    /// Shifts the rectangle by `offset`.
+    fn add(self, offset: Point<T>) -> Rect<T> {
+        Rect::new(
+            self.x + offset.x,
+            self.y + offset.y,
+            self.width,
+            self.height,
+        )
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Point<T>> for Rect<T> {
+    type Output = Rect<T>;
+
+    /// Shifts the rectangle by `-offset`.
+    fn sub(self, offset: Point<T>) -> Rect<T> {
+        Rect::new(
+            self.x - offset.x,
+            self.y - offset.y,
+            self.width,
+            self.height,
+        )
+    }
+}
+
+impl<T: Add<Output = T>> Add<Size<T>> for Rect<T> {
+    type Output = Rect<T>;
+
+    /// Grows the rectangle by `size`, keeping the top-left corner.
+    fn add(self, size: Size<T>) -> Rect<T> {
+        Rect::new(
+            self.x,
+            self.y,
+            self.width + size.width,
+            self.height + size.height,
+        )
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Size<T>> for Rect<T> {
+    type Output = Rect<T>;
+
+    /// Shrinks the rectangle by `size`, keeping the top-left corner.
+    fn sub(self, size: Size<T>) -> Rect<T> {
+        Rect::new(
+            self.x,
+            self.y,
+            self.width - size.width,
+            self.height - size.height,
+        )
+    }
+}
+
+/// This struct represents a rotated (i.e. not up-right) rectangle. Each
+/// rectangle is specified by the center point (mass center), length of each
+/// side (represented by `Size2f`) and the rotation angle in degrees.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct RotatedRect {
+    /// The rectangle mass center.
+    pub center: Point2f,
+    /// Width and height of the rectangle.
+    pub size: Size2f,
+    /// The clockwise rotation angle in degrees.
+    pub angle: f32,
+}
+
+impl RotatedRect {
+    /// Creates a new rotated rectangle.
+    pub fn new(center: Point2f, size: Size2f, angle: f32) -> Self {
+        RotatedRect {
+            center: center,
+            size: size,
+            angle: angle,
+        }
+    }
+
+    /// Creates a rotated rectangle from three of its vertices, given in
+    /// (clockwise or counter-clockwise) order. Returns `None` if the sides
+    /// don't form a right angle.
+    pub fn from_points(p1: Point2f, p2: Point2f, p3: Point2f) -> Option<Self> {
+        let vecs = [p1 - p2, p2 - p3];
+        let norms = [vecs[0].norm(), vecs[1].norm()];
+        if norms[0] == 0.0 || norms[1] == 0.0 {
+            return None;
+        }
+        let cos = f64::from(vecs[0].dot(vecs[1])).abs() / (norms[0] * norms[1]);
+        if cos > 1e-3 {
+            return None;
+        }
+
+        // Like OpenCV, the width is the side closer to the x axis.
+        let wd = if vecs[1].y.abs() < vecs[1].x.abs() {
+            1
+        } else {
+            0
+        };
+        let ht = 1 - wd;
+        let angle = (vecs[wd].y / vecs[wd].x).atan().to_degrees();
+        let center = (p1 + p3) * 0.5;
+        let size = Size2f::new(norms[wd] as f32, norms[ht] as f32);
+        Some(RotatedRect::new(center, size, angle))
+    }
+
+    /// Return 4 vertices of the rectangle.
+    pub fn points(&self) -> [Point2f; 4] {
+        let angle = self.angle * ::std::f32::consts::PI / 180.0;
+
+        let b = angle.cos() * 0.5;
+        let a = angle.sin() * 0.5;
+
+        let mut pts: [Point2f; 4] = [Point2f::default(); 4];
+        pts[0].x = self.center.x - a * self.size.height - b * self.size.width;
+        pts[0].y = self.center.y + b * self.size.height - a * self.size.width;
+        pts[1].x = self.center.x + a * self.size.height - b * self.size.width;
+        pts[1].y = self.center.y - b * self.size.height - a * self.size.width;
+
+        pts[2].x = 2.0 * self.center.x - pts[0].x;
+        pts[2].y = 2.0 * self.center.y - pts[0].y;
+        pts[3].x = 2.0 * self.center.x - pts[1].x;
+        pts[3].y = 2.0 * self.center.y - pts[1].y;
+        pts
+    }
+
+    /// Return the minimal up-right rectangle containing the rotated rectangle
+    pub fn bounding_rect(&self) -> Rect {
+        let pt = self.points();
+        let x = pt.iter().map(|p| p.x).fold(0. / 0., f32::min).floor() as i32;
+        let y = pt.iter().map(|p| p.y).fold(0. / 0., f32::min).floor() as i32;
+
+        let width = pt.iter().map(|p| p.x).fold(0. / 0., f32::max).ceil() as i32 - x + 1;
+        let height = pt.iter().map(|p| p.y).fold(0. / 0., f32::max).ceil() as i32 - y + 1;
+        Rect::new(x, y, width, height)
+    }
+
+    /// Returns the minimal up-right floating point rectangle containing the
+    /// rotated rectangle, without rounding to pixels.
+    pub fn bounding_rect2f(&self) -> Rect2f {
+        let pt = self.points();
+        let tl = pt.iter().fold(
+            Point2f::new(::std::f32::INFINITY, ::std::f32::INFINITY),
+            |acc, p| Point2f::new(acc.x.min(p.x), acc.y.min(p.y)),
+        );
+        let br = pt.iter().fold(
+            Point2f::new(::std::f32::NEG_INFINITY, ::std::f32::NEG_INFINITY),
+            |acc, p| Point2f::new(acc.x.max(p.x), acc.y.max(p.y)),
+        );
+        Rect2f::from_points(tl, br)
+    }
+
+    /// Returns the area of the rectangle.
+    pub fn area(&self) -> f32 {
+        self.size.area()
+    }
+
+    /// Checks whether the point lies inside of the rectangle or on its edges.
+    /// Degenerate rectangles, without area, contain no points.
+    pub fn contains(&self, pt: Point2f) -> bool {
+        if !(self.size.width > 0.0 && self.size.height > 0.0) {
+            return false;
+        }
+        let pts = self.points();
+        let mut sign = 0.0;
+        for i in 0..4 {
+            let edge = pts[(i + 1) % 4] - pts[i];
+            let cross = edge.cross(pt - pts[i]);
+            if cross != 0.0 {
+                if sign * cross < 0.0 {
+                    return false;
+                }
+                sign = cross;
+            }
+        }
+        true
+    }
+}
+
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+macro_rules! vec_type {
+    ($name:ident, $n:expr, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[repr(C)]
+        pub struct $name<T>(pub [T; $n]);
+
+        impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> $name<T> {
+            /// Returns the dot product of two vectors.
+            pub fn dot(&self, other: $name<T>) -> T {
+                let mut sum = T::default();
+                for i in 0..$n {
+                    sum = sum + self.0[i] * other.0[i];
+                }
+                sum
+            }
+        }
+
+        impl<T> Index<usize> for $name<T> {
+            type Output = T;
+
+            fn index(&self, i: usize) -> &T {
+                &self.0[i]
+            }
+        }
+
+        impl<T> IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, i: usize) -> &mut T {
+                &mut self.0[i]
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> Add for $name<T> {
+            type Output = $name<T>;
+
+            fn add(self, other: $name<T>) -> $name<T> {
+                let mut result = self;
+                for i in 0..$n {
+                    result.0[i] = self.0[i] + other.0[i];
+                }
+                result
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> Sub for $name<T> {
+            type Output = $name<T>;
+
+            fn sub(self, other: $name<T>) -> $name<T> {
+                let mut result = self;
+                for i in 0..$n {
+                    result.0[i] = self.0[i] - other.0[i];
+                }
+                result
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $name<T> {
+            type Output = $name<T>;
+
+            fn mul(self, factor: T) -> $name<T> {
+                let mut result = self;
+                for v in &mut result.0 {
+                    *v = *v * factor;
+                }
+                result
+            }
+        }
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from(values: [T; $n]) -> Self {
+                $name(values)
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $n] {
+            fn from(v: $name<T>) -> Self {
+                v.0
+            }
+        }
+    };
+}
+
+vec_type!(Vec2, 2, "A 2-element vector, like OpenCV's `Vec2*` types.");
+vec_type!(Vec3, 3, "A 3-element vector, like OpenCV's `Vec3*` types.");
+vec_type!(Vec4, 4, "A 4-element vector, like OpenCV's `Vec4*` types.");
+
+/// Two `i32`.
+pub type Vec2i = Vec2<i32>;
+/// Two `f32`.
+pub type Vec2f = Vec2<f32>;
+/// Two `f64`.
+pub type Vec2d = Vec2<f64>;
+/// Three `u8`, e.g. a BGR pixel.
+pub type Vec3b = Vec3<u8>;
+/// Three `i32`.
+pub type Vec3i = Vec3<i32>;
+/// Three `f32`.
+pub type Vec3f = Vec3<f32>;
+/// Three `f64`.
+pub type Vec3d = Vec3<f64>;
+/// Four `u8`, e.g. a BGRA pixel.
+pub type Vec4b = Vec4<u8>;
+/// Four `i32`.
+pub type Vec4i = Vec4<i32>;
+/// Four `f32`.
+pub type Vec4f = Vec4<f32>;
+/// Four `f64`.
+pub type Vec4d = Vec4<f64>;
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Vec3<T> {
+    /// Returns the cross product of two vectors.
+    pub fn cross(&self, other: Vec3<T>) -> Vec3<T> {
+        let (a, b) = (self.0, other.0);
+        Vec3([
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ])
+    }
+}
+
+impl<T> From<Point<T>> for Vec2<T> {
+    fn from(p: Point<T>) -> Self {
+        Vec2([p.x, p.y])
+    }
+}
+
+impl<T: Copy> From<Vec2<T>> for Point<T> {
+    fn from(v: Vec2<T>) -> Self {
+        Point::new(v.0[0], v.0[1])
+    }
+}
+
+impl<T> From<Point3<T>> for Vec3<T> {
+    fn from(p: Point3<T>) -> Self {
+        Vec3([p.x, p.y, p.z])
+    }
+}
+
+impl<T: Copy> From<Vec3<T>> for Point3<T> {
+    fn from(v: Vec3<T>) -> Self {
+        Point3::new(v.0[0], v.0[1], v.0[2])
+    }
+}
+
+// Conversions between numeric types. Like OpenCV's `saturate_cast`, floating
+// point values are rounded to the nearest integer.
+macro_rules! convert_fields {
+    ($name:ident { $($field:ident),+ }, $from:ty => $to:ty, $conv:expr) => {
+        impl From<$name<$from>> for $name<$to> {
+            fn from(v: $name<$from>) -> Self {
+                let conv = $conv;
+                $name { $($field: conv(v.$field)),+ }
+            }
+        }
+    };
+}
+
+macro_rules! numeric_conversions {
+    ($($name:ident { $($field:ident),+ });+) => {
+        $(
+            convert_fields!($name { $($field),+ }, i32 => f32, |v: i32| v as f32);
+            convert_fields!($name { $($field),+ }, i32 => f64, f64::from);
+            convert_fields!($name { $($field),+ }, f32 => f64, f64::from);
+            convert_fields!($name { $($field),+ }, f64 => f32, |v: f64| v as f32);
+            convert_fields!($name { $($field),+ }, f32 => i32, |v: f32| v.round() as i32);
+            convert_fields!($name { $($field),+ }, f64 => i32, |v: f64| v.round() as i32);
+        )+
+    };
+}
+
+numeric_conversions!(
+    Point { x, y };
+    Point3 { x, y, z };
+    Size { width, height };
+    Rect { x, y, width, height }
+);
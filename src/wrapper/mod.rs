@@ -1,140 +1,143 @@
 use libc::{c_char, c_double, c_float, c_int, c_uchar, c_void, size_t, uint8_t};
-use super::core::{Mat, MatType, Rect2f};
+use super::core::{Mat, MatType};
+use super::errors::{ErrorKind, Result};
+use std::ffi::CString;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+use std::path::Path;
 
 mod core;
 mod functions;
+mod geometry;
 mod videoio;
 pub use self::core::*;
 pub use self::functions::*;
+pub use self::geometry::*;
 pub use self::videoio::*;
 
-/// A 4-element struct that is widely used to pass pixel values.
-#[derive(Default, Debug, Clone, Copy)]
+/// A 4-element vector of `f64` that is widely used to pass pixel values,
+/// like OpenCV's `cv::Scalar`. Channels that an operation doesn't need are
+/// ignored.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[repr(C)]
 pub struct Scalar {
-    v0: i32,
-    v1: i32,
-    v2: i32,
-    v3: i32,
+    val: [f64; 4],
 }
 
 impl Scalar {
     /// Creates a new scalar object.
-    pub fn new(v0: i32, v1: i32, v2: i32, v3: i32) -> Self {
+    pub fn new(v0: f64, v1: f64, v2: f64, v3: f64) -> Self {
         Scalar {
-            v0: v0,
-            v1: v1,
-            v2: v2,
-            v3: v3,
+            val: [v0, v1, v2, v3],
         }
     }
+
+    /// Creates a scalar with all four values set to `v`.
+    pub fn all(v: f64) -> Self {
+        Scalar { val: [v; 4] }
+    }
+
+    /// Creates a color for (default) BGR images from its red, green and blue
+    /// components.
+    pub fn from_rgb(r: f64, g: f64, b: f64) -> Self {
+        Scalar::new(b, g, r, 0.0)
+    }
+
+    /// Creates a color for (default) BGR images from its blue, green and red
+    /// components.
+    pub fn from_bgr(b: f64, g: f64, r: f64) -> Self {
+        Scalar::new(b, g, r, 0.0)
+    }
 }
 
-/// 2D integer points specified by its coordinates `x` and `y`.
-#[derive(Default, Debug, Clone, Copy)]
-#[repr(C)]
-pub struct Point2i {
-    /// x coordinate
-    pub x: i32,
+impl Index<usize> for Scalar {
+    type Output = f64;
 
-    /// y coordinate
-    pub y: i32,
+    fn index(&self, i: usize) -> &f64 {
+        &self.val[i]
+    }
 }
 
-/// 2D floating points specified by its coordinates `x` and `y`.
-#[derive(Default, Debug, Clone, Copy)]
-#[repr(C)]
-pub struct Point2f {
-    /// x coordinate
-    pub x: f32,
+impl IndexMut<usize> for Scalar {
+    fn index_mut(&mut self, i: usize) -> &mut f64 {
+        &mut self.val[i]
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
 
-    /// y coordinate
-    pub y: f32,
+    fn add(self, other: Scalar) -> Scalar {
+        let mut result = self;
+        for i in 0..4 {
+            result.val[i] += other.val[i];
+        }
+        result
+    }
 }
 
-/// Represents the integral size (width and height) of an image or rectangle.
-#[derive(Default, Debug, Clone, Copy)]
-#[repr(C)]
-pub struct Size2i {
-    /// The width.
-    pub width: c_int,
+impl Sub for Scalar {
+    type Output = Scalar;
 
-    /// The height.
-    pub height: c_int,
+    fn sub(self, other: Scalar) -> Scalar {
+        let mut result = self;
+        for i in 0..4 {
+            result.val[i] -= other.val[i];
+        }
+        result
+    }
 }
 
-impl Size2i {
-    /// Initializes a `Size2i` with the specified width and height.
-    pub fn new(width: c_int, height: c_int) -> Self {
-        Size2i {
-            width: width,
-            height: height,
+impl Mul<f64> for Scalar {
+    type Output = Scalar;
+
+    fn mul(self, factor: f64) -> Scalar {
+        let mut result = self;
+        for v in &mut result.val {
+            *v *= factor;
         }
+        result
     }
 }
 
-/// `Size2f` struct is used for specifying the size (`width` and `height` as
-/// `f32`) of an image or rectangle.
-#[derive(Default, Debug, Clone, Copy)]
-#[repr(C)]
-pub struct Size2f {
-    /// width
-    pub width: f32,
+impl Div<f64> for Scalar {
+    type Output = Scalar;
 
-    /// height
-    pub height: f32,
+    fn div(self, divisor: f64) -> Scalar {
+        let mut result = self;
+        for v in &mut result.val {
+            *v /= divisor;
+        }
+        result
+    }
 }
 
-/// The `Rect` defines a rectangle in integer.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(C)]
-pub struct Rect {
-    /// x coordinate of the left-top corner
-    pub x: i32,
-    /// y coordinate of the left-top corner
-    pub y: i32,
-    /// width of this rectangle
-    pub width: i32,
-    /// height of this rectangle
-    pub height: i32,
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        self * -1.0
+    }
 }
 
-impl Rect {
-    /// Creates a new `Rect` with (x, y, width, height) parameters.
-    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
-        Rect {
-            x: x,
-            y: y,
-            width: width,
-            height: height,
-        }
+impl From<[f64; 4]> for Scalar {
+    fn from(val: [f64; 4]) -> Self {
+        Scalar { val: val }
     }
+}
 
-    /// Scales the rectangle by the specified ratio.
-    pub fn scale(&self, ratio: f32) -> Rect {
-        let new_x = ((1.0 - ratio) * (self.width as f32) / 2.0) as i32 + self.x;
-        let new_y = ((1.0 - ratio) * (self.height as f32) / 2.0) as i32 + self.y;
-        let new_w = ((self.width as f32) * ratio) as i32;
-        let new_h = ((self.height as f32) * ratio) as i32;
-        Rect {
-            x: new_x,
-            y: new_y,
-            width: new_w,
-            height: new_h,
-        }
+impl From<Scalar> for [f64; 4] {
+    fn from(scalar: Scalar) -> Self {
+        scalar.val
     }
+}
 
-    /// Normalize the rectangle according to the image (if the rectangle is
-    /// inside the image, then the result should be all within (0, 1).
-    pub fn normalize_to_mat(&self, mat: &Mat) -> Rect2f {
-        let cols = mat.cols();
-        let rows = mat.rows();
-        Rect2f {
-            x: (self.x as f32) / (cols as f32),
-            y: (self.y as f32) / (rows as f32),
-            width: (self.width as f32) / (cols as f32),
-            height: (self.height as f32) / (rows as f32),
-        }
+/// Converts a `(b, g, r)` tuple, in the channel order of (default) BGR
+/// images.
+impl From<(u8, u8, u8)> for Scalar {
+    fn from((b, g, r): (u8, u8, u8)) -> Self {
+        Scalar::from_bgr(f64::from(b), f64::from(g), f64::from(r))
     }
 }
 
@@ -172,6 +175,40 @@ impl CVecOfRect {
     }
 }
 
+#[repr(C)]
+pub struct CVecOfMat {
+    pub array: *mut *mut CMat,
+    pub size: usize,
+}
+
+impl Default for CVecOfMat {
+    fn default() -> Self {
+        CVecOfMat {
+            array: ::std::ptr::null_mut::<*mut CMat>(),
+            size: 0,
+        }
+    }
+}
+
+impl Drop for CVecOfMat {
+    fn drop(&mut self) {
+        extern "C" {
+            fn cv_vec_of_mat_drop(_: *mut CVecOfMat);
+        }
+        unsafe {
+            cv_vec_of_mat_drop(self);
+        }
+    }
+}
+
+impl CVecOfMat {
+    pub fn rustify(self) -> Vec<Mat> {
+        (0..self.size)
+            .map(|i| Mat::from_raw(unsafe { *(self.array.offset(i as isize)) }))
+            .collect::<Vec<_>>()
+    }
+}
+
 #[repr(C)]
 pub struct CVecDouble {
     array: *mut c_double,
@@ -180,7 +217,7 @@ pub struct CVecDouble {
 
 impl CVecDouble {
     pub fn rustify(self) -> Vec<f64> {
-        (1..self.size)
+        (0..self.size)
             .map(|i| unsafe { *(self.array.offset(i as isize)) })
             .collect::<Vec<_>>()
     }
@@ -195,6 +232,51 @@ impl Default for CVecDouble {
     }
 }
 
+impl Drop for CVecDouble {
+    fn drop(&mut self) {
+        extern "C" {
+            fn cv_vec_double_drop(_: *mut CVecDouble);
+        }
+        unsafe {
+            cv_vec_double_drop(self);
+        }
+    }
+}
+
+#[repr(C)]
+pub struct CVecFloat {
+    array: *mut c_float,
+    size: usize,
+}
+
+impl CVecFloat {
+    pub fn rustify(self) -> Vec<f32> {
+        (0..self.size)
+            .map(|i| unsafe { *(self.array.offset(i as isize)) })
+            .collect::<Vec<_>>()
+    }
+}
+
+impl Default for CVecFloat {
+    fn default() -> Self {
+        CVecFloat {
+            array: ::std::ptr::null_mut::<c_float>(),
+            size: 0,
+        }
+    }
+}
+
+impl Drop for CVecFloat {
+    fn drop(&mut self) {
+        extern "C" {
+            fn cv_vec_float_drop(_: *mut CVecFloat);
+        }
+        unsafe {
+            cv_vec_float_drop(self);
+        }
+    }
+}
+
 #[repr(C)]
 pub struct ImencodeResult {
     pub status: bool,
@@ -213,49 +295,18 @@ pub enum CSvmDetector {}
 
 pub enum CTermCriteria {}
 
-/// This struct represents a rotated (i.e. not up-right) rectangle. Each
-/// rectangle is specified by the center point (mass center), length of each
-/// side (represented by `Size2f`) and the rotation angle in degrees.
-#[derive(Default, Debug, Clone, Copy)]
-#[repr(C)]
-pub struct RotatedRect {
-    center: Point2f,
-    size: Size2f,
-    angle: f32,
-}
+pub enum CPca {}
 
-impl RotatedRect {
-    /// Return 4 vertices of the rectangle.
-    pub fn points(&self) -> [Point2f; 4] {
-        let angle = self.angle * ::std::f32::consts::PI / 180.0;
-
-        let b = angle.cos() * 0.5;
-        let a = angle.sin() * 0.5;
-
-        let mut pts: [Point2f; 4] = [Point2f::default(); 4];
-        pts[0].x = self.center.x - a * self.size.height - b * self.size.width;
-        pts[0].y = self.center.y + b * self.size.height - a * self.size.width;
-        pts[1].x = self.center.x + a * self.size.height - b * self.size.width;
-        pts[1].y = self.center.y - b * self.size.height - a * self.size.width;
-
-        pts[2].x = 2.0 * self.center.x - pts[0].x;
-        pts[2].y = 2.0 * self.center.y - pts[0].y;
-        pts[3].x = 2.0 * self.center.x - pts[1].x;
-        pts[3].y = 2.0 * self.center.y - pts[1].y;
-        pts
-    }
+/// Opaque VideoWriter type.
+pub enum CvVideoWriter {}
 
-    /// Return the minimal up-right rectangle containing the rotated rectangle
-    pub fn bounding_rect(&self) -> Rect {
-        let pt = self.points();
-        let x = pt.iter().map(|p| p.x).fold(0. / 0., f32::min).floor() as i32;
-        let y = pt.iter().map(|p| p.y).fold(0. / 0., f32::min).floor() as i32;
+/// Opaque type for the pages of a multi-page image.
+pub enum CImagePages {}
 
-        let width = pt.iter().map(|p| p.x).fold(0. / 0., f32::max).ceil() as i32 - x + 1;
-        let height = pt.iter().map(|p| p.y).fold(0. / 0., f32::max).ceil() as i32 - y + 1;
-        Rect::new(x, y, width, height)
+/// Converts a path into a `CString` that can be handed to OpenCV.
+pub fn path_to_cstring<P: AsRef<Path>>(path: P) -> Result<CString> {
+    match path.as_ref().to_str() {
+        Some(p) => Ok(CString::new(p)?),
+        None => Err(ErrorKind::InvalidPath(path.as_ref().to_path_buf()).into()),
     }
 }
-
-/// Opaque VideoWriter type.
-pub enum CvVideoWriter {}
@@ -6,7 +6,21 @@ extern "C" {
     pub fn cv_videocapture_new(index: c_int) -> *mut CvVideoCapture;
     pub fn cv_videocapture_from_file(path: *const c_char) -> *mut CvVideoCapture;
     pub fn cv_videocapture_is_opened(ccap: *const CvVideoCapture) -> bool;
-    pub fn cv_videocapture_read(v: *mut CvVideoCapture, m: *mut CMat) -> bool;
+    pub fn cv_videocapture_read(v: *mut CvVideoCapture, m: *mut CMat, grabbed: *mut bool) -> bool;
+    pub fn cv_videocapture_grab(cap: *mut CvVideoCapture, grabbed: *mut bool) -> bool;
+    pub fn cv_videocapture_retrieve(
+        cap: *mut CvVideoCapture,
+        m: *mut CMat,
+        flag: c_int,
+        retrieved: *mut bool,
+    ) -> bool;
+    pub fn cv_videocapture_have_wait_any() -> bool;
+    pub fn cv_videocapture_wait_any(
+        caps: *const *mut CvVideoCapture,
+        len: usize,
+        timeout_ns: i64,
+        ready: *mut bool,
+    ) -> bool;
     pub fn cv_videocapture_drop(cap: *mut CvVideoCapture);
     pub fn cv_videocapture_set(cap: *mut CvVideoCapture, property: c_int, value: c_double) -> bool;
     pub fn cv_videocapture_get(cap: *mut CvVideoCapture, property: c_int) -> c_double;
@@ -30,7 +30,7 @@ fn bench_face_detect_physicists() {
     for i in 0..3 {
         let rate = 1.0 - (i as f64) * 0.1;
         let m = mat.resize_by(rate, rate, InterpolationFlag::InterLinear);
-        let name = format!("detect physicists: {}x{}", m.rows, m.cols);
+        let name = format!("detect physicists: {}x{}", m.rows(), m.cols());
         timed_multiple(&name, 1, || {
             cascade.detect(&m);
         });
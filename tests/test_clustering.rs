@@ -0,0 +1,138 @@
+extern crate cv;
+
+use cv::*;
+use cv::video::tracking::{TermCriteria, TermType};
+
+mod utils;
+use utils::*;
+
+fn two_clusters() -> Mat {
+    let mut samples = Vec::new();
+    for center in &[1.0, 8.0] {
+        for i in 0..10 {
+            samples.push(center + (i % 3) as f32 * 0.3);
+            samples.push(center + (i % 4) as f32 * 0.2);
+        }
+    }
+    mat_from_f32(20, 2, &samples)
+}
+
+/// Points close to the line `y = 2x`.
+fn line_points() -> Mat {
+    let mut samples = Vec::new();
+    for i in 0..10 {
+        let x = i as f32;
+        let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+        samples.push(x);
+        samples.push(2.0 * x + noise);
+    }
+    mat_from_f32(10, 2, &samples)
+}
+
+fn criteria() -> TermCriteria {
+    TermCriteria::new(TermType::CountEps, 100, 1e-3)
+}
+
+fn labels_of(labels: &Mat) -> Vec<i32> {
+    let len = labels.total();
+    unsafe { std::slice::from_raw_parts(labels.data() as *const i32, len).to_vec() }
+}
+
+#[test]
+fn kmeans_separates_clusters() {
+    let data = two_clusters();
+    let (labels, centers, compactness) = data
+        .kmeans(2, &criteria(), 3, KmeansFlags::PpCenters)
+        .unwrap();
+
+    let labels = labels_of(&labels);
+    assert_eq!(labels.len(), 20);
+    assert!(labels[..10].iter().all(|&l| l == labels[0]));
+    assert!(labels[10..].iter().all(|&l| l == labels[10]));
+    assert!(labels[0] != labels[10]);
+
+    assert_eq!(centers.rows(), 2);
+    assert_eq!(centers.cols(), 2);
+    assert!(compactness > 0.0 && compactness < 10.0);
+}
+
+#[test]
+fn kmeans_random_centers() {
+    let data = two_clusters();
+    let (labels, _, _) = data
+        .kmeans(2, &criteria(), 5, KmeansFlags::RandomCenters)
+        .unwrap();
+    let labels = labels_of(&labels);
+    assert!(labels[0] != labels[10]);
+}
+
+#[test]
+fn kmeans_from_initial_labels() {
+    let data = two_clusters();
+    let initial: Vec<i32> = (0..20).map(|i| if i < 12 { 0 } else { 1 }).collect();
+    let initial = mat_from_i32(20, 1, &initial);
+    let (labels, _, _) = data
+        .kmeans_with_labels(2, &initial, &criteria(), 1, KmeansFlags::PpCenters)
+        .unwrap();
+    let labels = labels_of(&labels);
+    assert_eq!(labels[0], 0);
+    assert_eq!(labels[19], 1);
+    assert_eq!(labels[11], 0);
+    assert_eq!(labels[10], 1);
+}
+
+#[test]
+fn kmeans_more_clusters_than_samples_fails() {
+    let data = mat_from_f32(2, 2, &[0.0, 0.0, 1.0, 1.0]);
+    assert!(data
+        .kmeans(3, &criteria(), 1, KmeansFlags::PpCenters)
+        .is_err());
+}
+
+#[test]
+fn pca_finds_main_direction() {
+    let data = line_points();
+    let pca = Pca::new(&data, PcaLayout::DataAsRow, 1).unwrap();
+
+    let eigenvectors = mat_to_f32(&pca.eigenvectors());
+    assert_eq!(eigenvectors.len(), 2);
+    let ratio = eigenvectors[1] / eigenvectors[0];
+    assert!((ratio - 2.0).abs() < 0.05);
+
+    let mean = mat_to_f32(&pca.mean());
+    assert!((mean[0] - 4.5).abs() < 1e-4);
+    assert_eq!(pca.eigenvalues().total(), 1);
+}
+
+#[test]
+fn pca_project_back_project() {
+    let data = line_points();
+    let pca = Pca::new(&data, PcaLayout::DataAsRow, 1).unwrap();
+
+    let projected = pca.project(&data).unwrap();
+    assert_eq!(projected.rows(), 10);
+    assert_eq!(projected.cols(), 1);
+
+    let restored = mat_to_f32(&pca.back_project(&projected).unwrap());
+    let original = mat_to_f32(&data);
+    for (r, o) in restored.iter().zip(original.iter()) {
+        assert!((r - o).abs() < 0.1);
+    }
+}
+
+#[test]
+fn pca_retained_variance() {
+    let data = line_points();
+    let pca = Pca::with_retained_variance(&data, PcaLayout::DataAsRow, 0.95).unwrap();
+    assert_eq!(pca.eigenvalues().total(), 1);
+
+    let pca = Pca::with_retained_variance(&data, PcaLayout::DataAsRow, 1.0).unwrap();
+    assert_eq!(pca.eigenvalues().total(), 2);
+}
+
+#[test]
+fn pca_project_mismatched_fails() {
+    let pca = Pca::new(&line_points(), PcaLayout::DataAsRow, 1).unwrap();
+    let wrong = mat_from_f32(1, 3, &[1.0, 2.0, 3.0]);
+    assert!(pca.project(&wrong).is_err());
+}
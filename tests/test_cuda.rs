@@ -20,7 +20,7 @@ fn test_pedestrian_detection() {
     params.hit_threshold = 0.3;
     let mut hog = Hog::with_params(params);
     let detector = SvmDetector::default_people_detector();
-    hog.set_svm_detector(detector);
+    hog.set_svm_detector(detector).unwrap();
     let result = hog.detect(&mat);
     assert!(result.len() > 1);
 }
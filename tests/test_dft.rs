@@ -0,0 +1,125 @@
+extern crate cv;
+
+use cv::*;
+use cv::imgproc::create_hanning_window;
+
+mod utils;
+use utils::*;
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+    }
+}
+
+/// A 32x32 image with a bright square whose top-left corner is at (x, y).
+fn square_at(x: usize, y: usize) -> Mat {
+    let mut data = vec![0.0f32; 32 * 32];
+    for row in y..y + 6 {
+        for col in x..x + 6 {
+            data[row * 32 + col] = 1.0;
+        }
+    }
+    mat_from_f32(32, 32, &data)
+}
+
+#[test]
+fn optimal_dft_size() {
+    assert_eq!(get_optimal_dft_size(512), 512);
+    assert_eq!(get_optimal_dft_size(97), 100);
+    assert_eq!(get_optimal_dft_size(101), 108);
+}
+
+#[test]
+fn dft_complex_output() {
+    let m = mat_from_f32(1, 4, &[1.0, 1.0, 1.0, 1.0]);
+    let spectrum = m.dft(DftFlags::DFT_COMPLEX_OUTPUT, 0).unwrap();
+    assert_eq!(spectrum.mat_type().channels(), 2);
+    assert_close(
+        &mat_to_f32(&spectrum),
+        &[4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    );
+}
+
+#[test]
+fn dft_idft_round_trip() {
+    let data: Vec<f32> = (0..16).map(|i| i as f32).collect();
+    let m = mat_from_f32(4, 4, &data);
+    let spectrum = m.dft(DftFlags::DFT_COMPLEX_OUTPUT, 0).unwrap();
+    let restored = spectrum
+        .idft(DftFlags::DFT_SCALE | DftFlags::DFT_REAL_OUTPUT, 0)
+        .unwrap();
+    assert_eq!(restored.mat_type().channels(), 1);
+    assert_close(&mat_to_f32(&restored), &data);
+}
+
+#[test]
+fn dft_rows() {
+    let m = mat_from_f32(2, 2, &[1.0, 1.0, 2.0, 2.0]);
+    let spectrum = m
+        .dft(DftFlags::DFT_ROWS | DftFlags::DFT_COMPLEX_OUTPUT, 0)
+        .unwrap();
+    assert_close(
+        &mat_to_f32(&spectrum),
+        &[2.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0],
+    );
+}
+
+#[test]
+fn dft_rejects_integer_input() {
+    let m = mat_from_i32(1, 4, &[1, 2, 3, 4]);
+    assert!(m.dft(DftFlags::empty(), 0).is_err());
+}
+
+#[test]
+fn dct_idct_round_trip() {
+    let m = mat_from_f32(1, 4, &[1.0, 1.0, 1.0, 1.0]);
+    let coefficients = m.dct(DftFlags::empty()).unwrap();
+    assert_close(&mat_to_f32(&coefficients), &[2.0, 0.0, 0.0, 0.0]);
+    let restored = coefficients.idct(DftFlags::empty()).unwrap();
+    assert_close(&mat_to_f32(&restored), &[1.0, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn mul_spectrums_with_impulse() {
+    let data: Vec<f32> = (0..8).map(|i| (i * i) as f32).collect();
+    let m = mat_from_f32(1, 8, &data);
+    let impulse = mat_from_f32(1, 8, &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+
+    let a = m.dft(DftFlags::DFT_COMPLEX_OUTPUT, 0).unwrap();
+    let b = impulse.dft(DftFlags::DFT_COMPLEX_OUTPUT, 0).unwrap();
+    let product = a.mul_spectrums(&b, DftFlags::empty(), false).unwrap();
+    let filtered = product
+        .idft(DftFlags::DFT_SCALE | DftFlags::DFT_REAL_OUTPUT, 0)
+        .unwrap();
+    assert_close(&mat_to_f32(&filtered), &data);
+}
+
+#[test]
+fn phase_correlate_finds_shift() {
+    let a = square_at(10, 12);
+    let b = square_at(13, 17);
+    let (shift, response) = a.phase_correlate(&b, None).unwrap();
+    assert!((shift.x.abs() - 3.0).abs() < 0.5, "{:?}", shift);
+    assert!((shift.y.abs() - 5.0).abs() < 0.5, "{:?}", shift);
+    assert!(response > 0.0);
+
+    let window = create_hanning_window(Size2i::new(32, 32), MatDepth::Float32).unwrap();
+    assert_eq!(window.rows(), 32);
+    let (shift, _) = a.phase_correlate(&b, Some(&window)).unwrap();
+    assert!((shift.x.abs() - 3.0).abs() < 0.5, "{:?}", shift);
+}
+
+#[test]
+fn create_hanning_window_fails() {
+    assert!(create_hanning_window(Size2i::new(32, 32), MatDepth::Unsigned8).is_err());
+    assert!(create_hanning_window(Size2i::new(1, 32), MatDepth::Float32).is_err());
+}
+
+#[test]
+fn phase_correlate_mismatched_fails() {
+    let a = square_at(0, 0);
+    let b = mat_from_f32(2, 2, &[0.0; 4]);
+    assert!(a.phase_correlate(&b, None).is_err());
+}
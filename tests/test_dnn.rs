@@ -0,0 +1,231 @@
+extern crate cv;
+
+use cv::*;
+use cv::dnn::*;
+use cv::objdetect::ObjectDetect;
+use std::path::PathBuf;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn nms_boxes_keeps_best_of_overlapping() {
+    let boxes = vec![
+        Rect::new(10, 10, 100, 100),
+        Rect::new(12, 12, 100, 100),
+        Rect::new(300, 300, 50, 50),
+    ];
+    let scores = vec![0.8, 0.9, 0.7];
+    let kept = nms_boxes(&boxes, &scores, 0.5, 0.4).unwrap();
+    assert_eq!(kept.len(), 2);
+    assert!(kept.contains(&1));
+    assert!(kept.contains(&2));
+}
+
+#[test]
+fn nms_boxes_drops_low_scores() {
+    let boxes = vec![Rect::new(10, 10, 100, 100), Rect::new(300, 300, 50, 50)];
+    let scores = vec![0.8, 0.2];
+    assert_eq!(nms_boxes(&boxes, &scores, 0.5, 0.4).unwrap(), vec![0]);
+}
+
+#[test]
+fn nms_boxes_mismatched_scores_fail() {
+    let boxes = vec![Rect::new(0, 0, 10, 10), Rect::new(20, 20, 10, 10)];
+    assert!(nms_boxes(&boxes, &[0.9], 0.5, 0.4).is_err());
+}
+
+#[test]
+fn decode_ssd_output() {
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    let output = mat_from_f32(3, 7, &[
+        0.0, 1.0, 0.9, 0.1, 0.2, 0.3, 0.4,
+        0.0, 2.0, 0.3, 0.5, 0.5, 0.6, 0.6,
+        0.0, 1.0, 0.6, 0.5, 0.5, 1.0, 1.0,
+    ]);
+    let detections = decode_ssd(&output, Size2i::new(200, 100), 0.5);
+    assert_eq!(detections.len(), 2);
+    assert_eq!(detections[0].rect, Rect::new(20, 20, 40, 20));
+    assert_eq!(detections[0].class_id, 1);
+    assert!((detections[0].score - 0.9).abs() < 1e-6);
+    assert_eq!(detections[1].rect, Rect::new(100, 50, 100, 50));
+}
+
+#[test]
+fn decode_yolo_output() {
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    let output = mat_from_f32(2, 7, &[
+        0.5, 0.5, 0.2, 0.4, 0.9, 0.1, 0.8,
+        0.5, 0.5, 0.2, 0.4, 0.9, 0.1, 0.2,
+    ]);
+    let detections = decode_yolo(&output, Size2i::new(100, 100), 0.5);
+    assert_eq!(detections.len(), 1);
+    assert_eq!(detections[0].rect, Rect::new(40, 30, 20, 40));
+    assert_eq!(detections[0].class_id, 1);
+    assert_eq!(detections[0].label, None);
+}
+
+#[test]
+fn decode_batched_yolo_output() {
+    let row = [0.5f32, 0.5, 0.2, 0.4, 0.9, 0.1, 0.8];
+    let mat_type = MatType::new(MatDepth::Float32, 1).unwrap();
+    let mut output = Mat::new_nd(&[1, 1, 7], mat_type).unwrap();
+    for (i, v) in row.iter().enumerate() {
+        output.set_at(&[0, 0, i as i32], *v).unwrap();
+    }
+    let detections = decode_yolo(&output, Size2i::new(100, 100), 0.5);
+    assert_eq!(detections.len(), 1);
+    assert_eq!(detections[0].rect, Rect::new(40, 30, 20, 40));
+}
+
+#[test]
+fn decode_unsupported_yolo_output() {
+    let mat_type = MatType::new(MatDepth::Float32, 1).unwrap();
+    let output = Mat::new_nd(&[1, 1, 1, 7], mat_type).unwrap();
+    assert!(decode_yolo(&output, Size2i::new(100, 100), 0.0).is_empty());
+
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    let output = mat_from_f32(2, 8, &[
+        0.5, 0.5, 0.2, 0.4, 0.9, 0.1, 0.8, 0.0,
+        0.5, 0.5, 0.2, 0.4, 0.9, 0.1, 0.8, 0.0,
+    ]);
+    let strided = output.col_range(0, 7).unwrap();
+    assert!(!strided.is_continuous());
+    assert!(decode_yolo(&strided, Size2i::new(100, 100), 0.5).is_empty());
+}
+
+#[test]
+fn missing_model_is_an_error() {
+    assert!(Net::from_onnx("assets/does-not-exist.onnx").is_err());
+    assert!(FaceDetector::from_caffe("deploy.prototxt", "missing.caffemodel").is_err());
+}
+
+/// Loads one of the test models. They ignore their input and always output
+/// the same four detections: two overlapping ones of class 1, one of class 2
+/// (class 0 for YOLO) and one below the default confidence threshold.
+fn load_test_net(name: &str) -> Net {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("assets");
+    d.push(name);
+    Net::from_onnx(d).unwrap()
+}
+
+#[test]
+fn net_forward() {
+    let net = load_test_net("ssd_test.onnx");
+    let blob = blob_from_image(
+        &load_lenna_color(),
+        1.0,
+        Size2i::new(300, 300),
+        Scalar::default(),
+        false,
+        false,
+    )
+    .unwrap();
+    assert_eq!(blob.sizes(), vec![1, 3, 300, 300]);
+    net.set_input(&blob, "").unwrap();
+    let output = net.forward("").unwrap();
+    assert_eq!(output.sizes(), vec![1, 1, 4, 7]);
+    let outputs = net.forward_all().unwrap();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].sizes(), vec![1, 1, 4, 7]);
+}
+
+#[test]
+fn set_unknown_input_fails() {
+    let net = load_test_net("ssd_test.onnx");
+    let blob = mat_from_f32(1, 1, &[0.0]);
+    assert!(net.set_input(&blob, "no-such-layer").is_err());
+}
+
+#[test]
+fn blob_from_empty_image_fails() {
+    let blob = blob_from_image(
+        &Mat::new(),
+        1.0,
+        Size2i::new(300, 300),
+        Scalar::default(),
+        false,
+        false,
+    );
+    assert!(blob.is_err());
+}
+
+#[test]
+fn ssd_detector() {
+    let mut detector =
+        SsdDetector::new(load_test_net("ssd_test.onnx"), DnnDetectorParams::default());
+    detector.labels = vec!["background".to_string(), "person".to_string()];
+    let lenna = load_lenna_color();
+
+    let detections = detector.detect_with_meta(&lenna);
+    assert_eq!(detections.len(), 2);
+    assert_eq!(detections[0].rect, Rect::new(64, 128, 192, 192));
+    assert_eq!(detections[0].class_id, 1);
+    assert_eq!(detections[0].label, Some("person".to_string()));
+    assert!((detections[0].score - 0.9).abs() < 1e-6);
+    assert_eq!(detections[1].rect, Rect::new(256, 256, 192, 128));
+    assert_eq!(detections[1].class_id, 2);
+    assert_eq!(detections[1].label, None);
+
+    let rects = detector.detect(&lenna);
+    assert_eq!(rects.len(), 2);
+    assert_eq!(rects[0].0, detections[0].rect);
+    assert_eq!(rects[1].0, detections[1].rect);
+}
+
+#[test]
+fn yolo_detector() {
+    let mut detector = YoloDetector::new(
+        load_test_net("yolo_test.onnx"),
+        DnnDetectorParams::default(),
+    );
+    detector.labels = vec!["person".to_string(), "bicycle".to_string()];
+    // Gray images are converted to color before being fed to the network.
+    let lenna = load_lenna();
+
+    let detections = detector.detect_with_meta(&lenna);
+    assert_eq!(detections.len(), 2);
+    assert_eq!(detections[0].rect, Rect::new(352, 320, 64, 128));
+    assert_eq!(detections[0].class_id, 0);
+    assert_eq!(detections[0].label, Some("person".to_string()));
+    assert!((detections[0].score - 0.9).abs() < 1e-6);
+    assert_eq!(detections[1].rect, Rect::new(128, 128, 128, 128));
+    assert_eq!(detections[1].class_id, 1);
+    assert_eq!(detections[1].label, Some("bicycle".to_string()));
+
+    let rects = detector.detect(&lenna);
+    assert_eq!(rects.len(), 2);
+    assert!((rects[1].1 - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn face_detector() {
+    let mut detector = FaceDetector::new(load_test_net("ssd_test.onnx"));
+    detector.set_confidence_threshold(0.75);
+    let lenna = load_lenna_color();
+
+    let detections = detector.detect_with_meta(&lenna);
+    assert_eq!(detections.len(), 1);
+    assert_eq!(detections[0].rect, Rect::new(64, 128, 192, 192));
+    assert_eq!(detections[0].label, Some("face".to_string()));
+
+    detector.set_nms_threshold(1.0);
+    assert_eq!(detector.detect(&lenna).len(), 2);
+}
+
+#[test]
+fn detectors_skip_empty_images() {
+    let detector = FaceDetector::new(load_test_net("ssd_test.onnx"));
+    assert!(detector.detect(&Mat::new()).is_empty());
+}
+
+#[test]
+fn unsupported_image_fails_try_detect() {
+    let detector = FaceDetector::new(load_test_net("ssd_test.onnx"));
+    // The detectors only accept 1, 3 or 4 channel images
+    let mat_type = MatType::new(MatDepth::Unsigned8, 2).unwrap();
+    let image = Mat::with_size(10, 10, mat_type);
+    assert!(detector.try_detect(&image).is_err());
+    assert!(detector.detect(&image).is_empty());
+}
@@ -0,0 +1,121 @@
+extern crate cv;
+
+use cv::*;
+
+#[test]
+fn point_arithmetic() {
+    let a = Point2i::new(1, 2);
+    let b = Point2i::new(4, 6);
+    assert_eq!(a + b, Point2i::new(5, 8));
+    assert_eq!(b - a, Point2i::new(3, 4));
+    assert_eq!(a * 3, Point2i::new(3, 6));
+    assert_eq!(-a, Point2i::new(-1, -2));
+    assert_eq!(a.dot(b), 16);
+    assert_eq!(a.cross(b), -2);
+    assert_eq!(a.distance(b), 5.0);
+    assert_eq!(Point2f::new(3.0, 4.0).norm(), 5.0);
+}
+
+#[test]
+fn point3_arithmetic() {
+    let x = Point3i::new(1, 0, 0);
+    let y = Point3i::new(0, 1, 0);
+    assert_eq!(x.cross(y), Point3i::new(0, 0, 1));
+    assert_eq!(x.dot(y), 0);
+    assert_eq!(x + y, Point3i::new(1, 1, 0));
+    assert_eq!((x - y) * 2, Point3i::new(2, -2, 0));
+    assert_eq!(
+        Point3d::new(1.0, 2.0, 2.0).distance(Point3d::default()),
+        3.0
+    );
+}
+
+#[test]
+fn numeric_conversions() {
+    let p: Point2f = Point2i::new(1, -2).into();
+    assert_eq!(p, Point2f::new(1.0, -2.0));
+    let p: Point2i = Point2f::new(1.6, -2.4).into();
+    assert_eq!(p, Point2i::new(2, -2));
+    let p: Point2d = Point2f::new(0.5, 0.25).into();
+    assert_eq!(p, Point2d::new(0.5, 0.25));
+
+    let s: Size2f = Size2i::new(3, 4).into();
+    assert_eq!(s, Size2f::new(3.0, 4.0));
+    let r: Rect = Rect2f::new(0.4, 0.6, 9.5, 10.2).into();
+    assert_eq!(r, Rect::new(0, 1, 10, 10));
+    let p: Point3d = Point3i::new(1, 2, 3).into();
+    assert_eq!(p, Point3d::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn size_ops() {
+    let s = Size2i::new(3, 4);
+    assert_eq!(s.area(), 12);
+    assert!(!s.is_empty());
+    assert!(Size2i::new(0, 4).is_empty());
+    assert_eq!(s + Size2i::new(1, 1), Size2i::new(4, 5));
+    assert_eq!(s * 2, Size2i::new(6, 8));
+}
+
+#[test]
+fn rect_corners() {
+    let r = Rect::new(10, 20, 30, 40);
+    assert_eq!(r.tl(), Point2i::new(10, 20));
+    assert_eq!(r.br(), Point2i::new(40, 60));
+    assert_eq!(r.center(), Point2i::new(25, 40));
+    assert_eq!(r.size(), Size2i::new(30, 40));
+    assert_eq!(Rect::from_points(r.br(), r.tl()), r);
+    assert_eq!(Rect::from_point_size(r.tl(), r.size()), r);
+
+    let f = Rect2f::new(0.0, 0.0, 3.0, 1.0);
+    assert_eq!(f.center(), Point2f::new(1.5, 0.5));
+}
+
+#[test]
+fn rect_shift_and_grow() {
+    let r = Rect::new(10, 20, 30, 40);
+    assert_eq!(r + Point2i::new(1, 2), Rect::new(11, 22, 30, 40));
+    assert_eq!(r - Point2i::new(10, 20), Rect::new(0, 0, 30, 40));
+    assert_eq!(r + Size2i::new(5, 5), Rect::new(10, 20, 35, 45));
+    assert_eq!(r - Size2i::new(5, 5), Rect::new(10, 20, 25, 35));
+}
+
+#[test]
+fn float_rect_set_operations() {
+    let a = Rect2f::new(0.0, 0.0, 2.0, 2.0);
+    let b = Rect2f::new(1.0, 1.0, 2.0, 2.0);
+    assert_eq!(a.intersection(&b), Some(Rect2f::new(1.0, 1.0, 1.0, 1.0)));
+    assert_eq!(a.union(&b), Rect2f::new(0.0, 0.0, 3.0, 3.0));
+    assert!(a.contains(Point2f::new(1.5, 0.5)));
+    assert!(!a.contains(Point2f::new(2.0, 0.5)));
+    assert_eq!(a.area(), 4.0);
+}
+
+#[test]
+fn vec_ops() {
+    let a = Vec3f::from([1.0, 0.0, 0.0]);
+    let b = Vec3f::from([0.0, 1.0, 0.0]);
+    assert_eq!(a.cross(b), Vec3f::from([0.0, 0.0, 1.0]));
+    assert_eq!(a.dot(b), 0.0);
+    assert_eq!((a + b) * 2.0, Vec3f::from([2.0, 2.0, 0.0]));
+    assert_eq!(a - b, Vec3f::from([1.0, -1.0, 0.0]));
+
+    let mut pixel = Vec3b::default();
+    pixel[2] = 255;
+    assert_eq!(pixel.0, [0, 0, 255]);
+    let array: [i32; 4] = Vec4i::from([1, 2, 3, 4]).into();
+    assert_eq!(array, [1, 2, 3, 4]);
+
+    let p: Point2f = Vec2f::from([1.0, 2.0]).into();
+    assert_eq!(p, Point2f::new(1.0, 2.0));
+    let v: Vec3i = Point3i::new(1, 2, 3).into();
+    assert_eq!(v, Vec3i::from([1, 2, 3]));
+}
+
+#[test]
+fn vec_reads_mat_pixels() {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 3).unwrap();
+    let mat = Mat::from_buffer(1, 2, mat_type, &[1, 2, 3, 4, 5, 6]).unwrap();
+    let pixel = mat.at::<Vec3b>(&[0, 1]).unwrap();
+    assert_eq!(pixel, Vec3b::from([4, 5, 6]));
+}
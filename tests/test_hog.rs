@@ -0,0 +1,104 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+use cv::objdetect::*;
+
+mod utils;
+
+#[test]
+fn default_descriptor_size() {
+    let hog = HogDescriptor::default();
+    // 7 * 15 blocks, 4 cells per block, 9 bins per cell
+    assert_eq!(hog.get_descriptor_size(), 3780);
+}
+
+#[test]
+fn compute_single_window() {
+    let mat = utils::load_avg_towncentre();
+    let hog = HogDescriptor::default();
+    let descriptors = hog
+        .compute(
+            &mat,
+            Size2i::new(8, 8),
+            Size2i::default(),
+            &[Point2i { x: 0, y: 0 }],
+        )
+        .unwrap();
+    assert_eq!(descriptors.len(), hog.get_descriptor_size());
+    assert!(descriptors.iter().any(|&v| v > 0.0));
+}
+
+#[test]
+fn compute_gradient_of_image() {
+    let mat = utils::load_lenna();
+    let hog = HogDescriptor::default();
+    let (grad, angle_ofs) = hog
+        .compute_gradient(&mat, Size2i::default(), Size2i::default())
+        .unwrap();
+    assert_eq!(grad.size().width, mat.size().width);
+    assert_eq!(grad.mat_type(), MatType::new(MatDepth::Float32, 2).unwrap());
+    assert_eq!(
+        angle_ofs.mat_type(),
+        MatType::new(MatDepth::Unsigned8, 2).unwrap()
+    );
+}
+
+#[test]
+fn custom_detector_from_coefficients() {
+    let mut hog = HogDescriptor::default();
+    let coefficients = vec![0.0; hog.get_descriptor_size() + 1];
+    let detector = SvmDetector::from_coefficients(coefficients);
+    assert_eq!(detector.len(), 3781);
+    hog.set_svm_detector(detector).unwrap();
+    assert!(hog.check_detector_size());
+}
+
+#[test]
+fn mismatched_detector_size_fails() {
+    let mut hog = HogDescriptor::default();
+    match hog.set_svm_detector(SvmDetector::from_coefficients(vec![0.0; 10])) {
+        Err(e) => match *e.kind() {
+            ErrorKind::SvmDetectorSize(3780, 10) => {}
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+    assert!(hog.check_detector_size());
+}
+
+#[test]
+fn compute_float_image_fails() {
+    let mat = utils::mat_from_f32(128, 64, &[0.0; 128 * 64]);
+    let hog = HogDescriptor::default();
+    assert!(hog
+        .compute(&mat, Size2i::new(8, 8), Size2i::default(), &[])
+        .is_err());
+    assert!(hog
+        .compute_gradient(&mat, Size2i::default(), Size2i::default())
+        .is_err());
+}
+
+#[test]
+fn save_and_load_config() {
+    let mut params = HogParams::default();
+    params.win_size = Size2i::new(32, 64);
+    params.l2hys_threshold = 0.3;
+    let hog = HogDescriptor::with_params(params);
+    assert_eq!(hog.get_descriptor_size(), 3 * 7 * 36);
+
+    for ext in &["xml", "yml"] {
+        let path = ::std::env::temp_dir().join(format!("cv-rs-hog.{}", ext));
+        hog.save(&path).unwrap();
+        let loaded = HogDescriptor::from_path(&path).unwrap();
+        assert_eq!(loaded.params.win_size.width, 32);
+        assert_eq!(loaded.params.win_size.height, 64);
+        assert!((loaded.params.l2hys_threshold - 0.3).abs() < 1e-9);
+        assert_eq!(loaded.get_descriptor_size(), hog.get_descriptor_size());
+    }
+}
+
+#[test]
+fn load_missing_config() {
+    assert!(HogDescriptor::from_path("/does/not/exist.yml").is_err());
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "image")]
+
+extern crate cv;
+extern crate image;
+
+use cv::*;
+use cv::imgproc::ColorConversionCodes;
+use image::{GrayImage, ImageBuffer, Luma, Rgb, RgbImage, Rgba, RgbaImage};
+use std::convert::TryFrom;
+
+mod utils;
+use utils::*;
+
+fn bgr_mat() -> Mat {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 3).unwrap();
+    Mat::from_buffer(1, 2, mat_type, &[1, 2, 3, 4, 5, 6]).unwrap()
+}
+
+#[test]
+fn mat_to_rgb_swaps_channels() {
+    let image = RgbImage::try_from(&bgr_mat()).unwrap();
+    assert_eq!(image.dimensions(), (2, 1));
+    assert_eq!(*image.get_pixel(0, 0), Rgb([3, 2, 1]));
+    assert_eq!(*image.get_pixel(1, 0), Rgb([6, 5, 4]));
+
+    let mat = Mat::from(&image);
+    assert_eq!(
+        mat.mat_type(),
+        MatType::new(MatDepth::Unsigned8, 3).unwrap()
+    );
+    assert_eq!(mat.at::<[u8; 3]>(&[0, 1]).unwrap(), [4, 5, 6]);
+}
+
+#[test]
+fn lenna_round_trip() {
+    let lenna = load_lenna_color();
+    let image = RgbImage::try_from(&lenna).unwrap();
+    assert_eq!(image.width() as i32, lenna.cols());
+    assert_eq!(image.height() as i32, lenna.rows());
+    let bgr = lenna.at::<[u8; 3]>(&[10, 20]).unwrap();
+    assert_eq!(image.get_pixel(20, 10).data, [bgr[2], bgr[1], bgr[0]]);
+
+    let restored = Mat::from(&image);
+    assert_eq!(
+        restored
+            .norm_diff(&lenna, NormTypes::NormInf, None)
+            .unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn rgba_round_trip() {
+    let image = RgbaImage::try_from(&bgr_mat()).unwrap();
+    assert_eq!(*image.get_pixel(0, 0), Rgba([3, 2, 1, 255]));
+
+    let mat = Mat::from(&image);
+    assert_eq!(mat.mat_type().channels(), 4);
+    assert_eq!(mat.at::<[u8; 4]>(&[0, 0]).unwrap(), [1, 2, 3, 255]);
+    assert_eq!(
+        *RgbImage::try_from(&mat).unwrap().get_pixel(0, 0),
+        Rgb([3, 2, 1])
+    );
+}
+
+#[test]
+fn gray_conversions() {
+    let lenna = load_lenna_color();
+    let expected = lenna.cvt_color(ColorConversionCodes::BGR2GRAY);
+    let image = GrayImage::try_from(&lenna).unwrap();
+    assert_eq!(
+        image.get_pixel(5, 7).data[0],
+        expected.at::<u8>(&[7, 5]).unwrap()
+    );
+
+    let mat = Mat::from(&image);
+    assert_eq!(mat.mat_type().channels(), 1);
+    assert_eq!(
+        mat.norm_diff(&expected, NormTypes::NormInf, None).unwrap(),
+        0.0
+    );
+
+    let rgb = RgbImage::try_from(&mat).unwrap();
+    let value = image.get_pixel(5, 7).data[0];
+    assert_eq!(rgb.get_pixel(5, 7).data, [value, value, value]);
+}
+
+#[test]
+fn gray16_round_trip() {
+    let mat_type = MatType::new(MatDepth::Unsigned16, 1).unwrap();
+    let mut mat = Mat::new_nd(&[2, 3], mat_type).unwrap();
+    mat.set_at(&[1, 2], 60000u16).unwrap();
+    let image = ImageBuffer::<Luma<u16>, Vec<u16>>::try_from(&mat).unwrap();
+    assert_eq!(image.get_pixel(2, 1).data[0], 60000);
+
+    let restored = Mat::from(&image);
+    assert_eq!(restored.mat_type(), mat_type);
+    assert_eq!(restored.at::<u16>(&[1, 2]).unwrap(), 60000);
+}
+
+#[test]
+fn region_of_interest() {
+    let mat = Mat::from(&GrayImage::from_fn(4, 4, |x, y| Luma([(y * 4 + x) as u8])));
+    let roi = mat.roi(Rect::new(1, 1, 2, 2));
+    let image = GrayImage::try_from(&roi).unwrap();
+    assert_eq!(image.into_raw(), vec![5, 6, 9, 10]);
+}
+
+#[test]
+fn unsupported_types() {
+    assert!(ImageBuffer::<Luma<u16>, Vec<u16>>::try_from(&bgr_mat()).is_err());
+    assert!(RgbImage::try_from(&mat_from_f32(1, 1, &[0.5])).is_err());
+
+    let two_channels = MatType::new(MatDepth::Unsigned8, 2).unwrap();
+    let mat = Mat::from_buffer(1, 1, two_channels, &[1, 2]).unwrap();
+    assert!(GrayImage::try_from(&mat).is_err());
+}
+
+#[test]
+fn empty_conversions() {
+    let image = RgbImage::try_from(&Mat::new()).unwrap();
+    assert_eq!(image.dimensions(), (0, 0));
+    assert_eq!(Mat::from(&image).total(), 0);
+    assert_eq!(Mat::from(&RgbaImage::new(0, 0)).total(), 0);
+    assert_eq!(
+        GrayImage::try_from(&Mat::new()).unwrap().dimensions(),
+        (0, 0)
+    );
+}
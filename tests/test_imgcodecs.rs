@@ -0,0 +1,637 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+use cv::imgcodecs::*;
+use cv::imgproc::ColorConversionCodes;
+use std::fs;
+use std::path::PathBuf;
+
+mod utils;
+use utils::*;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cv-rs-imgcodecs-{}", name))
+}
+
+#[test]
+fn write_and_read_png() {
+    let lenna = load_lenna();
+    let path = temp_path("lenna.png");
+    lenna
+        .write_to_path(&path, &[ImwriteParam::PngCompression(9)])
+        .unwrap();
+    let loaded = Mat::from_path(&path, ImreadModes::ImreadGrayscale).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.size(), lenna.size());
+    assert_eq!(
+        loaded.norm_diff(&lenna, NormTypes::NormInf, None).unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn write_jpeg_with_quality() {
+    let lenna = load_lenna();
+    let low = temp_path("low.jpg");
+    let high = temp_path("high.jpg");
+    lenna
+        .write_to_path(&low, &[ImwriteParam::JpegQuality(10)])
+        .unwrap();
+    lenna
+        .write_to_path(&high, &[ImwriteParam::JpegQuality(100)])
+        .unwrap();
+    let low_len = fs::metadata(&low).unwrap().len();
+    let high_len = fs::metadata(&high).unwrap().len();
+    let loaded = Mat::from_path(&low, ImreadModes::ImreadGrayscale).unwrap();
+    fs::remove_file(&low).unwrap();
+    fs::remove_file(&high).unwrap();
+    assert!(low_len < high_len);
+    assert_eq!(loaded.size(), lenna.size());
+}
+
+#[test]
+fn read_missing_file() {
+    let path = temp_path("missing.png");
+    match *Mat::from_path(&path, ImreadModes::ImreadColor)
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::ImageNotFound(ref p) => assert_eq!(*p, path),
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn read_unsupported_format() {
+    let path = temp_path("notes.txt");
+    fs::write(&path, "not an image").unwrap();
+    let result = Mat::from_path(&path, ImreadModes::ImreadColor);
+    fs::remove_file(&path).unwrap();
+    match *result.unwrap_err().kind() {
+        ErrorKind::UnsupportedImageFormat(ref p) => assert_eq!(*p, path),
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn read_corrupt_image() {
+    let path = temp_path("corrupt.png");
+    fs::write(&path, &load_lenna_as_buf()[..64]).unwrap();
+    let result = Mat::from_path(&path, ImreadModes::ImreadColor);
+    fs::remove_file(&path).unwrap();
+    match *result.unwrap_err().kind() {
+        ErrorKind::ImageDecode(ref p) => assert_eq!(*p, path),
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn invalid_paths_are_errors() {
+    assert!(Mat::from_path("lenna\0.png", ImreadModes::ImreadColor).is_err());
+    assert!(load_lenna().write_to_path("lenna\0.png", &[]).is_err());
+}
+
+#[test]
+fn write_unsupported_format() {
+    let path = temp_path("lenna.unknown");
+    match *load_lenna().write_to_path(&path, &[]).unwrap_err().kind() {
+        ErrorKind::UnsupportedImageFormat(ref p) => assert_eq!(*p, path),
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+    assert!(!path.exists());
+}
+
+#[test]
+fn reader_and_writer_queries() {
+    assert!(have_image_reader("assets/lenna.png"));
+    assert!(!have_image_reader("assets/does-not-exist.png"));
+    assert!(!have_image_reader("Cargo.toml"));
+    assert!(have_image_writer("out.png"));
+    assert!(have_image_writer("out.jpg"));
+    assert!(!have_image_writer("out.unknown"));
+    assert!(!have_image_writer("out"));
+}
+
+fn gray_mat() -> Mat {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    let data = (0..64u8).map(|v| v * 4).collect::<Vec<_>>();
+    Mat::from_buffer(8, 8, mat_type, &data).unwrap()
+}
+
+/// Encodes and decodes `mat`, returning `None` if this OpenCV build lacks an
+/// encoder for `ext`.
+fn round_trip(
+    mat: &Mat,
+    ext: &str,
+    params: &[ImwriteParam],
+    mode: ImreadModes,
+) -> Option<(Vec<u8>, Mat)> {
+    if !have_image_writer(ext) {
+        return None;
+    }
+    let buf = mat.imencode(ext, params).unwrap();
+    let decoded = Mat::imdecode(&buf, mode);
+    assert_eq!(decoded.size(), mat.size());
+    Some((buf, decoded))
+}
+
+fn assert_lossless(mat: &Mat, ext: &str, params: &[ImwriteParam]) {
+    if let Some((_, decoded)) = round_trip(mat, ext, params, ImreadModes::ImreadUnchanged) {
+        assert_eq!(decoded.mat_type(), mat.mat_type());
+        assert_eq!(
+            decoded.norm_diff(mat, NormTypes::NormInf, None).unwrap(),
+            0.0
+        );
+    }
+}
+
+#[test]
+fn param_flags_and_values() {
+    let cases = [
+        (
+            ImwriteParam::JpegQuality(80),
+            ImwriteFlags::ImwriteJpegQuality,
+            80,
+        ),
+        (
+            ImwriteParam::JpegProgressive(true),
+            ImwriteFlags::ImwriteJpegProgressive,
+            1,
+        ),
+        (
+            ImwriteParam::JpegOptimize(false),
+            ImwriteFlags::ImwriteJpegOptimize,
+            0,
+        ),
+        (
+            ImwriteParam::JpegRstInterval(65535),
+            ImwriteFlags::ImwriteJpegRstInterval,
+            65535,
+        ),
+        (
+            ImwriteParam::JpegLumaQuality(0),
+            ImwriteFlags::ImwriteJpegLumaQuality,
+            0,
+        ),
+        (
+            ImwriteParam::JpegChromaQuality(100),
+            ImwriteFlags::ImwriteJpegChromaQuality,
+            100,
+        ),
+        (
+            ImwriteParam::PngCompression(9),
+            ImwriteFlags::ImwritePngCompression,
+            9,
+        ),
+        (
+            ImwriteParam::PngStrategy(ImwritePngFlags::ImwritePngStrategyRle),
+            ImwriteFlags::ImwritePngStrategy,
+            3,
+        ),
+        (
+            ImwriteParam::PngBilevel(true),
+            ImwriteFlags::ImwritePngBilevel,
+            1,
+        ),
+        (
+            ImwriteParam::PxmBinary(false),
+            ImwriteFlags::ImwritePxmBinary,
+            0,
+        ),
+        (
+            ImwriteParam::WebpQuality(1),
+            ImwriteFlags::ImwriteWebpQuality,
+            1,
+        ),
+        (
+            ImwriteParam::ExrType(ImwriteExrTypeFlags::ImwriteExrTypeHalf),
+            ImwriteFlags::ImwriteExrType,
+            1,
+        ),
+        (
+            ImwriteParam::TiffResunit(ImwriteTiffResunit::Centimeter),
+            ImwriteFlags::ImwriteTiffResunit,
+            3,
+        ),
+        (
+            ImwriteParam::TiffXdpi(300),
+            ImwriteFlags::ImwriteTiffXdpi,
+            300,
+        ),
+        (
+            ImwriteParam::TiffYdpi(72),
+            ImwriteFlags::ImwriteTiffYdpi,
+            72,
+        ),
+        (
+            ImwriteParam::TiffCompression(ImwriteTiffCompression::Lzw),
+            ImwriteFlags::ImwriteTiffCompression,
+            5,
+        ),
+    ];
+    for &(param, flag, value) in cases.iter() {
+        assert_eq!(param.flag(), flag);
+        assert_eq!(param.value().unwrap(), value);
+    }
+}
+
+#[test]
+fn invalid_params_are_rejected() {
+    let invalid = [
+        ImwriteParam::JpegQuality(101),
+        ImwriteParam::JpegLumaQuality(101),
+        ImwriteParam::JpegChromaQuality(255),
+        ImwriteParam::PngCompression(10),
+        ImwriteParam::WebpQuality(0),
+        ImwriteParam::WebpQuality(101),
+        ImwriteParam::TiffXdpi(0),
+        ImwriteParam::TiffYdpi(0),
+    ];
+    let mat = gray_mat();
+    let path = temp_path("invalid.png");
+    for &param in invalid.iter() {
+        match *param.value().unwrap_err().kind() {
+            ErrorKind::InvalidImwriteParam(p) => assert_eq!(p, param),
+            ref e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(mat.imencode(".png", &[param]).is_err());
+        assert!(mat.write_to_path(&path, &[param]).is_err());
+        assert!(!path.exists());
+    }
+}
+
+#[test]
+fn imencode_unsupported_extension() {
+    match *gray_mat().imencode(".unknown", &[]).unwrap_err().kind() {
+        ErrorKind::UnsupportedImageFormat(_) => {}
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn imdecode_empty_buffer() {
+    assert!(Mat::imdecode_with_backend(&[], ImreadModes::ImreadColor).is_err());
+    assert_eq!(Mat::imdecode(&[], ImreadModes::ImreadColor).total(), 0);
+}
+
+#[test]
+fn jpeg_params_round_trip() {
+    let lenna = load_lenna();
+    let mode = ImreadModes::ImreadGrayscale;
+    let (low, _) = round_trip(&lenna, ".jpg", &[ImwriteParam::JpegQuality(5)], mode).unwrap();
+    let (high, _) = round_trip(&lenna, ".jpg", &[ImwriteParam::JpegQuality(100)], mode).unwrap();
+    assert!(low.len() < high.len());
+
+    let params = [
+        ImwriteParam::JpegProgressive(true),
+        ImwriteParam::JpegOptimize(true),
+        ImwriteParam::JpegRstInterval(4),
+        ImwriteParam::JpegLumaQuality(50),
+        ImwriteParam::JpegChromaQuality(40),
+    ];
+    for param in params.iter() {
+        round_trip(&lenna, ".jpg", &[*param], mode).unwrap();
+    }
+}
+
+#[test]
+fn png_params_round_trip() {
+    let lenna = load_lenna();
+    assert_lossless(&lenna, ".png", &[ImwriteParam::PngCompression(0)]);
+    assert_lossless(&lenna, ".png", &[ImwriteParam::PngCompression(9)]);
+    let fast = lenna
+        .imencode(".png", &[ImwriteParam::PngCompression(0)])
+        .unwrap();
+    let small = lenna
+        .imencode(".png", &[ImwriteParam::PngCompression(9)])
+        .unwrap();
+    assert!(small.len() < fast.len());
+
+    let strategies = [
+        ImwritePngFlags::ImwritePngStrategyDefault,
+        ImwritePngFlags::ImwritePngStrategyFiltered,
+        ImwritePngFlags::ImwritePngStrategyHuffmanOnly,
+        ImwritePngFlags::ImwritePngStrategyRle,
+        ImwritePngFlags::ImwritePngStrategyFixed,
+    ];
+    for strategy in strategies.iter() {
+        assert_lossless(&lenna, ".png", &[ImwriteParam::PngStrategy(*strategy)]);
+    }
+
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    let mat = Mat::from_buffer(2, 2, mat_type, &[0, 1, 1, 0]).unwrap();
+    let params = [ImwriteParam::PngBilevel(true)];
+    let (_, bilevel) = round_trip(&mat, ".png", &params, ImreadModes::ImreadGrayscale).unwrap();
+    let values = (0..4)
+        .map(|i| bilevel.at::<u8>(&[i / 2, i % 2]).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![0, 255, 255, 0]);
+}
+
+#[test]
+fn pxm_params_round_trip() {
+    let mat = gray_mat();
+    let mode = ImreadModes::ImreadUnchanged;
+    let (binary, _) = round_trip(&mat, ".pgm", &[ImwriteParam::PxmBinary(true)], mode).unwrap();
+    let (ascii, _) = round_trip(&mat, ".pgm", &[ImwriteParam::PxmBinary(false)], mode).unwrap();
+    assert!(binary.starts_with(b"P5"));
+    assert!(ascii.starts_with(b"P2"));
+    assert_lossless(&mat, ".pgm", &[ImwriteParam::PxmBinary(false)]);
+}
+
+#[test]
+fn webp_params_round_trip() {
+    let lenna = load_lenna();
+    let mode = ImreadModes::ImreadGrayscale;
+    if let Some((low, _)) = round_trip(&lenna, ".webp", &[ImwriteParam::WebpQuality(1)], mode) {
+        let (high, _) =
+            round_trip(&lenna, ".webp", &[ImwriteParam::WebpQuality(100)], mode).unwrap();
+        assert!(low.len() < high.len());
+    }
+}
+
+#[test]
+fn exr_params_round_trip() {
+    let mat = mat_from_f32(2, 2, &[0.5, 1.0, 2.0, -4.0]);
+    let mode = ImreadModes::ImreadUnchanged;
+    let exr_types = [
+        ImwriteExrTypeFlags::ImwriteExrTypeHalf,
+        ImwriteExrTypeFlags::ImwriteExrTypeFloat,
+    ];
+    for exr_type in exr_types.iter() {
+        if let Some((_, decoded)) =
+            round_trip(&mat, ".exr", &[ImwriteParam::ExrType(*exr_type)], mode)
+        {
+            assert_eq!(mat_to_f32(&decoded), mat_to_f32(&mat));
+        }
+    }
+}
+
+#[test]
+fn tiff_params_round_trip() {
+    let lenna = load_lenna();
+    let compressions = [
+        ImwriteTiffCompression::None,
+        ImwriteTiffCompression::Lzw,
+        ImwriteTiffCompression::Deflate,
+        ImwriteTiffCompression::PackBits,
+    ];
+    for compression in compressions.iter() {
+        assert_lossless(
+            &lenna,
+            ".tiff",
+            &[ImwriteParam::TiffCompression(*compression)],
+        );
+    }
+    let units = [
+        ImwriteTiffResunit::None,
+        ImwriteTiffResunit::Inch,
+        ImwriteTiffResunit::Centimeter,
+    ];
+    for unit in units.iter() {
+        let params = [
+            ImwriteParam::TiffResunit(*unit),
+            ImwriteParam::TiffXdpi(300),
+            ImwriteParam::TiffYdpi(150),
+        ];
+        assert_lossless(&lenna, ".tiff", &params);
+    }
+}
+
+/// Writes three distinct gray pages to a TIFF stack.
+fn write_stack(name: &str) -> (PathBuf, Vec<Mat>) {
+    let mut flipped_x = load_lenna();
+    flipped_x.flip(FlipCode::XAxis);
+    let mut flipped_y = load_lenna();
+    flipped_y.flip(FlipCode::YAxis);
+    let pages = vec![load_lenna(), flipped_x, flipped_y];
+    let path = temp_path(name);
+    let refs = pages.iter().collect::<Vec<_>>();
+    imwritemulti(
+        &path,
+        &refs,
+        &[ImwriteParam::TiffCompression(ImwriteTiffCompression::Lzw)],
+    )
+    .unwrap();
+    (path, pages)
+}
+
+fn assert_same(a: &Mat, b: &Mat) {
+    assert_eq!(a.size(), b.size());
+    assert_eq!(a.norm_diff(b, NormTypes::NormInf, None).unwrap(), 0.0);
+}
+
+#[test]
+fn tiff_stack_round_trip() {
+    let (path, pages) = write_stack("stack.tiff");
+    let mode = ImreadModes::ImreadGrayscale;
+    let count = imcount(&path, mode).unwrap();
+    let loaded = imreadmulti(&path, mode).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(loaded.len(), 3);
+    for (page, expected) in loaded.iter().zip(pages.iter()) {
+        assert_same(page, expected);
+    }
+}
+
+#[test]
+fn tiff_stack_lazy_pages() {
+    let (path, pages) = write_stack("lazy.tiff");
+    let mut iter = imread_pages(&path, ImreadModes::ImreadGrayscale).unwrap();
+    assert_eq!(iter.len(), 3);
+    assert_same(&iter.next().unwrap().unwrap(), &pages[0]);
+    assert_eq!(iter.len(), 2);
+    assert_same(&iter.nth(1).unwrap().unwrap(), &pages[2]);
+    assert!(iter.next().is_none());
+
+    let loaded = imread_pages(&path, ImreadModes::ImreadGrayscale)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.len(), 3);
+    assert_same(&loaded[1], &pages[1]);
+}
+
+#[test]
+fn single_page_images_have_one_page() {
+    let path = "assets/lenna.png";
+    assert_eq!(imcount(path, ImreadModes::ImreadColor).unwrap(), 1);
+    assert_eq!(
+        imreadmulti(path, ImreadModes::ImreadColor).unwrap().len(),
+        1
+    );
+    assert_eq!(
+        imread_pages(path, ImreadModes::ImreadColor)
+            .unwrap()
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn multi_page_errors() {
+    let missing = temp_path("missing.tiff");
+    let mode = ImreadModes::ImreadColor;
+    for result in vec![
+        imcount(&missing, mode).map(|_| ()),
+        imreadmulti(&missing, mode).map(|_| ()),
+    ] {
+        match *result.unwrap_err().kind() {
+            ErrorKind::ImageNotFound(_) => {}
+            ref e => panic!("unexpected error: {:?}", e),
+        }
+    }
+    match *imread_pages(&missing, mode).unwrap_err().kind() {
+        ErrorKind::ImageNotFound(_) => {}
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+    let lenna = load_lenna();
+    assert!(imwritemulti(temp_path("stack.unknown"), &[&lenna], &[]).is_err());
+}
+
+/// Checks the header of `mat` encoded as `ext` against the decoded image.
+fn assert_probed(mat: &Mat, ext: &str) {
+    if !have_image_writer(ext) {
+        return;
+    }
+    let buf = mat.imencode(ext, &[]).unwrap();
+    let info = ImageInfo::from_buffer(&buf).unwrap();
+    let decoded = Mat::imdecode(&buf, ImreadModes::ImreadUnchanged);
+    assert_eq!(info.size, decoded.size(), "{}", ext);
+    assert_eq!(info.mat_type, decoded.mat_type(), "{}", ext);
+    assert_eq!(info.orientation, ExifOrientation::TopLeft);
+}
+
+#[test]
+fn probe_encoded_headers() {
+    let gray = load_lenna().roi(Rect::new(0, 0, 300, 200));
+    let color = gray.cvt_color(ColorConversionCodes::GRAY2BGR);
+    let bgra = gray.cvt_color(ColorConversionCodes::GRAY2BGRA);
+    let gray16 = gray.convert_to(MatDepth::Unsigned16, 256.0, 0.0).unwrap();
+    let float = mat_from_f32(2, 3, &[0.5, 1.0, 1.5, 2.0, 2.5, 3.0]);
+
+    for ext in [".png", ".jpg", ".bmp", ".pgm", ".tiff", ".webp"].iter() {
+        assert_probed(&gray, ext);
+    }
+    for ext in [".png", ".jpg", ".bmp", ".ppm", ".tiff", ".webp"].iter() {
+        assert_probed(&color, ext);
+    }
+    for ext in [".png", ".tiff", ".webp"].iter() {
+        assert_probed(&bgra, ext);
+    }
+    for ext in [".png", ".pgm", ".tiff"].iter() {
+        assert_probed(&gray16, ext);
+    }
+    assert_probed(&float, ".tiff");
+}
+
+#[test]
+fn probe_only_needs_the_header() {
+    let buf = load_lenna_as_buf();
+    let info = ImageInfo::from_buffer(&buf[..64]).unwrap();
+    assert_eq!(info.size, Size2i::new(512, 512));
+    assert_eq!(info.mat_type, MatType::new(MatDepth::Unsigned8, 3).unwrap());
+    assert_eq!(ImageInfo::from_path("assets/lenna.png").unwrap(), info);
+
+    let info = ImageInfo::from_path("assets/Solvay_conference_1927.jpg").unwrap();
+    assert_eq!(info.mat_type.channels(), 1);
+    assert_eq!(info.size, load_physicists().size());
+}
+
+#[test]
+fn probe_errors() {
+    let buf = load_lenna_as_buf();
+    for bad in [&buf[..12], &b"not an image"[..], &[]].iter() {
+        match *ImageInfo::from_buffer(bad).unwrap_err().kind() {
+            ErrorKind::InvalidImageHeader(_) => {}
+            ref e => panic!("unexpected error: {:?}", e),
+        }
+    }
+    match *ImageInfo::from_path("Cargo.toml").unwrap_err().kind() {
+        ErrorKind::UnsupportedImageFormat(_) => {}
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+    match *ImageInfo::from_path(temp_path("missing.jpg"))
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::ImageNotFound(_) => {}
+        ref e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+/// Inserts an EXIF segment holding `orientation` after the JPEG SOI marker.
+fn with_exif_orientation(jpeg: &[u8], orientation: u8) -> Vec<u8> {
+    let mut exif = b"Exif\0\0MM\0*\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0".to_vec();
+    exif.extend_from_slice(&[orientation, 0, 0, 0, 0, 0, 0]);
+    let len = exif.len() + 2;
+    let mut buf = jpeg[..2].to_vec();
+    buf.extend_from_slice(&[0xff, 0xe1, (len >> 8) as u8, len as u8]);
+    buf.extend_from_slice(&exif);
+    buf.extend_from_slice(&jpeg[2..]);
+    buf
+}
+
+#[test]
+fn exif_orientation() {
+    let mat = load_lenna().roi(Rect::new(0, 0, 300, 200));
+    let jpeg = with_exif_orientation(&mat.imencode(".jpg", &[]).unwrap(), 6);
+    let info = ImageInfo::from_buffer(&jpeg).unwrap();
+    assert_eq!(info.orientation, ExifOrientation::RightTop);
+    assert_eq!(info.size, Size2i::new(300, 200));
+
+    let path = temp_path("rotated.jpg");
+    fs::write(&path, &jpeg).unwrap();
+    let rotated = Mat::from_path(&path, ImreadModes::ImreadColor).unwrap();
+    let mut stored = Mat::from_path(&path, ImreadModes::ImreadColorIgnoreOrientation).unwrap();
+    assert_eq!(ImageInfo::from_path(&path).unwrap(), info);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(stored.size(), Size2i::new(300, 200));
+    assert_eq!(rotated.size(), Size2i::new(200, 300));
+    stored.apply_exif_orientation(info.orientation).unwrap();
+    assert_eq!(
+        stored
+            .norm_diff(&rotated, NormTypes::NormInf, None)
+            .unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn apply_each_exif_orientation() {
+    let expected: [(ExifOrientation, i32, &[i32]); 8] = [
+        (ExifOrientation::TopLeft, 2, &[1, 2, 3, 4, 5, 6]),
+        (ExifOrientation::TopRight, 2, &[3, 2, 1, 6, 5, 4]),
+        (ExifOrientation::BottomRight, 2, &[6, 5, 4, 3, 2, 1]),
+        (ExifOrientation::BottomLeft, 2, &[4, 5, 6, 1, 2, 3]),
+        (ExifOrientation::LeftTop, 3, &[1, 4, 2, 5, 3, 6]),
+        (ExifOrientation::RightTop, 3, &[4, 1, 5, 2, 6, 3]),
+        (ExifOrientation::RightBottom, 3, &[6, 3, 5, 2, 4, 1]),
+        (ExifOrientation::LeftBottom, 3, &[3, 6, 2, 5, 1, 4]),
+    ];
+    for &(orientation, rows, values) in expected.iter() {
+        let mut mat = mat_from_i32(2, 3, &[1, 2, 3, 4, 5, 6]);
+        mat.apply_exif_orientation(orientation).unwrap();
+        assert_eq!(mat.rows(), rows, "{:?}", orientation);
+        let mut actual = Vec::new();
+        for r in 0..mat.rows() {
+            for c in 0..mat.cols() {
+                actual.push(mat.at::<i32>(&[r, c]).unwrap());
+            }
+        }
+        assert_eq!(actual, values, "{:?}", orientation);
+    }
+}
+
+#[test]
+fn exif_orientation_of_nd_mat_fails() {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    let mut mat = Mat::new_nd(&[2, 2, 2], mat_type).unwrap();
+    assert!(mat
+        .apply_exif_orientation(ExifOrientation::TopLeft)
+        .is_err());
+}
@@ -0,0 +1,152 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+
+mod utils;
+use utils::*;
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+    }
+}
+
+fn homography() -> Mat {
+    mat_from_f32(3, 3, &[2.0, 0.0, 10.0, 0.0, 3.0, 20.0, 0.0, 0.0, 1.0])
+}
+
+#[test]
+fn gemm_multiplies() {
+    let a = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = mat_from_f32(3, 2, &[1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    let c = a.gemm(&b, 1.0, None, 0.0, GemmFlags::empty()).unwrap();
+    assert_close(&mat_to_f32(&c), &[4.0, 5.0, 10.0, 11.0]);
+
+    let offset = mat_from_f32(2, 2, &[1.0, 1.0, 1.0, 1.0]);
+    let c = a
+        .gemm(&b, 2.0, Some(&offset), -1.0, GemmFlags::empty())
+        .unwrap();
+    assert_close(&mat_to_f32(&c), &[7.0, 9.0, 19.0, 21.0]);
+}
+
+#[test]
+fn gemm_with_transpose() {
+    let a = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let c = a.gemm(&a, 1.0, None, 0.0, GemmFlags::GEMM_2_T).unwrap();
+    assert_close(&mat_to_f32(&c), &[14.0, 32.0, 32.0, 77.0]);
+}
+
+#[test]
+fn gemm_mismatched_fails() {
+    let a = mat_from_f32(2, 3, &[0.0; 6]);
+    assert!(a.gemm(&a, 1.0, None, 0.0, GemmFlags::empty()).is_err());
+}
+
+#[test]
+fn transpose() {
+    let a = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let t = a.t().unwrap();
+    assert_eq!(t.rows(), 3);
+    assert_eq!(t.cols(), 2);
+    assert_close(&mat_to_f32(&t), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_close(&mat_to_f32(&a.transpose().unwrap()), &mat_to_f32(&t));
+
+    let volume = Mat::new_nd(&[2, 2, 2], a.mat_type()).unwrap();
+    assert!(volume.transpose().is_err());
+}
+
+#[test]
+fn invert_homography() {
+    let h = homography();
+    for method in &[DecompTypes::Lu, DecompTypes::Svd] {
+        let inv = h.invert(*method).unwrap();
+        let identity = h.gemm(&inv, 1.0, None, 0.0, GemmFlags::empty()).unwrap();
+        assert_close(
+            &mat_to_f32(&identity),
+            &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+        );
+    }
+}
+
+#[test]
+fn invert_cholesky() {
+    let spd = mat_from_f32(2, 2, &[4.0, 2.0, 2.0, 3.0]);
+    let inv = spd.invert(DecompTypes::Cholesky).unwrap();
+    assert_close(&mat_to_f32(&inv), &[0.375, -0.25, -0.25, 0.5]);
+}
+
+#[test]
+fn invert_singular_fails() {
+    let singular = mat_from_f32(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+    match singular.invert(DecompTypes::Lu) {
+        Err(e) => match *e.kind() {
+            ErrorKind::SingularMatrix => {}
+            ref k => panic!("unexpected error {:?}", k),
+        },
+        Ok(_) => panic!("singular matrix inverted"),
+    }
+}
+
+#[test]
+fn invert_singular_svd_is_pseudo_inverse() {
+    let singular = mat_from_f32(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+    let inv = singular.invert(DecompTypes::Svd).unwrap();
+    assert_close(&mat_to_f32(&inv), &[0.04, 0.08, 0.08, 0.16]);
+}
+
+#[test]
+fn solve_system() {
+    let a = mat_from_f32(2, 2, &[3.0, 2.0, 1.0, 2.0]);
+    let b = mat_from_f32(2, 1, &[7.0, 5.0]);
+    let x = a.solve(&b, DecompTypes::Lu).unwrap();
+    assert_close(&mat_to_f32(&x), &[1.0, 2.0]);
+
+    let singular = mat_from_f32(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+    assert!(singular.solve(&b, DecompTypes::Lu).is_err());
+
+    let wrong = mat_from_f32(3, 1, &[1.0, 2.0, 3.0]);
+    assert!(a.solve(&wrong, DecompTypes::Lu).is_err());
+}
+
+#[test]
+fn determinant_and_trace() {
+    let h = homography();
+    assert!((h.determinant().unwrap() - 6.0).abs() < 1e-6);
+    assert!((h.trace().unwrap() - 6.0).abs() < 1e-6);
+
+    let non_square = mat_from_f32(2, 3, &[0.0; 6]);
+    assert!(non_square.determinant().is_err());
+}
+
+#[test]
+fn svd_compute() {
+    let a = mat_from_f32(2, 2, &[3.0, 0.0, 0.0, -2.0]);
+    let svd = Svd::compute(&a, SvdFlags::empty()).unwrap();
+    assert_close(&mat_to_f32(&svd.w), &[3.0, 2.0]);
+    assert_eq!(svd.u.rows(), 2);
+    assert_eq!(svd.vt.cols(), 2);
+
+    let svd = Svd::compute(&a, SvdFlags::NO_UV).unwrap();
+    assert_close(&mat_to_f32(&svd.w), &[3.0, 2.0]);
+    assert_eq!(svd.u.total(), 0);
+}
+
+#[test]
+fn eigen_symmetric() {
+    let a = mat_from_f32(2, 2, &[2.0, 1.0, 1.0, 2.0]);
+    let (values, vectors) = a.eigen().unwrap();
+    assert_close(&mat_to_f32(&values), &[3.0, 1.0]);
+    let v = mat_to_f32(&vectors);
+    assert!((v[0].abs() - v[1].abs()).abs() < 1e-4);
+}
+
+#[test]
+fn mul_transposed() {
+    let a = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let aat = a.mul_transposed(false, None, 1.0).unwrap();
+    assert_close(&mat_to_f32(&aat), &[14.0, 32.0, 32.0, 77.0]);
+    let ata = a.mul_transposed(true, None, 1.0).unwrap();
+    assert_eq!(ata.rows(), 3);
+}
@@ -0,0 +1,125 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+
+mod utils;
+use utils::*;
+
+fn volume() -> Mat {
+    let mat_type = MatType::new(MatDepth::Float32, 1).unwrap();
+    Mat::new_nd(&[2, 3, 4], mat_type).unwrap()
+}
+
+#[test]
+fn new_nd_reports_shape() {
+    let mat = volume();
+    assert_eq!(mat.dims(), 3);
+    assert_eq!(mat.sizes(), vec![2, 3, 4]);
+    assert_eq!(mat.steps(), vec![48, 16, 4]);
+    assert_eq!(mat.total(), 24);
+    assert_eq!(mat.rows(), -1);
+    assert_eq!(mat.cols(), -1);
+}
+
+#[test]
+fn two_dimensional_shape() {
+    let lenna = load_lenna_color();
+    assert_eq!(lenna.dims(), 2);
+    assert_eq!(lenna.sizes(), vec![lenna.rows(), lenna.cols()]);
+    assert_eq!(lenna.steps(), vec![lenna.cols() as usize * 3, 3]);
+}
+
+#[test]
+fn new_nd_negative_size_fails() {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    assert!(Mat::new_nd(&[2, -1, 2], mat_type).is_err());
+}
+
+#[test]
+fn nd_indexed_access() {
+    let mut mat = volume();
+    for i in 0..2 {
+        for j in 0..3 {
+            for k in 0..4 {
+                mat.set_at(&[i, j, k], (i * 100 + j * 10 + k) as f32)
+                    .unwrap();
+            }
+        }
+    }
+    assert_eq!(mat.at::<f32>(&[0, 0, 0]).unwrap(), 0.0);
+    assert_eq!(mat.at::<f32>(&[1, 2, 3]).unwrap(), 123.0);
+    assert_eq!(mat.at::<f32>(&[1, 0, 2]).unwrap(), 102.0);
+}
+
+#[test]
+fn indexed_access_respects_step() {
+    let mat = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let col = mat.col_range(1, 3).unwrap();
+    assert_eq!(col.at::<f32>(&[1, 0]).unwrap(), 5.0);
+    assert_eq!(col.at::<f32>(&[1, 1]).unwrap(), 6.0);
+}
+
+#[test]
+fn indexed_access_multichannel() {
+    let lenna = load_lenna_color();
+    let pixel = lenna.at::<[u8; 3]>(&[0, 0]).unwrap();
+    assert_eq!(pixel[0], unsafe { *lenna.data() });
+}
+
+#[test]
+fn indexed_access_out_of_range() {
+    let mat = volume();
+    match mat.at::<f32>(&[2, 0, 0]) {
+        Err(e) => match *e.kind() {
+            ErrorKind::IndexOutOfRange(ref idx) => assert_eq!(idx, &vec![2, 0, 0]),
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+    assert!(mat.at::<f32>(&[0, 0]).is_err());
+    assert!(mat.at::<f32>(&[0, -1, 0]).is_err());
+}
+
+#[test]
+fn indexed_access_empty_mat() {
+    let mut mat = Mat::new();
+    match mat.at::<u8>(&[]) {
+        Err(e) => match *e.kind() {
+            ErrorKind::IndexOutOfRange(ref idx) => assert!(idx.is_empty()),
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+    assert!(mat.set_at(&[], 1u8).is_err());
+
+    let mat_type = MatType::new(MatDepth::Float32, 1).unwrap();
+    let empty = Mat::new_nd(&[2, 0, 3], mat_type).unwrap();
+    assert!(empty.at::<f32>(&[0, 0, 0]).is_err());
+}
+
+#[test]
+fn indexed_access_wrong_element_size() {
+    let mat = volume();
+    match mat.at::<f64>(&[0, 0, 0]) {
+        Err(e) => match *e.kind() {
+            ErrorKind::ElementSizeMismatch(4, 8) => {}
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn indexed_access_wrong_element_type() {
+    let mat = volume();
+    match mat.at::<i32>(&[0, 0, 0]) {
+        Err(e) => match *e.kind() {
+            ErrorKind::UnsupportedMatType(MatDepth::Float32, 1) => {}
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+    assert!(load_lenna_color().at::<[u8; 4]>(&[0, 0]).is_err());
+    assert!(load_lenna_color().at::<u8>(&[0, 0]).is_err());
+}
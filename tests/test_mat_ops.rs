@@ -0,0 +1,162 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+
+mod utils;
+use utils::*;
+
+fn mat_to_u8(mat: &Mat) -> Vec<u8> {
+    let len = mat.total() * mat.elem_size();
+    unsafe { std::slice::from_raw_parts(mat.data(), len).to_vec() }
+}
+
+#[test]
+fn from_buffer_of_wrong_size_fails() {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    match Mat::from_buffer(2, 2, mat_type, &[0, 1, 2]) {
+        Err(e) => match *e.kind() {
+            ErrorKind::BufferSize(expected, supplied) => assert_eq!((expected, supplied), (4, 3)),
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn clone_is_deep() {
+    let mut original = small();
+    let copy = original.clone();
+    original.set_to(Scalar::all(0.0), None).unwrap();
+    assert_eq!(mat_to_f32(&copy), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(mat_to_f32(&original), vec![0.0; 6]);
+}
+
+#[test]
+fn copy_to_with_and_without_mask() {
+    let src = small();
+    let mut dst = Mat::new();
+    src.copy_to(&mut dst, None).unwrap();
+    assert_eq!(mat_to_f32(&dst), mat_to_f32(&src));
+
+    let mut dst = mat_from_f32(2, 3, &[0.0; 6]);
+    let mask = mat_from_u8(2, 3, &[1, 0, 1, 0, 1, 0]);
+    src.copy_to(&mut dst, Some(&mask)).unwrap();
+    assert_eq!(mat_to_f32(&dst), vec![1.0, 0.0, 3.0, 0.0, 5.0, 0.0]);
+}
+
+#[test]
+fn copy_to_mismatched_mask_fails() {
+    let mut dst = Mat::new();
+    let mask = mat_from_u8(1, 1, &[1]);
+    assert!(small().copy_to(&mut dst, Some(&mask)).is_err());
+}
+
+#[test]
+fn convert_to_scales_and_saturates() {
+    let converted = small()
+        .convert_to(MatDepth::Unsigned8, 100.0, -50.0)
+        .unwrap();
+    assert_eq!(
+        converted.mat_type(),
+        MatType::new(MatDepth::Unsigned8, 1).unwrap()
+    );
+    assert_eq!(mat_to_u8(&converted), vec![50, 150, 250, 255, 255, 255]);
+
+    let lenna = load_lenna_color()
+        .convert_to(MatDepth::Float32, 1.0 / 255.0, 0.0)
+        .unwrap();
+    assert_eq!(lenna.mat_type().channels(), 3);
+    assert_eq!(lenna.mat_type().depth(), MatDepth::Float32);
+}
+
+#[test]
+fn set_to_with_mask() {
+    let mut mat = mat_from_u8(1, 4, &[1, 2, 3, 4]);
+    let mask = mat_from_u8(1, 4, &[0, 1, 1, 0]);
+    mat.set_to(Scalar::all(9.0), Some(&mask)).unwrap();
+    assert_eq!(mat_to_u8(&mat), vec![1, 9, 9, 4]);
+}
+
+#[test]
+fn reshape_shares_data() {
+    let mat = small();
+    let column = mat.reshape(0, 6).unwrap();
+    assert_eq!((column.rows(), column.cols()), (6, 1));
+    assert_eq!(mat_to_f32(&column), mat_to_f32(&mat));
+
+    let three = mat.reshape(3, 0).unwrap();
+    assert_eq!((three.rows(), three.cols()), (2, 1));
+    assert_eq!(three.mat_type().channels(), 3);
+
+    assert!(mat.reshape(0, 4).is_err());
+}
+
+#[test]
+fn rows_and_cols() {
+    let mat = small();
+    assert_eq!(mat_to_f32(&mat.row(1).unwrap()), vec![4.0, 5.0, 6.0]);
+    let col = mat.col(2).unwrap();
+    assert_eq!((col.rows(), col.cols()), (2, 1));
+    assert_eq!(mat_to_f32(&col.clone()), vec![3.0, 6.0]);
+
+    let cols = mat.col_range(1, 3).unwrap();
+    assert_eq!((cols.rows(), cols.cols()), (2, 2));
+    assert_eq!(mat.row_range(0, 2).unwrap().rows(), 2);
+
+    assert!(mat.row(2).is_err());
+    assert!(mat.col(-1).is_err());
+    assert!(mat.row_range(1, 1).is_err());
+}
+
+#[test]
+fn row_views_are_writable() {
+    let mat = small();
+    let mut row = mat.row(0).unwrap();
+    row.set_to(Scalar::all(7.0), None).unwrap();
+    assert_eq!(mat_to_f32(&mat), vec![7.0, 7.0, 7.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn concat() {
+    let a = mat_from_f32(2, 1, &[1.0, 2.0]);
+    let b = mat_from_f32(2, 2, &[3.0, 4.0, 5.0, 6.0]);
+    let h = Mat::hconcat(&[&a, &b]).unwrap();
+    assert_eq!((h.rows(), h.cols()), (2, 3));
+    assert_eq!(mat_to_f32(&h), vec![1.0, 3.0, 4.0, 2.0, 5.0, 6.0]);
+
+    let v = Mat::vconcat(&[&b, &b]).unwrap();
+    assert_eq!((v.rows(), v.cols()), (4, 2));
+
+    assert!(Mat::vconcat(&[&a, &b]).is_err());
+}
+
+#[test]
+fn repeat() {
+    let mat = mat_from_f32(1, 2, &[1.0, 2.0]);
+    let tiled = mat.repeat(2, 2).unwrap();
+    assert_eq!((tiled.rows(), tiled.cols()), (2, 4));
+    assert_eq!(
+        mat_to_f32(&tiled),
+        vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0]
+    );
+}
+
+#[test]
+fn copy_make_border() {
+    let mat = mat_from_u8(1, 2, &[1, 2]);
+    let constant = mat
+        .copy_make_border(1, 0, 1, 1, BorderTypes::Constant, Scalar::all(9.0))
+        .unwrap();
+    assert_eq!((constant.rows(), constant.cols()), (2, 4));
+    assert_eq!(mat_to_u8(&constant), vec![9, 9, 9, 9, 9, 1, 2, 9]);
+
+    let replicate = mat
+        .copy_make_border(0, 0, 2, 2, BorderTypes::Replicate, Scalar::default())
+        .unwrap();
+    assert_eq!(mat_to_u8(&replicate), vec![1, 1, 1, 2, 2, 2]);
+
+    assert!(mat
+        .copy_make_border(-1, 0, 0, 0, BorderTypes::Wrap, Scalar::default())
+        .is_err());
+}
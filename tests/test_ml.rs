@@ -0,0 +1,304 @@
+extern crate cv;
+
+use cv::*;
+use cv::ml::*;
+use cv::objdetect::HogDescriptor;
+use cv::video::tracking::{TermCriteria, TermType};
+
+mod utils;
+use utils::*;
+
+/// Two well separated clusters: class 0 around (1, 1) and class 1 around
+/// (8, 8).
+fn two_clusters() -> (Vec<f32>, Vec<i32>) {
+    let mut samples = Vec::new();
+    let mut labels = Vec::new();
+    for class in 0..2 {
+        for i in 0..10 {
+            let offset = 1.0 + 7.0 * class as f32;
+            samples.push(offset + (i % 3) as f32 * 0.5);
+            samples.push(offset + (i % 4) as f32 * 0.4);
+            labels.push(class);
+        }
+    }
+    (samples, labels)
+}
+
+fn train_data() -> TrainData {
+    let (samples, labels) = two_clusters();
+    let samples = mat_from_f32(20, 2, &samples);
+    let responses = mat_from_i32(20, 1, &labels);
+    TrainData::new(&samples, &responses).unwrap()
+}
+
+fn queries() -> Mat {
+    mat_from_f32(2, 2, &[1.2, 1.3, 8.4, 8.1])
+}
+
+fn assert_separates<M: StatModel>(model: &M) {
+    let results = mat_to_f32(&model.predict(&queries()).unwrap());
+    assert_eq!(results, vec![0.0, 1.0]);
+}
+
+#[test]
+fn train_data_reports_dimensions() {
+    let data = train_data();
+    assert_eq!(data.n_samples(), 20);
+    assert_eq!(data.n_vars(), 2);
+}
+
+#[test]
+fn train_data_rejects_mismatched_responses() {
+    let samples = mat_from_f32(4, 2, &[0.0; 8]);
+    let responses = mat_from_i32(3, 1, &[0, 1, 0]);
+    assert!(TrainData::new(&samples, &responses).is_err());
+}
+
+#[test]
+fn svm_linear_classifies() {
+    let mut svm = Svm::new();
+    svm.set_type(SvmType::CSvc);
+    svm.set_kernel(SvmKernel::Linear);
+    svm.set_term_criteria(&TermCriteria::new(TermType::Count, 100, 1e-6));
+    assert!(!svm.is_trained());
+
+    svm.train(&train_data()).unwrap();
+    assert!(svm.is_trained());
+    assert!(svm.is_classifier());
+    assert_eq!(svm.var_count(), 2);
+    assert_eq!(svm.kernel(), SvmKernel::Linear);
+    assert_separates(&svm);
+}
+
+#[test]
+fn svm_trains_with_every_kernel() {
+    let kernels = [
+        SvmKernel::Linear,
+        SvmKernel::Poly,
+        SvmKernel::Rbf,
+        SvmKernel::Sigmoid,
+        SvmKernel::Chi2,
+        SvmKernel::Inter,
+    ];
+    let data = train_data();
+    for kernel in kernels.iter() {
+        let mut svm = Svm::new();
+        svm.set_kernel(*kernel);
+        svm.set_degree(2.0);
+        svm.set_gamma(0.1);
+        svm.train(&data).unwrap();
+        assert!(svm.is_trained());
+    }
+}
+
+#[test]
+fn svm_train_auto() {
+    let mut svm = Svm::new();
+    svm.train_auto(&train_data(), 5, true).unwrap();
+    assert!(svm.c() > 0.0);
+    assert_separates(&svm);
+}
+
+#[test]
+fn svm_raw_output() {
+    let mut svm = Svm::new();
+    svm.set_kernel(SvmKernel::Linear);
+    svm.train(&train_data()).unwrap();
+    let raw = mat_to_f32(
+        &svm.predict_with_flags(&queries(), PredictFlags::RawOutput)
+            .unwrap(),
+    );
+    assert_eq!(raw.len(), 2);
+    assert!(raw[0].signum() != raw[1].signum());
+}
+
+#[test]
+fn svm_predict_untrained_fails() {
+    let svm = Svm::new();
+    assert!(svm.predict(&queries()).is_err());
+}
+
+#[test]
+fn svm_save_load() {
+    let mut svm = Svm::new();
+    svm.set_kernel(SvmKernel::Rbf);
+    svm.train(&train_data()).unwrap();
+
+    let mut path = std::env::temp_dir();
+    path.push("cv_test_ml_svm.yml");
+    svm.save(&path).unwrap();
+
+    let loaded = Svm::load(&path).unwrap();
+    assert!(loaded.is_trained());
+    assert_eq!(loaded.kernel(), SvmKernel::Rbf);
+    assert_separates(&loaded);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn load_missing_model_fails() {
+    assert!(Svm::load("/nonexistent/svm.xml").is_err());
+    assert!(AnnMlp::load("/nonexistent/mlp.xml").is_err());
+}
+
+#[test]
+fn linear_svm_to_hog_detector() {
+    let mat = load_lenna();
+    let mut hog = HogDescriptor::default();
+    let locations: Vec<Point2i> = (0..6)
+        .map(|i| Point2i {
+            x: i * 64,
+            y: i * 48,
+        })
+        .collect();
+    let descriptors = hog
+        .compute(&mat, Size2i::new(8, 8), Size2i::default(), &locations)
+        .unwrap();
+    let labels = [1, -1, 1, -1, 1, -1];
+    let samples = mat_from_f32(6, hog.get_descriptor_size() as i32, &descriptors);
+    let responses = mat_from_i32(6, 1, &labels);
+
+    let mut svm = Svm::new();
+    svm.set_kernel(SvmKernel::Linear);
+    svm.train(&TrainData::new(&samples, &responses).unwrap())
+        .unwrap();
+    assert_eq!(svm.support_vectors().rows(), 1);
+    let (_, _, rho) = svm.decision_function(0);
+    assert!(rho.is_finite());
+
+    let detector = svm.to_svm_detector().unwrap();
+    assert_eq!(detector.len(), hog.get_descriptor_size() + 1);
+    hog.set_svm_detector(detector).unwrap();
+    assert!(hog.check_detector_size());
+}
+
+#[test]
+fn rbf_svm_is_not_a_hog_detector() {
+    let mut svm = Svm::new();
+    svm.set_kernel(SvmKernel::Rbf);
+    svm.train(&train_data()).unwrap();
+    assert!(svm.to_svm_detector().is_err());
+}
+
+#[test]
+fn knearest_classifies() {
+    let mut knn = KNearest::new();
+    knn.set_default_k(3);
+    knn.set_algorithm_type(KNearestAlgorithm::BruteForce);
+    knn.train(&train_data()).unwrap();
+    assert_separates(&knn);
+
+    let (results, neighbors, dist) = knn.find_nearest(&queries(), 3).unwrap();
+    assert_eq!(mat_to_f32(&results), vec![0.0, 1.0]);
+    assert_eq!((neighbors.rows(), neighbors.cols()), (2, 3));
+    assert_eq!((dist.rows(), dist.cols()), (2, 3));
+}
+
+#[test]
+fn rtrees_classifies() {
+    let mut forest = RTrees::new();
+    forest.set_max_depth(4);
+    forest.set_min_sample_count(2);
+    forest.set_calculate_var_importance(true);
+    forest.set_term_criteria(&TermCriteria::new(TermType::Count, 10, 0.0));
+    forest.train(&train_data()).unwrap();
+    assert_separates(&forest);
+    assert_eq!(forest.var_importance().total(), 2);
+}
+
+#[test]
+fn boost_classifies() {
+    let mut boost = Boost::new();
+    boost.set_boost_type(BoostType::Gentle);
+    boost.set_weak_count(10);
+    boost.set_max_depth(1);
+    boost.train(&train_data()).unwrap();
+    assert_separates(&boost);
+}
+
+#[test]
+fn logistic_regression_classifies() {
+    let (samples, labels) = two_clusters();
+    let labels: Vec<f32> = labels.iter().map(|&l| l as f32).collect();
+    let samples = mat_from_f32(20, 2, &samples);
+    let responses = mat_from_f32(20, 1, &labels);
+    let data = TrainData::new(&samples, &responses).unwrap();
+
+    let mut lr = LogisticRegression::new();
+    lr.set_learning_rate(0.01);
+    lr.set_iterations(1000);
+    lr.set_regularization(Regularization::L2);
+    lr.set_train_method(LogisticRegressionMethod::Batch);
+    lr.train(&data).unwrap();
+
+    let results = lr.predict(&queries()).unwrap();
+    assert_eq!(results.rows(), 2);
+}
+
+#[test]
+fn normal_bayes_classifies() {
+    let mut bayes = NormalBayesClassifier::new();
+    bayes.train(&train_data()).unwrap();
+    assert_separates(&bayes);
+
+    let (outputs, probs) = bayes.predict_prob(&queries()).unwrap();
+    assert_eq!(outputs.rows(), 2);
+    assert_eq!((probs.rows(), probs.cols()), (2, 2));
+}
+
+#[test]
+fn ann_mlp_learns_two_classes() {
+    let (samples, labels) = two_clusters();
+    let one_hot: Vec<f32> = labels
+        .iter()
+        .flat_map(|&l| {
+            if l == 0 {
+                vec![1.0, 0.0]
+            } else {
+                vec![0.0, 1.0]
+            }
+        })
+        .collect();
+    let samples = mat_from_f32(20, 2, &samples);
+    let responses = mat_from_f32(20, 2, &one_hot);
+    let data = TrainData::builder(&samples, &responses)
+        .response_type(ResponseType::Ordered)
+        .build()
+        .unwrap();
+
+    let mut mlp = AnnMlp::new();
+    mlp.set_layer_sizes(&[2, 4, 2]).unwrap();
+    mlp.set_activation_function(ActivationFunction::SigmoidSym, 1.0, 1.0);
+    mlp.set_train_method(AnnMlpTrainMethod::Backprop, 0.1, 0.1);
+    mlp.set_term_criteria(&TermCriteria::new(TermType::Count, 1000, 0.0));
+    mlp.train(&data).unwrap();
+
+    let outputs = mat_to_f32(&mlp.predict(&queries()).unwrap());
+    assert!(outputs[0] > outputs[1]);
+    assert!(outputs[3] > outputs[2]);
+}
+
+#[test]
+fn train_test_split_error() {
+    let mut data = train_data();
+    data.set_train_test_split_ratio(0.5, true).unwrap();
+
+    let mut knn = KNearest::new();
+    knn.set_default_k(1);
+    knn.train(&data).unwrap();
+    assert_eq!(knn.calc_error(&data, true).unwrap(), 0.0);
+}
+
+#[test]
+fn invalid_train_test_split_ratio_fails() {
+    let mut data = train_data();
+    assert!(data.set_train_test_split_ratio(1.5, true).is_err());
+    assert!(data.set_train_test_split_ratio(-0.1, false).is_err());
+}
+
+#[test]
+fn invalid_layer_sizes_fail() {
+    let mut mlp = AnnMlp::new();
+    assert!(mlp.set_layer_sizes(&[2, 0, 2]).is_err());
+    assert!(mlp.set_layer_sizes(&[0, 2]).is_err());
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "nalgebra")]
+
+extern crate cv;
+extern crate nalgebra;
+
+use cv::*;
+use cv::errors::ErrorKind;
+use nalgebra::{Matrix2x3, Matrix3};
+use std::convert::TryFrom;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn matrix3_round_trip() {
+    let matrix = Matrix3::new(500.0, 0.0, 320.0, 0.0, 500.0, 240.0, 0.0, 0.0, 1.0);
+    let mat = Mat::from(&matrix);
+    assert_eq!(mat.sizes(), vec![3, 3]);
+    assert_eq!(mat.mat_type(), MatType::new(MatDepth::Float64, 1).unwrap());
+    assert_eq!(mat.at::<f64>(&[0, 2]).unwrap(), 320.0);
+    assert_eq!(mat.at::<f64>(&[1, 2]).unwrap(), 240.0);
+    assert_eq!(Matrix3::<f64>::try_from(&mat).unwrap(), matrix);
+}
+
+#[test]
+fn matrix2x3_round_trip() {
+    let mat = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let matrix = Matrix2x3::<f32>::try_from(&mat).unwrap();
+    assert_eq!(matrix, Matrix2x3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+    assert_eq!(mat_to_f32(&Mat::from(&matrix)), mat_to_f32(&mat));
+}
+
+#[test]
+fn conversion_errors() {
+    let mat = mat_from_f32(2, 3, &[0.0; 6]);
+    assert!(Matrix2x3::<f64>::try_from(&mat).is_err());
+    match Matrix3::<f32>::try_from(&mat) {
+        Err(e) => match *e.kind() {
+            ErrorKind::ShapeMismatch(ref expected, ref supplied) => {
+                assert_eq!(expected, &vec![3, 3]);
+                assert_eq!(supplied, &vec![2, 3]);
+            }
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+}
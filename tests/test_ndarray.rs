@@ -0,0 +1,76 @@
+#![cfg(feature = "ndarray")]
+
+extern crate cv;
+extern crate ndarray;
+
+use cv::*;
+use ndarray::{arr2, arr3, Array2, Array3, ArrayView2, ArrayView3};
+use std::convert::TryFrom;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn view2_shares_data() {
+    let mat = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let view = ArrayView2::<f32>::try_from(&mat).unwrap();
+    assert_eq!(view, arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+    assert_eq!(view.as_ptr() as *const u8, mat.data());
+}
+
+#[test]
+fn view3_of_color_image() {
+    let lenna = load_lenna_color();
+    let view = ArrayView3::<u8>::try_from(&lenna).unwrap();
+    assert_eq!(
+        view.dim(),
+        (lenna.rows() as usize, lenna.cols() as usize, 3)
+    );
+    let pixel = lenna.at::<[u8; 3]>(&[10, 20]).unwrap();
+    assert_eq!(view[[10, 20, 0]], pixel[0]);
+    assert_eq!(view[[10, 20, 2]], pixel[2]);
+}
+
+#[test]
+fn view_respects_step() {
+    let mat = mat_from_i32(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    let roi = mat.roi(Rect::new(1, 1, 2, 2));
+    let view = ArrayView2::<i32>::try_from(&roi).unwrap();
+    assert_eq!(view, arr2(&[[5, 6], [8, 9]]));
+}
+
+#[test]
+fn view_requires_matching_type() {
+    let mat = mat_from_f32(1, 2, &[1.0, 2.0]);
+    assert!(ArrayView2::<f64>::try_from(&mat).is_err());
+    assert!(ArrayView2::<u8>::try_from(&load_lenna_color()).is_err());
+}
+
+#[test]
+fn array_to_mat() {
+    let array = arr2(&[[1.0f64, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    let mat = Mat::try_from(&array).unwrap();
+    assert_eq!(mat.sizes(), vec![3, 2]);
+    assert_eq!(mat.mat_type(), MatType::new(MatDepth::Float64, 1).unwrap());
+    assert_eq!(mat.at::<f64>(&[2, 1]).unwrap(), 6.0);
+
+    let transposed = Mat::try_from(&array.t()).unwrap();
+    assert_eq!(transposed.sizes(), vec![2, 3]);
+    assert_eq!(transposed.at::<f64>(&[1, 2]).unwrap(), 6.0);
+}
+
+#[test]
+fn array3_round_trip() {
+    let array = arr3(&[[[1u8, 2, 3], [4, 5, 6]]]);
+    let mat = Mat::try_from(&array).unwrap();
+    assert_eq!(
+        mat.mat_type(),
+        MatType::new(MatDepth::Unsigned8, 3).unwrap()
+    );
+    assert_eq!(mat.at::<[u8; 3]>(&[0, 1]).unwrap(), [4, 5, 6]);
+    assert_eq!(ArrayView3::<u8>::try_from(&mat).unwrap(), array);
+
+    assert!(Mat::try_from(&Array3::<u8>::zeros((2, 2, 0))).is_err());
+    let empty = Mat::try_from(&Array2::<f32>::zeros((0, 0))).unwrap();
+    assert_eq!(ArrayView2::<f32>::try_from(&empty).unwrap().len(), 0);
+}
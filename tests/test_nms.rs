@@ -0,0 +1,83 @@
+extern crate cv;
+
+use cv::*;
+use cv::objdetect::*;
+
+#[test]
+fn rect_intersection_and_union() {
+    let a = Rect::new(0, 0, 10, 10);
+    let b = Rect::new(5, 5, 10, 10);
+    assert_eq!(a.area(), 100);
+    assert_eq!(a.intersection(&b), Some(Rect::new(5, 5, 5, 5)));
+    assert_eq!(a.union(&b), Rect::new(0, 0, 15, 15));
+    assert_eq!(a.intersection(&Rect::new(10, 0, 5, 5)), None);
+    assert!((a.iou(&b) - 25.0 / 175.0).abs() < 1e-9);
+    assert_eq!(a.iou(&a), 1.0);
+}
+
+#[test]
+fn rect_contains_and_clip() {
+    let r = Rect::new(2, 2, 4, 4);
+    assert!(r.contains(Point2i { x: 2, y: 2 }));
+    assert!(r.contains(Point2i { x: 5, y: 5 }));
+    assert!(!r.contains(Point2i { x: 6, y: 5 }));
+
+    let size = Size2i::new(4, 4);
+    assert_eq!(r.clip_to(size), Some(Rect::new(2, 2, 2, 2)));
+    assert_eq!(
+        Rect::new(-2, -2, 4, 4).clip_to(size),
+        Some(Rect::new(0, 0, 2, 2))
+    );
+    assert_eq!(Rect::new(5, 5, 4, 4).clip_to(size), None);
+}
+
+#[test]
+fn nms_drops_overlapping_detections() {
+    let detections = vec![
+        (Rect::new(0, 0, 10, 10), 0.5),
+        (Rect::new(1, 1, 10, 10), 0.9),
+        (Rect::new(50, 50, 10, 10), 0.7),
+    ];
+    let kept = nms(&detections, 0.5);
+    assert_eq!(kept, vec![detections[1], detections[2]]);
+}
+
+#[test]
+fn soft_nms_decays_overlapping_detections() {
+    let detections = vec![
+        (Rect::new(0, 0, 10, 10), 0.8),
+        (Rect::new(1, 1, 10, 10), 0.9),
+        (Rect::new(50, 50, 10, 10), 0.7),
+    ];
+    let kept = soft_nms(&detections, 0.5, 0.001);
+    assert_eq!(kept.len(), 3);
+    assert_eq!(kept[0], detections[1]);
+    assert_eq!(kept[1], detections[2]);
+    assert!(kept[2].1 < 0.8);
+
+    let kept = soft_nms(&detections, 0.5, 0.5);
+    assert_eq!(kept.len(), 2);
+}
+
+#[test]
+fn group_similar_rectangles() {
+    let rects = vec![
+        Rect::new(10, 10, 100, 100),
+        Rect::new(12, 12, 100, 100),
+        Rect::new(11, 9, 100, 100),
+        Rect::new(300, 300, 50, 50),
+    ];
+    let grouped = group_rectangles(&rects, 1, 0.2);
+    assert_eq!(grouped.len(), 1);
+    assert_eq!(grouped[0].1, 3.0);
+}
+
+#[test]
+fn group_rectangles_meanshift_mismatched_scales_fail() {
+    let detections = vec![
+        (Rect::new(10, 10, 64, 128), 1.0),
+        (Rect::new(12, 10, 64, 128), 1.0),
+    ];
+    let grouped = group_rectangles_meanshift(&detections, &[1.0], 0.0, Size2i::new(64, 128));
+    assert!(grouped.is_err());
+}
@@ -23,3 +23,14 @@ fn detect_lenna() {
         2,
     ));
 }
+
+#[test]
+fn cascade_reports_confidence() {
+    let mat = load_lenna();
+    let cascade = load_frontal_face();
+    let result = cascade.detect_with_meta(&mat);
+    assert_eq!(result.len(), 1);
+    assert!(result[0].score > 0.0);
+    assert_eq!(result[0].class_id, 0);
+    assert_eq!(result[0].label, None);
+}
@@ -0,0 +1,220 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+use cv::objdetect::HogParams;
+use cv::persistence::*;
+use cv::video::tracking::{TermCriteria, TermType};
+
+mod utils;
+use utils::*;
+
+const FORMATS: [FileStorageFormat; 3] = [
+    FileStorageFormat::Xml,
+    FileStorageFormat::Yaml,
+    FileStorageFormat::Json,
+];
+
+fn round_trip<F>(format: FileStorageFormat, write: F) -> FileStorage
+where
+    F: FnOnce(&mut FileStorage),
+{
+    let mut fs = FileStorage::in_memory(format).unwrap();
+    write(&mut fs);
+    let content = fs.release_and_get_string().unwrap();
+    FileStorage::from_memory(&content).unwrap()
+}
+
+#[test]
+fn numbers_and_strings() {
+    for &format in &FORMATS {
+        let fs = round_trip(format, |fs| {
+            fs.write("count", &42).unwrap();
+            fs.write("ratio", &0.25).unwrap();
+            fs.write("enabled", &true).unwrap();
+            fs.write("label", &"lenna".to_string()).unwrap();
+        });
+        assert_eq!(fs.read::<i32>("count").unwrap(), 42);
+        assert_eq!(fs.read::<f64>("ratio").unwrap(), 0.25);
+        assert_eq!(fs.read::<f32>("ratio").unwrap(), 0.25);
+        assert!(fs.read::<bool>("enabled").unwrap());
+        assert_eq!(fs.read::<String>("label").unwrap(), "lenna");
+        assert_eq!(fs.get("count").unwrap().node_type(), FileNodeType::Int);
+        assert_eq!(fs.get("label").unwrap().node_type(), FileNodeType::String);
+    }
+}
+
+#[test]
+fn sequences_and_maps() {
+    for &format in &FORMATS {
+        let fs = round_trip(format, |fs| {
+            fs.write("values", &vec![1, 2, 3]).unwrap();
+            fs.start_map("camera").unwrap();
+            fs.write("id", &7).unwrap();
+            fs.write("name", &"left".to_string()).unwrap();
+            fs.end_struct().unwrap();
+        });
+        assert_eq!(fs.read::<Vec<i32>>("values").unwrap(), vec![1, 2, 3]);
+
+        let camera = fs.get("camera").unwrap();
+        assert_eq!(camera.node_type(), FileNodeType::Map);
+        assert_eq!(camera.len(), 2);
+        assert_eq!(camera.keys(), vec!["id".to_string(), "name".to_string()]);
+        assert_eq!(camera.read_key::<i32>("id").unwrap(), 7);
+        assert_eq!(camera.get("id").unwrap().iter().count(), 0);
+        assert_eq!(camera.read_key_or("missing", 5).unwrap(), 5);
+        assert!(camera.get("missing").is_none());
+    }
+}
+
+#[test]
+fn mat_round_trip() {
+    let mat = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.5, 6.0]);
+    for &format in &FORMATS {
+        let fs = round_trip(format, |fs| fs.write("m", &mat).unwrap());
+        let restored: Mat = fs.read("m").unwrap();
+        assert_eq!(restored.size(), mat.size());
+        assert_eq!(mat_to_f32(&restored), mat_to_f32(&mat));
+    }
+}
+
+#[test]
+fn geometry_round_trip() {
+    let rotated = RotatedRect::new(Point2f::new(1.5, 2.0), Size2f::new(3.0, 4.0), 30.0);
+    for &format in &FORMATS {
+        let fs = round_trip(format, |fs| {
+            fs.write("point", &Point2i::new(1, -2)).unwrap();
+            fs.write("point3", &Point3d::new(0.5, 1.0, 1.5)).unwrap();
+            fs.write("size", &Size2f::new(2.5, 3.0)).unwrap();
+            fs.write("rect", &Rect::new(1, 2, 3, 4)).unwrap();
+            fs.write("rotated", &rotated).unwrap();
+            fs.write("scalar", &Scalar::new(1.0, 2.0, 3.0, 4.0))
+                .unwrap();
+            fs.write("vec", &Vec3b::from([1, 2, 3])).unwrap();
+        });
+        assert_eq!(fs.read::<Point2i>("point").unwrap(), Point2i::new(1, -2));
+        assert_eq!(
+            fs.read::<Point3d>("point3").unwrap(),
+            Point3d::new(0.5, 1.0, 1.5)
+        );
+        assert_eq!(fs.read::<Size2f>("size").unwrap(), Size2f::new(2.5, 3.0));
+        assert_eq!(fs.read::<Rect>("rect").unwrap(), Rect::new(1, 2, 3, 4));
+        assert_eq!(fs.read::<RotatedRect>("rotated").unwrap(), rotated);
+        assert_eq!(
+            fs.read::<Scalar>("scalar").unwrap(),
+            Scalar::new(1.0, 2.0, 3.0, 4.0)
+        );
+        assert_eq!(fs.read::<Vec3b>("vec").unwrap(), Vec3b::from([1, 2, 3]));
+        assert_eq!(fs.read::<Vec<i32>>("rect").unwrap(), vec![1, 2, 3, 4]);
+    }
+}
+
+#[test]
+fn hog_params_round_trip() {
+    let mut params = HogParams::default();
+    params.win_size = Size2i::new(48, 96);
+    params.gamma_correction = true;
+    params.scale = 1.2;
+    params.group_threshold = 0;
+    for &format in &FORMATS {
+        let fs = round_trip(format, |fs| fs.write("hog", &params).unwrap());
+        let restored: HogParams = fs.read("hog").unwrap();
+        assert_eq!(restored.win_size, params.win_size);
+        assert_eq!(restored.cell_size, params.cell_size);
+        assert_eq!(restored.gamma_correction, true);
+        assert_eq!(restored.nlevels, params.nlevels);
+        assert_eq!(restored.scale, 1.2);
+        assert_eq!(restored.group_threshold, 0);
+    }
+}
+
+#[test]
+fn hog_params_from_cxx_file() {
+    let yml = "%YAML:1.0\n---\nhog:\n   winSize: [ 32, 64 ]\n   nbins: 12\n";
+    let fs = FileStorage::from_memory(yml).unwrap();
+    let params: HogParams = fs.read("hog").unwrap();
+    assert_eq!(params.win_size, Size2i::new(32, 64));
+    assert_eq!(params.nbins, 12);
+    assert_eq!(params.block_size, HogParams::default().block_size);
+
+    let yml = "%YAML:1.0\n---\nhog:\n   nlevels: -1\n";
+    let fs = FileStorage::from_memory(yml).unwrap();
+    assert!(fs.read::<HogParams>("hog").is_err());
+}
+
+#[test]
+fn term_criteria_round_trip() {
+    let fs = round_trip(FileStorageFormat::Yaml, |fs| {
+        fs.write("both", &TermCriteria::new(TermType::CountEps, 10, 0.5))
+            .unwrap();
+        fs.write("count", &TermCriteria::new(TermType::Count, 20, 0.0))
+            .unwrap();
+    });
+    let both: TermCriteria = fs.read("both").unwrap();
+    assert_eq!(both.term_type(), TermType::CountEps);
+    assert_eq!(both.max_count(), 10);
+    assert_eq!(both.epsilon(), 0.5);
+
+    let count: TermCriteria = fs.read("count").unwrap();
+    assert_eq!(count.term_type(), TermType::Count);
+    assert_eq!(count.max_count(), 20);
+    assert!(fs.get("count").unwrap().get("epsilon").is_none());
+}
+
+#[test]
+fn camera_intrinsics_file() {
+    let intrinsics = CameraIntrinsics {
+        camera_matrix: mat_from_f32(3, 3, &[500.0, 0.0, 320.0, 0.0, 500.0, 240.0, 0.0, 0.0, 1.0]),
+        dist_coeffs: mat_from_f32(1, 5, &[0.1, -0.2, 0.0, 0.0, 0.05]),
+        image_size: Size2i::new(640, 480),
+    };
+    for ext in &["yml", "xml", "json"] {
+        let path = std::env::temp_dir().join(format!("cv-rs-intrinsics.{}", ext));
+        intrinsics.save(&path).unwrap();
+        let restored = CameraIntrinsics::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.image_size, intrinsics.image_size);
+        assert_eq!(
+            mat_to_f32(&restored.camera_matrix),
+            mat_to_f32(&intrinsics.camera_matrix)
+        );
+        assert_eq!(
+            mat_to_f32(&restored.dist_coeffs),
+            mat_to_f32(&intrinsics.dist_coeffs)
+        );
+    }
+}
+
+#[test]
+fn file_round_trip() {
+    let path = std::env::temp_dir().join("cv-rs-persistence.yml");
+    let mut fs = FileStorage::open(&path, FileStorageMode::Write).unwrap();
+    fs.write("answer", &42).unwrap();
+    fs.release().unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("%YAML"));
+    let fs = FileStorage::open(&path, FileStorageMode::Read).unwrap();
+    assert_eq!(fs.read::<i32>("answer").unwrap(), 42);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_errors() {
+    let fs = round_trip(FileStorageFormat::Yaml, |fs| {
+        fs.write("label", &"text".to_string()).unwrap();
+        fs.write("point", &Point2i::new(1, 2)).unwrap();
+    });
+    match fs.read::<i32>("missing") {
+        Err(e) => match *e.kind() {
+            ErrorKind::MissingKey(ref key) => assert_eq!(key, "missing"),
+            _ => panic!("unexpected error {}", e),
+        },
+        Ok(_) => panic!("expected an error"),
+    }
+    assert!(fs.read::<i32>("label").is_err());
+    assert!(fs.read::<Mat>("label").is_err());
+    assert!(fs.read::<Rect>("point").is_err());
+    assert!(FileStorage::open("/nonexistent/cv-rs.yml", FileStorageMode::Read).is_err());
+}
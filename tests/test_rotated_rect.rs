@@ -0,0 +1,128 @@
+extern crate cv;
+
+use cv::*;
+use cv::imgproc::*;
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+fn axis_aligned() -> RotatedRect {
+    RotatedRect::new(Point2f::new(10.0, 10.0), Size2f::new(4.0, 2.0), 0.0)
+}
+
+#[test]
+fn new_and_area() {
+    let rect = axis_aligned();
+    assert_eq!(rect.center, Point2f::new(10.0, 10.0));
+    assert_eq!(rect.area(), 8.0);
+    let rect = RotatedRect::new(Point2f::default(), Size2f::new(3.0, 5.0), 33.0);
+    assert_eq!(rect.area(), 15.0);
+}
+
+#[test]
+fn from_points_round_trip() {
+    let rect = RotatedRect::new(Point2f::new(5.0, 7.0), Size2f::new(6.0, 2.0), 30.0);
+    let pts = rect.points();
+    let restored = RotatedRect::from_points(pts[0], pts[1], pts[2]).unwrap();
+    assert!(close(restored.center.x, 5.0) && close(restored.center.y, 7.0));
+    assert!(close(restored.area(), 12.0));
+
+    let restored_pts = restored.points();
+    for p in &pts {
+        assert!(restored_pts
+            .iter()
+            .any(|q| close(p.x, q.x) && close(p.y, q.y)));
+    }
+}
+
+#[test]
+fn from_points_requires_right_angle() {
+    let a = Point2f::new(0.0, 0.0);
+    let b = Point2f::new(4.0, 0.0);
+    assert!(RotatedRect::from_points(a, b, Point2f::new(5.0, 3.0)).is_none());
+    assert!(RotatedRect::from_points(a, a, b).is_none());
+
+    let rect = RotatedRect::from_points(a, b, Point2f::new(4.0, 2.0)).unwrap();
+    assert_eq!(rect.center, Point2f::new(2.0, 1.0));
+    assert!(close(rect.size.width, 4.0) && close(rect.size.height, 2.0));
+    assert!(close(rect.angle, 0.0));
+}
+
+#[test]
+fn bounding_rects() {
+    let rect = axis_aligned();
+    assert_eq!(rect.bounding_rect2f(), Rect2f::new(8.0, 9.0, 4.0, 2.0));
+    let bounding = rect.bounding_rect();
+    assert!(bounding.contains(Point2i::new(8, 9)));
+    assert!(bounding.contains(Point2i::new(11, 10)));
+
+    let rotated = RotatedRect::new(Point2f::default(), Size2f::new(2.0, 2.0), 45.0);
+    let r = rotated.bounding_rect2f();
+    let half_diagonal = 2.0f32.sqrt();
+    assert!(close(r.x, -half_diagonal) && close(r.width, 2.0 * half_diagonal));
+}
+
+#[test]
+fn contains() {
+    let rect = RotatedRect::new(Point2f::default(), Size2f::new(2.0, 2.0), 45.0);
+    assert!(rect.contains(Point2f::new(0.0, 0.0)));
+    assert!(rect.contains(Point2f::new(0.0, 1.3)));
+    assert!(!rect.contains(Point2f::new(0.9, 0.9)));
+    assert!(axis_aligned().contains(Point2f::new(12.0, 11.0)));
+    assert!(!axis_aligned().contains(Point2f::new(12.1, 11.0)));
+}
+
+#[test]
+fn degenerate_contains_nothing() {
+    let point = RotatedRect::new(Point2f::default(), Size2f::new(0.0, 0.0), 0.0);
+    assert!(!point.contains(Point2f::new(5.0, 5.0)));
+    assert!(!point.contains(Point2f::default()));
+
+    let segment = RotatedRect::new(Point2f::default(), Size2f::new(0.0, 2.0), 0.0);
+    assert!(!segment.contains(Point2f::new(0.0, 5.0)));
+    assert!(!segment.contains(Point2f::new(0.0, 0.5)));
+}
+
+#[test]
+fn intersection_types() {
+    let a = axis_aligned();
+    let (kind, vertices) = rotated_rectangle_intersection(&a, &a).unwrap();
+    assert_eq!(kind, RectanglesIntersectTypes::Full);
+    assert_eq!(vertices.len(), 4);
+
+    let far = RotatedRect::new(Point2f::new(100.0, 100.0), Size2f::new(4.0, 2.0), 0.0);
+    let (kind, vertices) = rotated_rectangle_intersection(&a, &far).unwrap();
+    assert_eq!(kind, RectanglesIntersectTypes::None);
+    assert!(vertices.is_empty());
+
+    let shifted = RotatedRect::new(Point2f::new(12.0, 10.0), Size2f::new(4.0, 2.0), 0.0);
+    let (kind, _) = rotated_rectangle_intersection(&a, &shifted).unwrap();
+    assert_eq!(kind, RectanglesIntersectTypes::Partial);
+}
+
+#[test]
+fn overlap_scores() {
+    let a = axis_aligned();
+    let shifted = RotatedRect::new(Point2f::new(12.0, 10.0), Size2f::new(4.0, 2.0), 0.0);
+    assert!((a.intersection_area(&shifted).unwrap() - 4.0).abs() < 1e-3);
+    assert!((a.iou(&shifted).unwrap() - 4.0 / 12.0).abs() < 1e-3);
+    assert!((a.iou(&a).unwrap() - 1.0).abs() < 1e-3);
+
+    let far = RotatedRect::new(Point2f::new(100.0, 100.0), Size2f::new(4.0, 2.0), 0.0);
+    assert_eq!(a.iou(&far).unwrap(), 0.0);
+}
+
+#[test]
+fn draw_rotated_rect() {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    let mat = Mat::from_buffer(20, 20, mat_type, &[0; 400]).unwrap();
+    let rect = RotatedRect::new(Point2f::new(10.0, 10.0), Size2f::new(8.0, 8.0), 45.0);
+    mat.draw_rotated_rect(rect, Scalar::all(255.0), 1, LineTypes::Line8)
+        .unwrap();
+    assert!(mat.sum().unwrap()[0] > 0.0);
+    assert_eq!(mat.at::<u8>(&[10, 10]).unwrap(), 0);
+    assert!(mat
+        .draw_rotated_rect(rect, Scalar::all(255.0), 0, LineTypes::Line8)
+        .is_err());
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "rust-codecs")]
+
+extern crate cv;
+
+use cv::*;
+use cv::imgcodecs::*;
+
+mod utils;
+use utils::*;
+
+fn assert_same_layout(rust: &Mat, opencv: &Mat) {
+    assert_eq!(rust.mat_type(), opencv.mat_type());
+    assert_eq!(rust.size(), opencv.size());
+}
+
+fn assert_same_pixels(rust: &Mat, opencv: &Mat) {
+    assert_same_layout(rust, opencv);
+    assert_eq!(
+        rust.norm_diff(opencv, NormTypes::NormInf, None).unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn decode_png_like_opencv() {
+    let buf = load_lenna_as_buf();
+    assert!(rust_codecs::can_decode(&buf));
+    for &mode in &[
+        ImreadModes::ImreadColor,
+        ImreadModes::ImreadUnchanged,
+        ImreadModes::ImreadGrayscale,
+    ] {
+        let rust = rust_codecs::decode(&buf, mode).unwrap();
+        let opencv = Mat::imdecode(&buf, mode);
+        if mode == ImreadModes::ImreadGrayscale {
+            // OpenCV converts to gray with its own rounding.
+            assert_same_layout(&rust, &opencv);
+        } else {
+            assert_same_pixels(&rust, &opencv);
+        }
+    }
+}
+
+#[test]
+fn decode_bmp_and_ppm_like_opencv() {
+    let lenna = load_lenna_color();
+    for ext in &[".bmp", ".ppm"] {
+        let buf = lenna.imencode(ext, &[]).unwrap();
+        let rust = rust_codecs::decode(&buf, ImreadModes::ImreadUnchanged).unwrap();
+        assert_same_pixels(&rust, &lenna);
+    }
+}
+
+#[test]
+fn decode_jpeg_like_opencv() {
+    let buf = load_lenna_color().imencode(".jpg", &[]).unwrap();
+    let rust = rust_codecs::decode(&buf, ImreadModes::ImreadColor).unwrap();
+    let opencv = Mat::imdecode(&buf, ImreadModes::ImreadColor);
+    assert_same_layout(&rust, &opencv);
+}
+
+#[test]
+fn decode_rejects_unknown_format() {
+    let buf = b"GIF89a\x01\x00\x01\x00";
+    assert!(!rust_codecs::can_decode(buf));
+    assert!(rust_codecs::decode(buf, ImreadModes::ImreadColor).is_err());
+}
+
+#[test]
+fn encode_round_trips_through_opencv() {
+    let lenna = load_lenna_color();
+    for ext in &[".png", ".bmp", ".ppm"] {
+        assert!(rust_codecs::can_encode(ext));
+        let buf = rust_codecs::encode(&lenna, ext, &[]).unwrap();
+        let decoded = Mat::imdecode(&buf, ImreadModes::ImreadUnchanged);
+        assert_same_pixels(&decoded, &lenna);
+    }
+}
+
+#[test]
+fn encode_gray_pgm() {
+    let gray = load_lenna();
+    let buf = rust_codecs::encode(&gray, ".pgm", &[ImwriteParam::PxmBinary(false)]).unwrap();
+    assert!(buf.starts_with(b"P2"));
+    let decoded = Mat::imdecode(&buf, ImreadModes::ImreadUnchanged);
+    assert_same_pixels(&decoded, &gray);
+}
+
+#[test]
+fn encode_jpeg_honors_quality() {
+    let lenna = load_lenna_color();
+    let low = rust_codecs::encode(&lenna, ".jpg", &[ImwriteParam::JpegQuality(10)]).unwrap();
+    let high = rust_codecs::encode(&lenna, ".jpg", &[ImwriteParam::JpegQuality(100)]).unwrap();
+    assert!(low.len() < high.len());
+    assert_same_layout(&Mat::imdecode(&low, ImreadModes::ImreadColor), &lenna);
+}
+
+#[test]
+fn encode_rejects_unknown_extension() {
+    assert!(!rust_codecs::can_encode(".gif"));
+    assert!(rust_codecs::encode(&load_lenna_color(), ".gif", &[]).is_err());
+}
+
+#[test]
+fn opencv_backend_is_preferred() {
+    let (mat, backend) =
+        Mat::imdecode_with_backend(&load_lenna_as_buf(), ImreadModes::ImreadColor).unwrap();
+    assert_eq!(backend, CodecBackend::OpenCv);
+    assert_eq!(mat.size(), load_lenna_color().size());
+
+    let (_, backend) = mat.imencode_with_backend(".png", &[]).unwrap();
+    assert_eq!(backend, CodecBackend::OpenCv);
+}
@@ -0,0 +1,77 @@
+extern crate cv;
+
+use cv::*;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn constructors() {
+    assert_eq!(Scalar::all(2.5), Scalar::new(2.5, 2.5, 2.5, 2.5));
+    assert_eq!(
+        Scalar::from_rgb(1.0, 2.0, 3.0),
+        Scalar::new(3.0, 2.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        Scalar::from_bgr(1.0, 2.0, 3.0),
+        Scalar::new(1.0, 2.0, 3.0, 0.0)
+    );
+    assert_eq!(Scalar::default(), Scalar::all(0.0));
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(
+        Scalar::from([1.0, 2.0, 3.0, 4.0]),
+        Scalar::new(1.0, 2.0, 3.0, 4.0)
+    );
+    assert_eq!(
+        Scalar::from((255, 128, 0)),
+        Scalar::new(255.0, 128.0, 0.0, 0.0)
+    );
+    let array: [f64; 4] = Scalar::new(1.0, 2.0, 3.0, 4.0).into();
+    assert_eq!(array, [1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn indexing() {
+    let mut s = Scalar::new(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(s[0], 1.0);
+    assert_eq!(s[3], 4.0);
+    s[1] = 0.5;
+    assert_eq!(s, Scalar::new(1.0, 0.5, 3.0, 4.0));
+}
+
+#[test]
+#[should_panic]
+fn indexing_out_of_range() {
+    let s = Scalar::default();
+    let _ = s[4];
+}
+
+#[test]
+fn arithmetic() {
+    let a = Scalar::new(1.0, 2.0, 3.0, 4.0);
+    let b = Scalar::all(0.5);
+    assert_eq!(a + b, Scalar::new(1.5, 2.5, 3.5, 4.5));
+    assert_eq!(a - b, Scalar::new(0.5, 1.5, 2.5, 3.5));
+    assert_eq!(a * 2.0, Scalar::new(2.0, 4.0, 6.0, 8.0));
+    assert_eq!(a / 2.0, Scalar::new(0.5, 1.0, 1.5, 2.0));
+    assert_eq!(-a, Scalar::new(-1.0, -2.0, -3.0, -4.0));
+}
+
+#[test]
+fn in_range_with_fractional_bounds() {
+    let mat = mat_from_f32(1, 4, &[0.1, 0.25, 0.5, 0.75]);
+    let mask = mat.in_range(Scalar::all(0.2), Scalar::all(0.6));
+    let len = mask.total();
+    let values = unsafe { std::slice::from_raw_parts(mask.data(), len) };
+    assert_eq!(values, &[0, 255, 255, 0]);
+}
+
+#[test]
+fn fractional_fill() {
+    let mut mat = mat_from_f32(1, 2, &[0.0, 0.0]);
+    mat.set_to(Scalar::all(0.25), None).unwrap();
+    assert_eq!(mat_to_f32(&mat), vec![0.25, 0.25]);
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "serde")]
+
+extern crate cv;
+extern crate serde_json;
+
+use cv::*;
+use cv::objdetect::HogParams;
+use cv::videoio::CapProp;
+
+mod utils;
+use utils::*;
+
+#[test]
+fn geometry_json() {
+    let rect = Rect::new(1, 2, 3, 4);
+    let json = serde_json::to_string(&rect).unwrap();
+    assert_eq!(json, r#"{"x":1,"y":2,"width":3,"height":4}"#);
+    assert_eq!(serde_json::from_str::<Rect>(&json).unwrap(), rect);
+
+    let point = Point2f::new(0.5, -1.5);
+    let json = serde_json::to_string(&point).unwrap();
+    assert_eq!(serde_json::from_str::<Point2f>(&json).unwrap(), point);
+
+    let size = Size2i::new(640, 480);
+    let json = serde_json::to_string(&size).unwrap();
+    assert_eq!(json, r#"{"width":640,"height":480}"#);
+    assert_eq!(serde_json::from_str::<Size2i>(&json).unwrap(), size);
+
+    let rect = Rect2f::new(0.5, 1.0, 2.0, 4.0);
+    let json = serde_json::to_string(&rect).unwrap();
+    assert_eq!(serde_json::from_str::<Rect2f>(&json).unwrap(), rect);
+
+    let rotated = RotatedRect::new(Point2f::new(1.0, 2.0), Size2f::new(3.0, 4.0), 45.0);
+    let json = serde_json::to_string(&rotated).unwrap();
+    assert_eq!(serde_json::from_str::<RotatedRect>(&json).unwrap(), rotated);
+}
+
+#[test]
+fn scalar_json() {
+    let scalar = Scalar::new(1.0, 2.0, 3.0, 0.5);
+    let json = serde_json::to_string(&scalar).unwrap();
+    assert_eq!(json, "[1.0,2.0,3.0,0.5]");
+    assert_eq!(serde_json::from_str::<Scalar>(&json).unwrap(), scalar);
+}
+
+#[test]
+fn mat_type_json() {
+    let mat_type = MatType::new(MatDepth::Float32, 3).unwrap();
+    let json = serde_json::to_string(&mat_type).unwrap();
+    assert_eq!(json, r#"{"depth":"Float32","channels":3}"#);
+    assert_eq!(serde_json::from_str::<MatType>(&json).unwrap(), mat_type);
+    assert!(serde_json::from_str::<MatType>(r#"{"depth":"Float32","channels":600}"#).is_err());
+    assert!(serde_json::from_str::<MatDepth>(r#""Float16""#).is_err());
+}
+
+#[test]
+fn hog_params_and_cap_prop_json() {
+    let mut params = HogParams::default();
+    params.win_size = Size2i::new(48, 96);
+    params.use_meanshift_grouping = true;
+    let json = serde_json::to_string(&params).unwrap();
+    let restored: HogParams = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.win_size, params.win_size);
+    assert_eq!(restored.scale, params.scale);
+    assert!(restored.use_meanshift_grouping);
+
+    let json = serde_json::to_string(&CapProp::FrameWidth).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CapProp>(&json).unwrap(),
+        CapProp::FrameWidth
+    );
+}
+
+#[test]
+fn mat_round_trip() {
+    let mat = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let json = serde_json::to_string(&mat).unwrap();
+    let restored: Mat = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.size(), mat.size());
+    assert_eq!(restored.mat_type(), mat.mat_type());
+    assert_eq!(mat_to_f32(&restored), mat_to_f32(&mat));
+}
+
+#[test]
+fn mat_round_trip_non_continuous() {
+    let mat = mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let roi = mat.col_range(1, 3).unwrap();
+    assert!(!roi.is_continuous());
+    let restored: Mat = serde_json::from_str(&serde_json::to_string(&roi).unwrap()).unwrap();
+    assert!(restored.is_continuous());
+    assert_eq!(mat_to_f32(&restored), vec![2.0, 3.0, 5.0, 6.0]);
+}
+
+#[test]
+fn mat_round_trip_nd_and_empty() {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    let mut mat = Mat::new_nd(&[2, 2, 2], mat_type).unwrap();
+    mat.set_at(&[1, 1, 1], 7u8).unwrap();
+    let restored: Mat = serde_json::from_str(&serde_json::to_string(&mat).unwrap()).unwrap();
+    assert_eq!(restored.sizes(), vec![2, 2, 2]);
+    assert_eq!(restored.at::<u8>(&[1, 1, 1]).unwrap(), 7);
+
+    let empty: Mat = serde_json::from_str(&serde_json::to_string(&Mat::new()).unwrap()).unwrap();
+    assert_eq!(empty.total(), 0);
+}
+
+#[test]
+fn mat_data_length_is_checked() {
+    let json = r#"{"sizes":[2,2],"mat_type":{"depth":"Unsigned8","channels":1},"data":[1,2,3]}"#;
+    assert!(serde_json::from_str::<Mat>(json).is_err());
+}
+
+#[test]
+fn mat_huge_sizes_are_rejected() {
+    let json = concat!(
+        r#"{"sizes":[2147483647,2147483647,2147483647],"#,
+        r#""mat_type":{"depth":"Float64","channels":4},"data":[1,2,3]}"#
+    );
+    assert!(serde_json::from_str::<Mat>(json).is_err());
+    let json = r#"{"sizes":[2,-2],"mat_type":{"depth":"Unsigned8","channels":1},"data":[]}"#;
+    assert!(serde_json::from_str::<Mat>(json).is_err());
+}
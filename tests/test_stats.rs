@@ -0,0 +1,185 @@
+extern crate cv;
+
+use cv::*;
+use cv::imgproc::InterpolationFlag;
+
+mod utils;
+use utils::*;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+#[test]
+fn mean_and_sum() {
+    let mat = small();
+    let mean = mat.mean(None).unwrap();
+    assert!(close(mean[0], 3.5));
+    assert_eq!(mean, Scalar::new(3.5, 0.0, 0.0, 0.0));
+    assert!(close(mat.sum().unwrap()[0], 21.0));
+}
+
+#[test]
+fn sum_of_five_channels_fails() {
+    let mat_type = MatType::new(MatDepth::Float32, 5).unwrap();
+    assert!(Mat::with_size(2, 2, mat_type).sum().is_err());
+}
+
+#[test]
+fn mean_with_mask() {
+    let mat = small();
+    let mask = mat_from_u8(2, 3, &[0, 0, 0, 1, 1, 1]);
+    let mean = mat.mean(Some(&mask)).unwrap();
+    assert!(close(mean[0], 5.0));
+}
+
+#[test]
+fn mean_with_mismatched_mask_fails() {
+    let mask = mat_from_u8(1, 2, &[1, 1]);
+    assert!(small().mean(Some(&mask)).is_err());
+}
+
+#[test]
+fn mean_std_dev() {
+    let mat = mat_from_f32(1, 4, &[2.0, 4.0, 4.0, 6.0]);
+    let (mean, stddev) = mat.mean_std_dev(None).unwrap();
+    assert!(close(mean[0], 4.0));
+    assert!(close(stddev[0], 2.0f64.sqrt()));
+}
+
+#[test]
+fn mean_of_multichannel_image() {
+    let lenna = load_lenna_color();
+    let mean = lenna.mean(None).unwrap();
+    for c in 0..3 {
+        assert!(mean[c] > 0.0 && mean[c] < 255.0);
+    }
+    assert_eq!(mean[3], 0.0);
+}
+
+#[test]
+fn min_max_loc() {
+    let mat = mat_from_f32(2, 3, &[3.0, -1.0, 2.0, 9.0, 0.0, 4.0]);
+    let (min, max, min_loc, max_loc) = mat.min_max_loc(None).unwrap();
+    assert_eq!(min, -1.0);
+    assert_eq!(max, 9.0);
+    assert_eq!((min_loc.x, min_loc.y), (1, 0));
+    assert_eq!((max_loc.x, max_loc.y), (0, 1));
+
+    let mask = mat_from_u8(2, 3, &[1, 0, 1, 0, 1, 1]);
+    let (min, max, _, max_loc) = mat.min_max_loc(Some(&mask)).unwrap();
+    assert_eq!(min, 0.0);
+    assert_eq!(max, 4.0);
+    assert_eq!((max_loc.x, max_loc.y), (2, 1));
+}
+
+#[test]
+fn min_max_loc_multichannel_fails() {
+    assert!(load_lenna_color().min_max_loc(None).is_err());
+}
+
+#[test]
+fn min_max_idx() {
+    let mat = mat_from_f32(2, 3, &[3.0, -1.0, 2.0, 9.0, 0.0, 4.0]);
+    let (min, max, min_idx, max_idx) = mat.min_max_idx(None).unwrap();
+    assert_eq!(min, -1.0);
+    assert_eq!(max, 9.0);
+    assert_eq!(min_idx, vec![0, 1]);
+    assert_eq!(max_idx, vec![1, 0]);
+}
+
+#[test]
+fn norms() {
+    let mat = mat_from_f32(1, 3, &[3.0, -4.0, 0.0]);
+    assert!(close(mat.norm(NormTypes::NormL1, None).unwrap(), 7.0));
+    assert!(close(mat.norm(NormTypes::NormL2, None).unwrap(), 5.0));
+    assert!(close(mat.norm(NormTypes::NormL2Sqr, None).unwrap(), 25.0));
+    assert!(close(mat.norm(NormTypes::NormInf, None).unwrap(), 4.0));
+
+    let mask = mat_from_u8(1, 3, &[1, 0, 1]);
+    assert!(close(
+        mat.norm(NormTypes::NormL1, Some(&mask)).unwrap(),
+        3.0
+    ));
+}
+
+#[test]
+fn norm_diff() {
+    let a = mat_from_f32(1, 3, &[1.0, 2.0, 3.0]);
+    let b = mat_from_f32(1, 3, &[1.0, 4.0, 6.0]);
+    assert!(close(
+        a.norm_diff(&b, NormTypes::NormL1, None).unwrap(),
+        5.0
+    ));
+    assert!(close(
+        a.norm_diff(&b, NormTypes::NormInf, None).unwrap(),
+        3.0
+    ));
+    assert!(close(
+        a.norm_diff(&a, NormTypes::NormL2, None).unwrap(),
+        0.0
+    ));
+
+    let c = mat_from_f32(1, 2, &[1.0, 2.0]);
+    assert!(a.norm_diff(&c, NormTypes::NormL2, None).is_err());
+}
+
+#[test]
+fn reduce_to_row() {
+    let mat = small();
+    let sum = mat
+        .reduce(ReduceDimension::ToRow, ReduceTypes::Sum)
+        .unwrap();
+    assert_eq!(sum, vec![5.0, 7.0, 9.0]);
+    let avg = mat
+        .reduce(ReduceDimension::ToRow, ReduceTypes::Avg)
+        .unwrap();
+    assert_eq!(avg, vec![2.5, 3.5, 4.5]);
+    let max = mat
+        .reduce(ReduceDimension::ToRow, ReduceTypes::Max)
+        .unwrap();
+    assert_eq!(max, vec![4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn reduce_to_col() {
+    let mat = small();
+    let sum = mat
+        .reduce(ReduceDimension::ToCol, ReduceTypes::Sum)
+        .unwrap();
+    assert_eq!(sum, vec![6.0, 15.0]);
+    let min = mat
+        .reduce(ReduceDimension::ToCol, ReduceTypes::Min)
+        .unwrap();
+    assert_eq!(min, vec![1.0, 4.0]);
+}
+
+#[test]
+fn reduce_multichannel_interleaves() {
+    let lenna = load_lenna_color();
+    let cols = lenna
+        .reduce(ReduceDimension::ToCol, ReduceTypes::Max)
+        .unwrap();
+    assert_eq!(cols.len(), lenna.rows() as usize * 3);
+}
+
+#[test]
+fn psnr() {
+    let lenna = load_lenna();
+    let same = lenna.psnr(&lenna).unwrap();
+    assert!(same > 300.0);
+
+    let size = Size2i::new(lenna.cols(), lenna.rows());
+    let blurred = lenna
+        .pyr_down()
+        .resize_to(size, InterpolationFlag::InterLinear);
+    let noisy = lenna.psnr(&blurred).unwrap();
+    assert!(noisy > 10.0 && noisy < same);
+}
+
+#[test]
+fn psnr_mismatched_fails() {
+    let a = mat_from_u8(1, 2, &[1, 2]);
+    let b = mat_from_u8(1, 3, &[1, 2, 3]);
+    assert!(a.psnr(&b).is_err());
+}
@@ -0,0 +1,172 @@
+extern crate cv;
+
+use cv::*;
+use cv::errors::ErrorKind;
+use cv::imgproc::{ColorConversionCodes, InterpolationFlag};
+use cv::videoio::*;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+mod utils;
+use utils::*;
+
+const FRAME_COUNT: usize = 5;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("cv-rs-videoio-{}", name))
+}
+
+/// Writes a short Motion JPEG video of a shrunk lenna, and returns its path and
+/// frame size.
+fn write_video(name: &str) -> (PathBuf, Size2i) {
+    write_video_with_frames(name, FRAME_COUNT)
+}
+
+fn write_video_with_frames(name: &str, count: usize) -> (PathBuf, Size2i) {
+    let frame = load_lenna().resize_to(Size2i::new(128, 128), InterpolationFlag::InterLinear);
+    let frame = frame.cvt_color(ColorConversionCodes::GRAY2BGR);
+    let path = temp_path(name);
+    let writer = VideoWriter::new(
+        path.to_str().unwrap(),
+        fourcc('M', 'J', 'P', 'G'),
+        25.0,
+        frame.size(),
+        true,
+    );
+    assert!(writer.is_open());
+    for _ in 0..count {
+        writer.write(&frame);
+    }
+    (path, frame.size())
+}
+
+#[test]
+fn open_missing_video() {
+    let path = temp_path("missing.avi");
+    let e = VideoCapture::open(&path).unwrap_err();
+    match *e.kind() {
+        ErrorKind::VideoOpen(ref p) => assert_eq!(*p, path),
+        ref kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn frames_iterates_over_video() {
+    let (path, size) = write_video("frames.avi");
+    let cap = VideoCapture::open(&path).unwrap();
+    let frames = cap.frames().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(frames.len(), FRAME_COUNT);
+    for frame in &frames {
+        assert_eq!(frame.size(), size);
+        assert_eq!(
+            frame.mat_type(),
+            MatType::new(MatDepth::Unsigned8, 3).unwrap()
+        );
+    }
+    assert!(cap.frames().next().is_none());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_into_reuses_buffer() {
+    let (path, size) = write_video("read_into.avi");
+    let cap = VideoCapture::open(&path).unwrap();
+    let mut frame = Mat::new();
+    assert!(cap.read_into(&mut frame).unwrap());
+    assert_eq!(frame.size(), size);
+    let data = frame.data();
+
+    let mut count = 1;
+    while cap.read_into(&mut frame).unwrap() {
+        assert_eq!(frame.data(), data);
+        count += 1;
+    }
+    assert_eq!(count, FRAME_COUNT);
+    assert_eq!(frame.total(), 0);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_matches_frames() {
+    let (path, _) = write_video("read.avi");
+    let first = VideoCapture::open(&path).unwrap().read().unwrap();
+    let from_frames = VideoCapture::open(&path)
+        .unwrap()
+        .frames()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        first
+            .norm_diff(&from_frames, NormTypes::NormInf, None)
+            .unwrap(),
+        0.0
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn grab_then_retrieve() {
+    let (path, size) = write_video("grab.avi");
+    let cap = VideoCapture::open(&path).unwrap();
+    let mut count = 0;
+    while cap.grab().unwrap() {
+        let frame = cap.retrieve(0).unwrap().unwrap();
+        assert_eq!(frame.size(), size);
+        count += 1;
+    }
+    assert_eq!(count, FRAME_COUNT);
+    assert!(cap.retrieve(0).unwrap().is_none());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn wait_any_on_files() {
+    let (path, _) = write_video("wait_any.avi");
+    let cap = VideoCapture::open(&path).unwrap();
+    // Only camera backends can wait for frames.
+    let e = VideoCapture::wait_any(&[&cap], Some(Duration::from_millis(10))).unwrap_err();
+    match *e.kind() {
+        ErrorKind::Operation(_) | ErrorKind::UnsupportedOperation(_) => {}
+        ref kind => panic!("unexpected error: {:?}", kind),
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn capture_group_reads_every_capture() {
+    let (left, size) = write_video("left.avi");
+    let (right, _) = write_video_with_frames("right.avi", FRAME_COUNT - 2);
+    let group = CaptureGroup::open(&[&left, &right]).unwrap();
+    assert_eq!(group.captures().len(), 2);
+
+    let mut count = 0;
+    let mut last_timestamp = -1.0;
+    while let Some(frames) = group.read().unwrap() {
+        assert_eq!(frames.len(), 2);
+        for frame in &frames {
+            assert_eq!(frame.frame.size(), size);
+        }
+        assert_eq!(frames[0].timestamp_ms, frames[1].timestamp_ms);
+        assert!(frames[0].timestamp_ms > last_timestamp);
+        last_timestamp = frames[0].timestamp_ms;
+        count += 1;
+    }
+    // The group stops with its shortest capture.
+    assert_eq!(count, FRAME_COUNT - 2);
+    fs::remove_file(&left).unwrap();
+    fs::remove_file(&right).unwrap();
+}
+
+#[test]
+fn capture_group_fails_to_open() {
+    let (path, _) = write_video("group.avi");
+    let missing = temp_path("group-missing.avi");
+    let e = CaptureGroup::open(&[&path, &missing]).unwrap_err();
+    match *e.kind() {
+        ErrorKind::VideoOpen(ref p) => assert_eq!(*p, missing),
+        ref kind => panic!("unexpected error: {:?}", kind),
+    }
+    fs::remove_file(&path).unwrap();
+}
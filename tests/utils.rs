@@ -40,6 +40,39 @@ where
     println!("  {}: {} ms", label, total / (iteration as f64));
 }
 
+pub fn mat_from_f32(rows: i32, cols: i32, data: &[f32]) -> Mat {
+    let bytes = unsafe { ::std::slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * 4) };
+    let mat_type = MatType::new(MatDepth::Float32, 1).unwrap();
+    Mat::from_buffer(rows, cols, mat_type, bytes).unwrap()
+}
+
+pub fn mat_from_u8(rows: i32, cols: i32, data: &[u8]) -> Mat {
+    let mat_type = MatType::new(MatDepth::Unsigned8, 1).unwrap();
+    Mat::from_buffer(rows, cols, mat_type, data).unwrap()
+}
+
+pub fn mat_from_i32(rows: i32, cols: i32, data: &[i32]) -> Mat {
+    let bytes = unsafe { ::std::slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * 4) };
+    let mat_type = MatType::new(MatDepth::Signed32, 1).unwrap();
+    Mat::from_buffer(rows, cols, mat_type, bytes).unwrap()
+}
+
+/// A 2x3 matrix:
+///
+/// ```text
+/// 1 2 3
+/// 4 5 6
+/// ```
+pub fn small() -> Mat {
+    mat_from_f32(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+}
+
+pub fn mat_to_f32(mat: &Mat) -> Vec<f32> {
+    assert_eq!(mat.mat_type().depth(), MatDepth::Float32);
+    let len = mat.total() * mat.mat_type().channels() as usize;
+    unsafe { ::std::slice::from_raw_parts(mat.data() as *const f32, len).to_vec() }
+}
+
 pub fn load_physicists() -> Mat {
     let buf = load_image_as_buf("assets/Solvay_conference_1927.jpg");
     Mat::imdecode(&buf, ImreadModes::ImreadGrayscale)
@@ -55,6 +88,11 @@ pub fn load_lenna() -> Mat {
     Mat::imdecode(&buf, ImreadModes::ImreadGrayscale)
 }
 
+pub fn load_lenna_color() -> Mat {
+    let buf = load_lenna_as_buf();
+    Mat::imdecode(&buf, ImreadModes::ImreadColor)
+}
+
 pub fn load_lenna_as_buf() -> Vec<u8> {
     load_image_as_buf("assets/lenna.png")
 }